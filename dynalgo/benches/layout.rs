@@ -0,0 +1,110 @@
+//! Compares the layout engines on sparse graphs of growing sizes, the springs layout being also measured as it was before its approximations (`baseline`).
+//!
+//! Run with `cargo bench --bench layout`.
+
+#[path = "layout/baseline.rs"]
+mod baseline;
+
+use baseline::BaselineSprings;
+use dynalgo::graph::layout::{FruchtermanReingold, Layout, Placement, Springs};
+use dynalgo::graph::Graph;
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::{Duration, Instant};
+
+type Nodes = BTreeMap<u32, Placement>;
+type Adjacency = BTreeMap<u32, BTreeSet<u32>>;
+
+// A ring with pseudo-random chords (about 3 links per node).
+fn graph(count: u32) -> (Nodes, Adjacency) {
+    let mut nodes = BTreeMap::new();
+    let mut adjacency: Adjacency = BTreeMap::new();
+    let mut seed: u64 = 42;
+    for node in 0..count {
+        let placement = Placement {
+            x: 0,
+            y: 0,
+            freezed: false,
+            placed: false,
+            radius: 13,
+        };
+        nodes.insert(node, placement);
+        adjacency.entry(node).or_default();
+    }
+    for node in 0..count {
+        adjacency.get_mut(&node).unwrap().insert((node + 1) % count);
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        let other = ((seed >> 33) % count as u64) as u32;
+        if other != node {
+            adjacency.get_mut(&node).unwrap().insert(other);
+        }
+    }
+    (nodes, adjacency)
+}
+
+fn measure(name: &str, layout: &dyn Layout<u32>, count: u32) -> Duration {
+    let (nodes, adjacency) = graph(count);
+    let start = Instant::now();
+    let centers = layout.arrange(&nodes, &adjacency, None);
+    let elapsed = start.elapsed();
+    assert_eq!(centers.len(), count as usize);
+    println!(
+        "{:<32} {:>6} nodes {:>10.3} s",
+        name,
+        count,
+        elapsed.as_secs_f64()
+    );
+    elapsed
+}
+
+// Builds the graph with the layout engine of `Graph` (the default one), nodes being laid out once.
+fn measure_default(count: u32) -> Duration {
+    let (_, adjacency) = graph(count);
+    let mut config: Vec<String> = adjacency.keys().map(|node| node.to_string()).collect();
+    for (node, successors) in &adjacency {
+        for other in successors
+            .iter()
+            .filter(|other| !adjacency[other].contains(node) || node < other)
+        {
+            config.push(format!("{} - {}", node, other));
+        }
+    }
+    let config = config.join(", ");
+    let start = Instant::now();
+    let mut graph = Graph::<u32>::default();
+    graph.from_str(&config);
+    let elapsed = start.elapsed();
+    assert_eq!(graph.nodes().len(), count as usize);
+    println!(
+        "{:<32} {:>6} nodes {:>10.3} s",
+        "graph (default engine)",
+        count,
+        elapsed.as_secs_f64()
+    );
+    elapsed
+}
+
+fn main() {
+    let exact = FruchtermanReingold {
+        theta: 0.,
+        ..FruchtermanReingold::default()
+    };
+    let barnes_hut = FruchtermanReingold::default();
+
+    for count in [100, 500, 1000, 2000] {
+        measure_default(count);
+    }
+    for count in [50, 100, 200] {
+        measure("springs baseline", &BaselineSprings, count);
+    }
+    for count in [50, 100, 200, 500, 1000] {
+        measure("springs", &Springs, count);
+    }
+    for count in [100, 500, 1000] {
+        measure("fruchterman-reingold exact", &exact, count);
+    }
+    for count in [100, 500, 1000, 2000, 5000] {
+        measure("fruchterman-reingold barnes-hut", &barnes_hut, count);
+    }
+}
@@ -0,0 +1,286 @@
+//! The springs layout engine as it was before the Barnes-Hut and grid approximations, measured as the baseline of the benchmark.
+//!
+//! Every pair of nodes is linked by a spring, and every move is checked against all the nodes, so that its cost grows as the cube of the nodes count.
+//! It is only measured up to 200 nodes: 500 nodes take about 400 s in release mode, against about 2 s for the current springs layout.
+
+use dynalgo::graph::layout::{Layout, Placement};
+use dynalgo::graph::NodeId;
+use std::cmp::max;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::f64::consts::PI;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BaselineSprings;
+
+impl<N: NodeId> Layout<N> for BaselineSprings {
+    fn arrange(
+        &self,
+        nodes: &BTreeMap<N, Placement>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        _seed: Option<u64>,
+    ) -> BTreeMap<N, (i32, i32)> {
+        let mut adja: BTreeMap<N, BTreeSet<N>> = adjacency.clone();
+        for (node_from, neighbors) in adjacency {
+            for node_to in neighbors {
+                adja.get_mut(node_to).unwrap().insert(node_from.clone());
+            }
+        }
+
+        if adja.is_empty() {
+            return BTreeMap::new();
+        }
+
+        let distance = |x1: i64, y1: i64, x2: i64, y2: i64| {
+            (((x2 - x1).pow(2) + (y2 - y1).pow(2)) as f64).sqrt() as u64
+        };
+        // nodes may have distinct radii: two nodes collide when their centers are closer than the sum of their radii
+        let radii: BTreeMap<N, u64> = nodes
+            .iter()
+            .map(|(name, node)| (name.clone(), node.radius as u64))
+            .collect();
+        let gap = |node: &N, other: &N| radii[node] + radii[other];
+
+        let mut avg_x: i64 = 0;
+        let mut avg_y: i64 = 0;
+        let mut freezed_count = 0;
+        let mut radius_min = 0;
+        let mut forces = BTreeMap::new();
+        let mut positions = BTreeMap::new();
+        for (node, placement) in nodes {
+            let freezed = placement.freezed;
+            positions.insert(
+                node.clone(),
+                (freezed, placement.x as i64, placement.y as i64),
+            );
+            if freezed {
+                avg_x += placement.x as i64;
+                avg_y += placement.y as i64;
+                freezed_count += 1;
+                continue;
+            }
+            forces.insert(node.clone(), (0, 0));
+        }
+
+        if freezed_count > 0 {
+            if adja.keys().len() == freezed_count {
+                return BTreeMap::new();
+            }
+            avg_x /= freezed_count as i64;
+            avg_y /= freezed_count as i64;
+            for (freezed, x, y) in positions.values() {
+                if *freezed {
+                    radius_min = max(radius_min, distance(*x, *y, avg_x, avg_y));
+                }
+            }
+        }
+
+        let diameter = 2 * radii.values().max().copied().unwrap_or(0) as u32;
+        let perimeter = (diameter * (positions.keys().len() - freezed_count) as u32 * 2) as f64;
+        let mut radius = perimeter / (2. * PI);
+        radius = max(radius as u32, radius_min as u32 + 2 * diameter) as f64;
+        let angle = 2. * PI / (positions.keys().len() - freezed_count) as f64;
+
+        let mut i = 0;
+        for (_, (freezed, x, y)) in positions.iter_mut() {
+            if *freezed {
+                continue;
+            }
+            *x = (radius * (i as f64 * angle).cos() + avg_x as f64) as i64;
+            *y = (radius * (i as f64 * angle).sin() + avg_y as f64) as i64;
+            i += 1;
+        }
+
+        let links_count: usize = adja
+            .values()
+            .map(|n| n.len())
+            .collect::<Vec<usize>>()
+            .iter()
+            .sum::<usize>()
+            / 2;
+
+        let mut density = links_count / adja.len();
+        density = max(1, density);
+        let mut springs = HashMap::new();
+        let radius_unit = radii.values().min().copied().unwrap_or(0) as u32;
+        let length_unit = radius_unit * density as u32;
+        let k_unit = 0.01;
+        for node in positions.keys() {
+            for other in positions.keys() {
+                if *other == *node {
+                    continue;
+                }
+
+                let (length, k) = if adja.get(node).unwrap().contains(other) {
+                    match max(
+                        adja.get(node).unwrap().len(),
+                        adja.get(other).unwrap().len(),
+                    ) {
+                        1 => (5 * length_unit as i64, 30. * k_unit),
+                        2 => (6 * length_unit as i64, 25. * k_unit),
+                        3 => (7 * length_unit as i64, 20. * k_unit),
+                        4 => (8 * length_unit as i64, 15. * k_unit),
+                        _ => (9 * length_unit as i64, 10. * k_unit),
+                    }
+                } else {
+                    (
+                        max(24, adja.len() / 2) as i64 * length_unit as i64,
+                        5. * k_unit,
+                    )
+                };
+                springs.insert((node.clone(), other.clone()), (length, k));
+            }
+        }
+
+        let mut not_moved = Vec::with_capacity(adja.len());
+        for (node, neighbors) in &adja {
+            not_moved.push((node.clone(), neighbors.len()));
+        }
+        not_moved.sort_by(|(_, l1), (_, l2)| l2.cmp(l1));
+        let mut not_moved: Vec<N> = not_moved.into_iter().map(|(n, _)| n).collect();
+
+        for i in 0..(10 * adja.len()) {
+            if i % adja.len() == 0 && !not_moved.is_empty() {
+                for node in &not_moved {
+                    let (freezed, _, _) = positions.get(node).unwrap();
+                    if *freezed {
+                        continue;
+                    }
+                    let neighbors = adja.get(node).unwrap();
+                    if neighbors.is_empty() {
+                        continue;
+                    }
+
+                    let mut try_x: i64 = 0;
+                    let mut try_y: i64 = 0;
+                    if neighbors.len() == 1 {
+                        let other = neighbors.iter().next().unwrap();
+                        let (_, o_x, o_y) = positions.get(other).unwrap();
+                        let dist = distance(*o_x, *o_y, 0, 0);
+                        let length = 1.1 * gap(node, other) as f64;
+                        try_x = o_x + (length * *o_x as f64 / dist as f64) as i64;
+                        try_y = o_y + (length * *o_y as f64 / dist as f64) as i64;
+                    } else {
+                        for other in neighbors {
+                            let (_, o_x, o_y) = positions.get(other).unwrap();
+                            try_x += o_x;
+                            try_y += o_y;
+                        }
+                        try_x = 10 + try_x / neighbors.len() as i64;
+                        try_y = 10 + try_y / neighbors.len() as i64;
+                    }
+
+                    let mut collision = false;
+                    for (other, (_, o_x, o_y)) in &positions {
+                        if *other == *node {
+                            continue;
+                        }
+                        collision = distance(try_x, try_y, *o_x, *o_y) <= gap(node, other);
+                        if collision {
+                            break;
+                        }
+                    }
+                    if !collision {
+                        let (_, n_x, n_y) = positions.get_mut(node).unwrap();
+                        *n_x = try_x;
+                        *n_y = try_y;
+                    }
+                }
+                not_moved.clear();
+            }
+
+            let mut max_f = 0;
+            for (node, (n_freezed, n_x, n_y)) in &positions {
+                if *n_freezed {
+                    continue;
+                }
+
+                let mut sum_f_x = 0;
+                let mut sum_f_y = 0;
+                for (other, (_, o_x, o_y)) in &positions {
+                    if *other == *node {
+                        continue;
+                    }
+
+                    let dist = distance(*n_x, *n_y, *o_x, *o_y);
+                    assert!(dist >= gap(node, other));
+
+                    let (d_x, d_y) = (o_x - n_x, o_y - n_y);
+                    let (spring_length, k) = springs.get(&(node.clone(), other.clone())).unwrap();
+                    let force = (dist as i64 - spring_length) as f64 * k;
+                    let f_x = (force * (d_x as f64 / dist as f64)) as i64;
+                    let f_y = (force * (d_y as f64 / dist as f64)) as i64;
+                    sum_f_x += f_x;
+                    sum_f_y += f_y;
+                }
+
+                let f = distance(sum_f_x, sum_f_y, 0, 0) as i64;
+                max_f = max(max_f, f);
+
+                let (f_x, f_y) = forces.get_mut(node).unwrap();
+                *f_x = sum_f_x;
+                *f_y = sum_f_y;
+            }
+
+            let maxi = 6 * radius as i64 / adja.len() as i64;
+            if max_f > maxi {
+                let reduce = maxi as f64 / max_f as f64;
+                for (_, (f_x, f_y)) in forces.iter_mut() {
+                    *f_x = (*f_x as f64 * reduce) as i64;
+                    *f_y = (*f_y as f64 * reduce) as i64;
+                }
+            }
+
+            for (node, (f_x, f_y)) in &forces {
+                let f = distance(*f_x, *f_y, 0, 0);
+                if f <= length_unit as u64 / 10 {
+                    continue;
+                };
+                let (freezed, n_x, n_y) = positions.get(node).unwrap();
+                if *freezed {
+                    continue;
+                }
+                let mut new_x = *n_x;
+                let mut new_y = *n_y;
+                for m in (0..=4).rev() {
+                    let try_x = *n_x + ((f * m / 4) as f64 * (*f_x as f64 / f as f64)) as i64;
+                    let try_y = *n_y + ((f * m / 4) as f64 * (*f_y as f64 / f as f64)) as i64;
+                    let mut collision = false;
+                    for (other, (_, o_x, o_y)) in &positions {
+                        if *other == *node {
+                            continue;
+                        }
+                        collision = distance(try_x, try_y, *o_x, *o_y) <= gap(node, other);
+                        if collision {
+                            assert!(m != 0);
+                            break;
+                        }
+                    }
+                    if !collision {
+                        new_x = try_x;
+                        new_y = try_y;
+                        break;
+                    }
+                }
+                if *n_x != new_x || *n_y != new_y {
+                    let (_, n_x, n_y) = positions.get_mut(node).unwrap();
+                    *n_x = new_x;
+                    *n_y = new_y;
+                } else {
+                    if !not_moved.contains(node) {
+                        not_moved.push(node.clone());
+                    }
+                }
+            }
+
+            if not_moved.len() == adja.len() {
+                break;
+            }
+        }
+
+        positions
+            .into_iter()
+            .filter(|(_, (freezed, _, _))| !freezed)
+            .map(|(node, (_, x, y))| (node, (x as i32, y as i32)))
+            .collect()
+    }
+}
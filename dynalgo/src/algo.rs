@@ -2,6 +2,10 @@
 
 pub mod coloration;
 pub mod connectivity;
+pub mod dag;
 pub mod eulerian;
+pub mod flow;
+pub mod matching;
+pub mod shortest_path;
 pub mod tree;
 mod utils;
@@ -1,7 +1,9 @@
 use crate::algo::connectivity::Connectivity;
 use crate::algo::utils::color::Color;
 use crate::algo::utils::transform::Transform;
-use crate::graph::Graph;
+use crate::graph::{Graph, NodeId, Weight};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::f64::consts::PI;
 
 pub struct Coloration {}
@@ -9,9 +11,8 @@ pub struct Coloration {}
 impl Coloration {
     /// Partitions nodes in empty subgraphs.
     /// Nodes in the same subgraph can be coloured with a single color.
-    pub fn quick_partition(g: &Graph) -> (Graph, Vec<Vec<char>>) {
-        let mut cg = Graph::new();
-        cg.from_str(&g.to_string());
+    pub fn quick_partition<N: NodeId, W: Weight>(g: &Graph<N, W>) -> (Graph<N, W>, Vec<Vec<N>>) {
+        let mut cg = g.copy();
         if cg.directed() {
             cg.pause();
             Transform::undirect(&mut cg);
@@ -29,7 +30,7 @@ impl Coloration {
             for (node_from, neighbors) in &adj_list {
                 if neighbors.len() == 1 {
                     let (node_to, _) = adj_list.get(node_from).unwrap().first_key_value().unwrap();
-                    leafs_add.push((*node_from, *node_to));
+                    leafs_add.push((node_from.clone(), node_to.clone()));
                 }
             }
             if leafs_add.is_empty() {
@@ -45,12 +46,12 @@ impl Coloration {
                 for (_, neighbors) in adj_list.iter_mut() {
                     neighbors.remove(leaf);
                 }
-                cg.color_node(*leaf, Color::disabled());
+                cg.color_node(leaf.clone(), Color::disabled());
             }
             leafs.extend(leafs_add);
         }
 
-        let mut nodes: Vec<char> = adj_list.iter().map(|(k, _)| *k).collect();
+        let mut nodes: Vec<N> = adj_list.keys().cloned().collect();
         let mut matrix = Vec::new();
         for node_from in &nodes {
             let mut v = Vec::new();
@@ -73,8 +74,8 @@ impl Coloration {
 
             let mut maxi = 0;
             let mut maxi_idx = 0;
-            for j in left..=right {
-                let cnt = matrix[j][left..=right].iter().filter(|x| !*x).count();
+            for (j, row) in matrix.iter().enumerate().take(right + 1).skip(left) {
+                let cnt = row[left..=right].iter().filter(|x| !*x).count();
                 if cnt > maxi {
                     maxi = cnt;
                     maxi_idx = j;
@@ -82,12 +83,12 @@ impl Coloration {
             }
 
             matrix.swap(i, maxi_idx);
-            for k in 0..nodes.len() {
-                matrix[k].swap(i, maxi_idx);
+            for row in matrix.iter_mut() {
+                row.swap(i, maxi_idx);
             }
             nodes.swap(i, maxi_idx);
-            partition.push(nodes[i]);
-            cg.color_node(nodes[i], color);
+            partition.push(nodes[i].clone());
+            cg.color_node(nodes[i].clone(), color);
 
             while left < right {
                 while !matrix[i][left] && left < right {
@@ -98,8 +99,8 @@ impl Coloration {
                 }
                 if matrix[i][left] && !matrix[i][right] {
                     matrix.swap(left, right);
-                    for k in 0..nodes.len() {
-                        matrix[k].swap(left, right);
+                    for row in matrix.iter_mut() {
+                        row.swap(left, right);
                     }
                     nodes.swap(left, right);
                 }
@@ -131,10 +132,10 @@ impl Coloration {
                 if set.contains(neighbor) {
                     for (j, color) in colors.iter().enumerate() {
                         if i != j {
-                            partitions[j].push(*leaf);
+                            partitions[j].push(leaf.clone());
                             cg.pause();
-                            cg.color_node(*leaf, *color);
-                            cg.fill_node(*leaf, Color::disabled());
+                            cg.color_node(leaf.clone(), *color);
+                            cg.fill_node(leaf.clone(), Color::disabled());
                             cg.resume();
                             break;
                         }
@@ -147,7 +148,7 @@ impl Coloration {
         let perimeter = ((1.5 * g.node_radius() as f64) as u32
             * (nodes.len() + leafs.len() + partitions.len()) as u32
             * 2) as f64;
-        let radius = (perimeter / (2. * PI)) as f64;
+        let radius = perimeter / (2. * PI);
         let angle = 2. * PI / (nodes.len() + leafs.len() + partitions.len()) as f64;
 
         let mut i = 0;
@@ -158,7 +159,7 @@ impl Coloration {
                 let x = (radius * (i as f64 * angle).cos()) as i32;
                 let y = (radius * (i as f64 * angle).sin()) as i32;
                 i += 1;
-                cg.move_node(*node, (x, y));
+                cg.move_node(node.clone(), (x, y));
             }
             i += 1;
         }
@@ -166,4 +167,216 @@ impl Coloration {
 
         (cg, partitions)
     }
+
+    /// Colors nodes with the DSatur heuristic (most saturated node first).
+    /// Returns the number of colors used and the nodes partition by color.
+    pub fn dsatur<N: NodeId, W: Weight>(g: &Graph<N, W>) -> (Graph<N, W>, usize, Vec<Vec<N>>) {
+        let mut cg = Self::undirected(g);
+        let adj = cg.adjacency_list();
+
+        let mut coloring: BTreeMap<N, usize> = BTreeMap::new();
+        while coloring.len() < adj.len() {
+            let node = adj
+                .iter()
+                .filter(|(node, _)| !coloring.contains_key(*node))
+                .map(|(node, neighbors)| {
+                    let saturation: BTreeSet<usize> = neighbors
+                        .keys()
+                        .filter_map(|neighbor| coloring.get(neighbor).cloned())
+                        .collect();
+                    (node, saturation.len(), neighbors.len())
+                })
+                .min_by(|(_, s_a, d_a), (_, s_b, d_b)| s_b.cmp(s_a).then(d_b.cmp(d_a)))
+                .map(|(node, _, _)| node.clone())
+                .unwrap();
+            let color = Self::first_free(&adj, &coloring, &node);
+            Self::assign(&mut cg, &mut coloring, node, color);
+        }
+
+        Self::result(cg, coloring)
+    }
+
+    /// Colors nodes with the Welsh-Powell heuristic (highest degree nodes first, one color at a time).
+    /// Returns the number of colors used and the nodes partition by color.
+    pub fn welsh_powell<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> (Graph<N, W>, usize, Vec<Vec<N>>) {
+        let mut cg = Self::undirected(g);
+        let adj = cg.adjacency_list();
+        let nodes = Self::by_degree(&adj);
+
+        let mut coloring: BTreeMap<N, usize> = BTreeMap::new();
+        let mut color = 0;
+        while coloring.len() < adj.len() {
+            for node in &nodes {
+                if coloring.contains_key(node)
+                    || adj[node]
+                        .keys()
+                        .any(|neighbor| coloring.get(neighbor) == Some(&color))
+                {
+                    continue;
+                }
+                Self::assign(&mut cg, &mut coloring, node.clone(), color);
+            }
+            color += 1;
+        }
+
+        Self::result(cg, coloring)
+    }
+
+    /// Colors nodes with the minimum number of colors (chromatic number), by backtracking.
+    /// The complexity is exponential: use it on small graphs only.
+    /// Returns the number of colors used and the nodes partition by color.
+    pub fn backtracking<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> (Graph<N, W>, usize, Vec<Vec<N>>) {
+        let mut cg = Self::undirected(g);
+        let adj = cg.adjacency_list();
+        let nodes = Self::by_degree(&adj);
+
+        let mut coloring: BTreeMap<N, usize> = BTreeMap::new();
+        for count in 1..=nodes.len() {
+            if Self::backtrack(&mut cg, &adj, &nodes, count, &mut coloring) {
+                break;
+            }
+        }
+
+        Self::result(cg, coloring)
+    }
+
+    /// Colors links so that links sharing a node have different colors (greedy, at most 2 * max degree - 1 colors).
+    /// Returns the number of colors used and the links partition by color, where parallel links appear once each.
+    #[allow(clippy::type_complexity)]
+    pub fn edge_coloring<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> (Graph<N, W>, usize, Vec<Vec<(N, N)>>) {
+        let mut cg = Self::undirected(g);
+        let adj = cg.adjacency_list();
+
+        let mut counts: BTreeMap<(N, N), usize> = BTreeMap::new();
+        for (node_from, node_to, _, _) in cg.link_entries() {
+            if node_from != node_to {
+                let link = (
+                    node_from.clone().min(node_to.clone()),
+                    node_from.max(node_to),
+                );
+                *counts.entry(link).or_default() += 1;
+            }
+        }
+
+        let mut used: BTreeMap<N, BTreeSet<usize>> = BTreeMap::new();
+        let mut partitions: Vec<Vec<(N, N)>> = Vec::new();
+        for node_from in Self::by_degree(&adj) {
+            for node_to in adj[&node_from].keys() {
+                let link = (
+                    node_from.clone().min(node_to.clone()),
+                    node_from.clone().max(node_to.clone()),
+                );
+                let count = match counts.remove(&link) {
+                    Some(count) => count,
+                    None => continue,
+                };
+                for _ in 0..count {
+                    let free = |color: &usize| {
+                        [&node_from, node_to]
+                            .iter()
+                            .all(|node| !used.get(*node).is_some_and(|set| set.contains(color)))
+                    };
+                    let color = (0..).find(free).unwrap();
+                    for node in [&node_from, node_to] {
+                        used.entry(node.clone()).or_default().insert(color);
+                    }
+                    if partitions.len() <= color {
+                        partitions.resize(color + 1, Vec::new());
+                    }
+                    partitions[color].push((node_from.clone(), node_to.clone()));
+                    cg.color_link(node_from.clone(), node_to.clone(), Color::distinct(color));
+                }
+            }
+        }
+
+        (cg, partitions.len(), partitions)
+    }
+
+    fn undirected<N: NodeId, W: Weight>(g: &Graph<N, W>) -> Graph<N, W> {
+        let mut cg = g.copy();
+        if cg.directed() {
+            cg.pause();
+            Transform::undirect(&mut cg);
+            cg.resume();
+        }
+        cg
+    }
+
+    fn by_degree<N: NodeId, W: Weight>(adj: &BTreeMap<N, BTreeMap<N, W>>) -> Vec<N> {
+        let mut nodes: Vec<N> = adj.keys().cloned().collect();
+        nodes.sort_by(|a, b| adj[b].len().cmp(&adj[a].len()));
+        nodes
+    }
+
+    fn first_free<N: NodeId, W: Weight>(
+        adj: &BTreeMap<N, BTreeMap<N, W>>,
+        coloring: &BTreeMap<N, usize>,
+        node: &N,
+    ) -> usize {
+        let used: BTreeSet<usize> = adj[node]
+            .keys()
+            .filter_map(|neighbor| coloring.get(neighbor).cloned())
+            .collect();
+        (0..).find(|color| !used.contains(color)).unwrap()
+    }
+
+    fn assign<N: NodeId, W: Weight>(
+        cg: &mut Graph<N, W>,
+        coloring: &mut BTreeMap<N, usize>,
+        node: N,
+        color: usize,
+    ) {
+        cg.fill_node(node.clone(), Color::distinct(color));
+        coloring.insert(node, color);
+    }
+
+    fn backtrack<N: NodeId, W: Weight>(
+        cg: &mut Graph<N, W>,
+        adj: &BTreeMap<N, BTreeMap<N, W>>,
+        nodes: &[N],
+        count: usize,
+        coloring: &mut BTreeMap<N, usize>,
+    ) -> bool {
+        let node = match nodes.get(coloring.len()) {
+            Some(node) => node.clone(),
+            None => return true,
+        };
+        let max_used = coloring.values().max().map_or(0, |color| color + 1);
+        for color in 0..count.min(max_used + 1) {
+            if adj[&node]
+                .keys()
+                .any(|neighbor| coloring.get(neighbor) == Some(&color))
+            {
+                continue;
+            }
+            Self::assign(cg, coloring, node.clone(), color);
+            if Self::backtrack(cg, adj, nodes, count, coloring) {
+                return true;
+            }
+            coloring.remove(&node);
+            cg.fill_node(node.clone(), cg.style().node_fill);
+        }
+        false
+    }
+
+    fn result<N: NodeId, W: Weight>(
+        cg: Graph<N, W>,
+        coloring: BTreeMap<N, usize>,
+    ) -> (Graph<N, W>, usize, Vec<Vec<N>>) {
+        let mut partitions: Vec<Vec<N>> = Vec::new();
+        for (node, color) in coloring {
+            if partitions.len() <= color {
+                partitions.resize(color + 1, Vec::new());
+            }
+            partitions[color].push(node);
+        }
+
+        (cg, partitions.len(), partitions)
+    }
 }
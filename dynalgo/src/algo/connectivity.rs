@@ -1,13 +1,15 @@
 use crate::algo::utils::color::Color;
 use crate::algo::utils::transform::Transform;
-use crate::graph::Graph;
+use crate::graph::{Graph, NodeId, Weight};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
 pub struct Connectivity;
 
 impl Connectivity {
     /// Returns a list of connected components
-    pub fn components(g: &Graph) -> (Graph, Vec<Vec<char>>) {
-        let mut cg = Graph::new();
-        cg.from_str(&g.to_string());
+    pub fn components<N: NodeId, W: Weight>(g: &Graph<N, W>) -> (Graph<N, W>, Vec<Vec<N>>) {
+        let mut cg = g.copy();
 
         if cg.directed() {
             cg.pause();
@@ -20,24 +22,27 @@ impl Connectivity {
         Self::dfs_components(cg, nodes)
     }
 
-    fn dfs_components(mut cg: Graph, mut nodes: Vec<char>) -> (Graph, Vec<Vec<char>>) {
+    fn dfs_components<N: NodeId, W: Weight>(
+        mut cg: Graph<N, W>,
+        mut nodes: Vec<N>,
+    ) -> (Graph<N, W>, Vec<Vec<N>>) {
         let mut components = Vec::new();
 
         let colors = Color::colors();
         let mut it_colors = colors.into_iter().cycle();
         while let Some(start) = nodes.pop() {
-            let already: Vec<char> = components.iter().flatten().cloned().collect();
+            let already: Vec<N> = components.iter().flatten().cloned().collect();
             if already.contains(&start) {
                 continue;
             }
             let mut visited = Vec::new();
             let mut backtracked = Vec::new();
-            Self::dfs_components_rec(&mut cg, start, &mut visited, &mut backtracked, &already);
+            Self::dfs_components_rec(&mut cg, &start, &mut visited, &mut backtracked, &already);
             assert!(visited.is_empty());
             let color = it_colors.next().unwrap();
             cg.pause();
             for node in backtracked.iter() {
-                cg.color_node(*node, color);
+                cg.color_node(node.clone(), color);
             }
             cg.resume();
             components.push(backtracked);
@@ -46,46 +51,44 @@ impl Connectivity {
         (cg, components)
     }
 
-    fn dfs_components_rec(
-        g: &mut Graph,
-        start: char,
-        visited: &mut Vec<char>,
-        backtracked: &mut Vec<char>,
-        already: &Vec<char>,
+    fn dfs_components_rec<N: NodeId, W: Weight>(
+        g: &mut Graph<N, W>,
+        start: &N,
+        visited: &mut Vec<N>,
+        backtracked: &mut Vec<N>,
+        already: &[N],
     ) {
-        if backtracked.contains(&start) || visited.contains(&start) || already.contains(&start) {
+        if backtracked.contains(start) || visited.contains(start) || already.contains(start) {
             return;
         }
 
-        let prec_start = match visited.last() {
-            Some(n) => *n,
-            None => ' ',
-        };
-        visited.push(start);
-        g.fill_node(start, (0, 196, 0));
-        for neighbor in g.neighbors(start) {
-            if neighbor == prec_start {
+        let prec_start = visited.last().cloned();
+        visited.push(start.clone());
+        g.fill_node(start.clone(), (0, 196, 0));
+        for neighbor in g.neighbors(start.clone()) {
+            if Some(&neighbor) == prec_start.as_ref() {
                 continue;
             }
 
-            Self::dfs_components_rec(g, neighbor, visited, backtracked, already);
+            Self::dfs_components_rec(g, &neighbor, visited, backtracked, already);
         }
-        assert!(visited.pop() == Some(start));
-        backtracked.push(start);
+        assert!(visited.pop().as_ref() == Some(start));
+        backtracked.push(start.clone());
         g.pause();
-        g.fill_node(start, (255, 255, 255));
-        g.color_label(start, (0, 196, 0));
+        g.fill_node(start.clone(), (255, 255, 255));
+        g.color_label(start.clone(), (0, 196, 0));
         g.resume();
     }
 
     /// Returns a list of strongly connected components
-    pub fn strongly_connected_components(g: &Graph) -> (Graph, Vec<Vec<char>>) {
-        let mut cg = Graph::new();
-        cg.from_str(&g.to_string());
+    pub fn strongly_connected_components<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> (Graph<N, W>, Vec<Vec<N>>) {
+        let cg = g.copy();
 
         let nodes = cg.nodes();
         let (mut cg, backtracked) = Self::dfs_components(cg, nodes);
-        let nodes: Vec<char> = backtracked.into_iter().flatten().collect();
+        let nodes: Vec<N> = backtracked.into_iter().flatten().collect();
         cg.pause();
         Transform::transpose(&mut cg);
         cg.resume();
@@ -102,4 +105,191 @@ impl Connectivity {
 
         (cg, components)
     }
+
+    /// Returns a list of articulation points (Tarjan): nodes whose removal disconnects their component.
+    /// Node badges show the discovery and low-link numbers (`discovery/low`) found during the DFS, and label colors the low-link numbers.
+    pub fn articulation_points<N: NodeId, W: Weight>(g: &Graph<N, W>) -> (Graph<N, W>, Vec<N>) {
+        let (mut cg, tarjan) = Self::tarjan(g);
+
+        let points: Vec<N> = tarjan.articulation_points.into_iter().collect();
+        cg.pause();
+        for node in &points {
+            cg.fill_node(node.clone(), (255, 0, 0));
+        }
+        cg.resume();
+
+        (cg, points)
+    }
+
+    /// Returns a list of bridges (Tarjan): links whose removal disconnects their component (a link having a parallel link is never a bridge).
+    /// Node badges show the discovery and low-link numbers (`discovery/low`) found during the DFS, and label colors the low-link numbers.
+    pub fn bridges<N: NodeId, W: Weight>(g: &Graph<N, W>) -> (Graph<N, W>, Vec<(N, N)>) {
+        let (mut cg, tarjan) = Self::tarjan(g);
+
+        cg.pause();
+        for (node_from, node_to) in &tarjan.bridges {
+            cg.color_link(node_from.clone(), node_to.clone(), (255, 0, 0));
+        }
+        cg.resume();
+
+        (cg, tarjan.bridges)
+    }
+
+    /// Returns a list of biconnected components (Tarjan): maximal sets of linked nodes that stay connected after any single node removal.
+    /// An isolated node is a component on its own.
+    /// Node badges show the discovery and low-link numbers (`discovery/low`) found during the DFS, and label colors the low-link numbers.
+    pub fn biconnected_components<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> (Graph<N, W>, Vec<Vec<N>>) {
+        let (mut cg, tarjan) = Self::tarjan(g);
+
+        let colors = Color::colors();
+        let mut components = Vec::new();
+        for (i, links) in tarjan.components.iter().enumerate() {
+            let color = colors[i % colors.len()];
+            let mut component = BTreeSet::new();
+            cg.pause();
+            for (node_from, node_to) in links {
+                cg.color_link(node_from.clone(), node_to.clone(), color);
+                component.insert(node_from.clone());
+                component.insert(node_to.clone());
+            }
+            cg.resume();
+            components.push(component.into_iter().collect());
+        }
+        for node in cg.nodes() {
+            if cg
+                .neighbors(node.clone())
+                .iter()
+                .all(|neighbor| *neighbor == node)
+            {
+                components.push(vec![node]);
+            }
+        }
+
+        (cg, components)
+    }
+
+    fn tarjan<N: NodeId, W: Weight>(g: &Graph<N, W>) -> (Graph<N, W>, Tarjan<N>) {
+        let mut cg = g.copy();
+
+        if cg.directed() {
+            cg.pause();
+            Transform::undirect(&mut cg);
+            cg.resume();
+            assert!(!cg.directed());
+        }
+
+        let mut links = BTreeSet::new();
+        let mut parallels = BTreeSet::new();
+        for (node_from, node_to, _, _) in cg.link_entries() {
+            let link = (
+                node_from.clone().min(node_to.clone()),
+                node_from.max(node_to),
+            );
+            if !links.insert(link.clone()) {
+                parallels.insert(link);
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            parallels,
+            discoveries: BTreeMap::new(),
+            lows: BTreeMap::new(),
+            stack: Vec::new(),
+            articulation_points: BTreeSet::new(),
+            bridges: Vec::new(),
+            components: Vec::new(),
+        };
+        for node in cg.nodes() {
+            if !tarjan.discoveries.contains_key(&node) {
+                Self::tarjan_rec(&mut cg, &mut tarjan, &node, None);
+            }
+        }
+
+        (cg, tarjan)
+    }
+
+    fn tarjan_rec<N: NodeId, W: Weight>(
+        g: &mut Graph<N, W>,
+        tarjan: &mut Tarjan<N>,
+        node: &N,
+        parent: Option<&N>,
+    ) {
+        let colors = Color::colors();
+        let discovery = tarjan.discoveries.len();
+        tarjan.discoveries.insert(node.clone(), discovery);
+        tarjan.lows.insert(node.clone(), discovery);
+        g.pause();
+        g.color_node(node.clone(), (255, 127, 0));
+        g.color_label(node.clone(), colors[discovery % colors.len()]);
+        g.set_node_badge(node.clone(), &format!("{}/{}", discovery, discovery));
+        g.resume();
+
+        let mut children = 0;
+        for neighbor in g.neighbors(node.clone()) {
+            let link = (
+                node.clone().min(neighbor.clone()),
+                node.clone().max(neighbor.clone()),
+            );
+            if Some(&neighbor) == parent && !tarjan.parallels.contains(&link) || neighbor == *node {
+                continue;
+            }
+            let low = tarjan.lows[node];
+            match tarjan.discoveries.get(&neighbor).cloned() {
+                None => {
+                    children += 1;
+                    tarjan.stack.push((node.clone(), neighbor.clone()));
+                    g.color_link(node.clone(), neighbor.clone(), (0, 192, 0));
+                    Self::tarjan_rec(g, tarjan, &neighbor, Some(node));
+
+                    let low_neighbor = tarjan.lows[&neighbor];
+                    if low_neighbor < low {
+                        tarjan.lows.insert(node.clone(), low_neighbor);
+                        g.pause();
+                        g.color_label(node.clone(), colors[low_neighbor % colors.len()]);
+                        g.set_node_badge(node.clone(), &format!("{}/{}", discovery, low_neighbor));
+                        g.resume();
+                    }
+                    if low_neighbor > discovery {
+                        tarjan.bridges.push((node.clone(), neighbor.clone()));
+                    }
+                    if low_neighbor >= discovery {
+                        if parent.is_some() || children > 1 {
+                            tarjan.articulation_points.insert(node.clone());
+                        }
+                        let link = (node.clone(), neighbor.clone());
+                        let position = tarjan.stack.iter().rposition(|l| *l == link).unwrap();
+                        tarjan.components.push(tarjan.stack.split_off(position));
+                    }
+                }
+                Some(discovery_neighbor) if discovery_neighbor < discovery => {
+                    tarjan.stack.push((node.clone(), neighbor.clone()));
+                    g.color_link(node.clone(), neighbor.clone(), (255, 127, 0));
+                    if discovery_neighbor < low {
+                        tarjan.lows.insert(node.clone(), discovery_neighbor);
+                        g.pause();
+                        g.color_label(node.clone(), colors[discovery_neighbor % colors.len()]);
+                        g.set_node_badge(
+                            node.clone(),
+                            &format!("{}/{}", discovery, discovery_neighbor),
+                        );
+                        g.resume();
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+        g.color_node(node.clone(), (0, 192, 0));
+    }
+}
+
+struct Tarjan<N> {
+    parallels: BTreeSet<(N, N)>,
+    discoveries: BTreeMap<N, usize>,
+    lows: BTreeMap<N, usize>,
+    stack: Vec<(N, N)>,
+    articulation_points: BTreeSet<N>,
+    bridges: Vec<(N, N)>,
+    components: Vec<Vec<(N, N)>>,
 }
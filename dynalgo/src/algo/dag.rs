@@ -0,0 +1,297 @@
+use crate::algo::utils::color::Color;
+use crate::graph::{Graph, GraphError, NodeId, Weight};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+
+/// Directed acyclic graphs algorithms, returning an error for a graph whose links are all bidirectional.
+pub struct Dag;
+
+impl Dag {
+    /// Returns the nodes in topological order (Kahn). The order is partial (missing nodes) if the graph has a cycle.
+    #[allow(clippy::type_complexity)]
+    pub fn kahn<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> Result<(Graph<N, W>, Vec<N>), GraphError<N>> {
+        let mut dg = Self::copy(g)?;
+        let adj = dg.adjacency_list();
+
+        let order = Self::order(&dg, &adj);
+        let mut indegrees = Self::indegrees(&dg);
+
+        dg.pause();
+        for (node, _) in indegrees.iter().filter(|(_, indegree)| **indegree == 0) {
+            dg.color_label(node.clone(), (0, 255, 0));
+        }
+        dg.resume();
+
+        for node in &order {
+            dg.color_node(node.clone(), (0, 192, 0));
+            for node_to in adj[node].keys() {
+                let indegree = indegrees.get_mut(node_to).unwrap();
+                *indegree -= 1;
+                dg.pause();
+                dg.color_link(node.clone(), node_to.clone(), Color::disabled());
+                if *indegree == 0 {
+                    dg.color_label(node_to.clone(), (0, 255, 0));
+                }
+                dg.resume();
+            }
+        }
+
+        Ok((dg, order))
+    }
+
+    /// Returns the nodes in topological order (reverse DFS postorder). The order is meaningless if the graph has a cycle.
+    #[allow(clippy::type_complexity)]
+    pub fn dfs_sort<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> Result<(Graph<N, W>, Vec<N>), GraphError<N>> {
+        let mut dg = Self::copy(g)?;
+        let adj = dg.adjacency_list();
+
+        let mut visited = BTreeSet::new();
+        let mut order = Vec::new();
+        for node in adj.keys() {
+            Self::dfs(&mut dg, &adj, node, &mut visited, &mut order);
+        }
+        order.reverse();
+
+        Ok((dg, order))
+    }
+
+    /// Returns a cycle (first node equals last node), or an empty vector if the graph is acyclic.
+    #[allow(clippy::type_complexity)]
+    pub fn cycle<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> Result<(Graph<N, W>, Vec<N>), GraphError<N>> {
+        let mut dg = Self::copy(g)?;
+        let adj = dg.adjacency_list();
+
+        let mut visited = BTreeSet::new();
+        let mut stack = Vec::new();
+        let mut cycle = Vec::new();
+        for node in adj.keys() {
+            if Self::dfs_cycle(&mut dg, &adj, node, &mut visited, &mut stack, &mut cycle) {
+                break;
+            }
+        }
+
+        dg.pause();
+        for node in cycle.windows(2) {
+            dg.color_node(node[0].clone(), (255, 0, 0));
+            dg.color_link(node[0].clone(), node[1].clone(), (255, 0, 0));
+        }
+        dg.resume();
+
+        Ok((dg, cycle))
+    }
+
+    /// Returns the longest distance from a source node (indegree 0) to each node, and a critical path (a longest path of the graph).
+    /// The graph must be acyclic: an error is returned otherwise.
+    #[allow(clippy::type_complexity)]
+    pub fn longest_path<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> Result<(Graph<N, W>, BTreeMap<N, W>, Vec<N>), GraphError<N>> {
+        let mut dg = Self::copy(g)?;
+        let adj = dg.adjacency_list();
+        let order = Self::acyclic_order(&dg, &adj)?;
+
+        let mut distances: BTreeMap<N, W> = BTreeMap::new();
+        let mut predecessors: BTreeMap<N, N> = BTreeMap::new();
+        for node in &order {
+            let distance = *distances.entry(node.clone()).or_default();
+            dg.color_node(node.clone(), (0, 192, 0));
+            for (node_to, value) in &adj[node] {
+                let candidate = distance + *value;
+                if distances.get(node_to).is_some_and(|d| candidate <= *d) {
+                    dg.color_link(node.clone(), node_to.clone(), Color::disabled());
+                    continue;
+                }
+                distances.insert(node_to.clone(), candidate);
+                dg.pause();
+                if let Some(previous) = predecessors.insert(node_to.clone(), node.clone()) {
+                    dg.color_link(previous, node_to.clone(), Color::disabled());
+                }
+                dg.color_link(node.clone(), node_to.clone(), (255, 127, 0));
+                dg.color_label(node_to.clone(), (0, 255, 0));
+                dg.resume();
+            }
+        }
+
+        let mut path = Vec::new();
+        let last = distances
+            .iter()
+            .max_by(|(_, a), (_, b)| a.to_f64().total_cmp(&b.to_f64()))
+            .map(|(node, _)| node.clone());
+        if let Some(last) = last {
+            path.push(last);
+            while let Some(node) = predecessors.get(path.last().unwrap()) {
+                path.push(node.clone());
+            }
+            path.reverse();
+        }
+
+        dg.pause();
+        for node in &path {
+            dg.color_node(node.clone(), (255, 0, 0));
+        }
+        for node in path.windows(2) {
+            dg.color_link(node[0].clone(), node[1].clone(), (255, 0, 0));
+        }
+        dg.resume();
+
+        Ok((dg, distances, path))
+    }
+
+    /// Returns the nodes layers by topological rank (longest count of links from a source node), and moves nodes to draw layers from left to right.
+    /// The graph must be acyclic: an error is returned otherwise.
+    #[allow(clippy::type_complexity)]
+    pub fn layers<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> Result<(Graph<N, W>, Vec<Vec<N>>), GraphError<N>> {
+        let mut dg = Self::copy(g)?;
+        let adj = dg.adjacency_list();
+        let order = Self::acyclic_order(&dg, &adj)?;
+
+        let mut ranks: BTreeMap<N, usize> = BTreeMap::new();
+        for node in &order {
+            let rank = *ranks.entry(node.clone()).or_default();
+            for node_to in adj[node].keys() {
+                let rank_to = ranks.entry(node_to.clone()).or_default();
+                *rank_to = (*rank_to).max(rank + 1);
+            }
+        }
+
+        let mut layers: Vec<Vec<N>> = Vec::new();
+        for node in &order {
+            let rank = ranks[node];
+            if layers.len() <= rank {
+                layers.resize(rank + 1, Vec::new());
+            }
+            layers[rank].push(node.clone());
+        }
+
+        let d = 4 * dg.node_radius() as i32;
+        let x_0 = -(layers.len() as i32 - 1) * d / 2;
+        dg.pause();
+        for (rank, layer) in layers.iter().enumerate() {
+            let y_0 = -(layer.len() as i32 - 1) * d / 2;
+            for (i, node) in layer.iter().enumerate() {
+                dg.move_node(node.clone(), (x_0 + rank as i32 * d, y_0 + i as i32 * d));
+            }
+        }
+        dg.resume();
+
+        Ok((dg, layers))
+    }
+
+    fn copy<N: NodeId, W: Weight>(g: &Graph<N, W>) -> Result<Graph<N, W>, GraphError<N>> {
+        if !g.directed() && !g.links().is_empty() {
+            return Err(GraphError::AlgorithmError(String::from(
+                "graph is not directed",
+            )));
+        }
+        Ok(g.copy())
+    }
+
+    fn acyclic_order<N: NodeId, W: Weight>(
+        dg: &Graph<N, W>,
+        adj: &BTreeMap<N, BTreeMap<N, W>>,
+    ) -> Result<Vec<N>, GraphError<N>> {
+        let order = Self::order(dg, adj);
+        match order.len() == adj.len() {
+            true => Ok(order),
+            false => Err(GraphError::AlgorithmError(String::from(
+                "graph has a cycle",
+            ))),
+        }
+    }
+
+    fn indegrees<N: NodeId, W: Weight>(dg: &Graph<N, W>) -> BTreeMap<N, usize> {
+        dg.sequence()
+            .into_iter()
+            .map(|(node, (_, indegree))| (node, indegree))
+            .collect()
+    }
+
+    fn order<N: NodeId, W: Weight>(dg: &Graph<N, W>, adj: &BTreeMap<N, BTreeMap<N, W>>) -> Vec<N> {
+        let mut indegrees = Self::indegrees(dg);
+        let mut queue: VecDeque<N> = indegrees
+            .iter()
+            .filter(|(_, indegree)| **indegree == 0)
+            .map(|(node, _)| node.clone())
+            .collect();
+
+        let mut order = Vec::new();
+        while let Some(node) = queue.pop_front() {
+            for node_to in adj[&node].keys() {
+                let indegree = indegrees.get_mut(node_to).unwrap();
+                *indegree -= 1;
+                if *indegree == 0 {
+                    queue.push_back(node_to.clone());
+                }
+            }
+            order.push(node);
+        }
+        order
+    }
+
+    fn dfs<N: NodeId, W: Weight>(
+        dg: &mut Graph<N, W>,
+        adj: &BTreeMap<N, BTreeMap<N, W>>,
+        node: &N,
+        visited: &mut BTreeSet<N>,
+        order: &mut Vec<N>,
+    ) {
+        if !visited.insert(node.clone()) {
+            return;
+        }
+        dg.color_node(node.clone(), (255, 127, 0));
+        for node_to in adj[node].keys() {
+            if visited.contains(node_to) {
+                continue;
+            }
+            dg.color_link(node.clone(), node_to.clone(), (255, 127, 0));
+            Self::dfs(dg, adj, node_to, visited, order);
+        }
+        dg.pause();
+        dg.color_node(node.clone(), (0, 192, 0));
+        dg.color_label(node.clone(), (0, 255, 0));
+        dg.resume();
+        order.push(node.clone());
+    }
+
+    fn dfs_cycle<N: NodeId, W: Weight>(
+        dg: &mut Graph<N, W>,
+        adj: &BTreeMap<N, BTreeMap<N, W>>,
+        node: &N,
+        visited: &mut BTreeSet<N>,
+        stack: &mut Vec<N>,
+        cycle: &mut Vec<N>,
+    ) -> bool {
+        if !visited.insert(node.clone()) {
+            return false;
+        }
+        dg.color_node(node.clone(), (255, 127, 0));
+        stack.push(node.clone());
+        for node_to in adj[node].keys() {
+            if let Some(i) = stack.iter().position(|n| n == node_to) {
+                cycle.extend_from_slice(&stack[i..]);
+                cycle.push(node_to.clone());
+                return true;
+            }
+            if visited.contains(node_to) {
+                continue;
+            }
+            dg.color_link(node.clone(), node_to.clone(), (255, 127, 0));
+            if Self::dfs_cycle(dg, adj, node_to, visited, stack, cycle) {
+                return true;
+            }
+            dg.color_link(node.clone(), node_to.clone(), Color::disabled());
+        }
+        stack.pop();
+        dg.color_node(node.clone(), (0, 192, 0));
+        false
+    }
+}
@@ -1,15 +1,14 @@
 use crate::algo::connectivity::Connectivity;
 use crate::algo::utils::color::Color;
-use crate::graph::Graph;
+use crate::graph::{Graph, NodeId, Weight};
 
 pub struct Eulerian;
 
 impl Eulerian {
     /// Returns an Eulerian path or cycle, if exists.
-    pub fn hierholzer(g: &Graph) -> (Graph, Vec<char>) {
+    pub fn hierholzer<N: NodeId, W: Weight>(g: &Graph<N, W>) -> (Graph<N, W>, Vec<N>) {
         let directed = g.directed();
-        let mut eg = Graph::new();
-        eg.from_str(&g.to_string());
+        let mut eg = g.copy();
 
         let mut cycle = Vec::new();
 
@@ -19,33 +18,33 @@ impl Eulerian {
         let sequence = eg.sequence();
         let mut extra_link = None;
         if directed {
-            let odd_node: Vec<(char, (usize, usize))> =
+            let odd_node: Vec<(N, (usize, usize))> =
                 sequence.into_iter().filter(|(_, (o, i))| o != i).collect();
             match odd_node.len() {
                 0 => {}
                 2 => {
-                    let ((n1, (o1, i1)), (n2, (o2, i2))) = (odd_node[0], odd_node[1]);
+                    let ((n1, (o1, i1)), (n2, (o2, i2))) = (&odd_node[0], &odd_node[1]);
                     assert!(o1 + o2 == i1 + i2);
                     let (node_from, node_to) = if o1 > i1 { (n2, n1) } else { (n1, n2) };
-                    extra_link = Some((node_from, node_to));
-                    eg.delete_link(node_to, node_from);
-                    eg.add_link(node_from, node_to, true, 0);
-                    eg.color_link(node_from, node_to, Color::disabled());
+                    extra_link = Some((node_from.clone(), node_to.clone()));
+                    eg.delete_link(node_to.clone(), node_from.clone());
+                    eg.add_link(node_from.clone(), node_to.clone(), true, W::default());
+                    eg.color_link(node_from.clone(), node_to.clone(), Color::disabled());
                 }
                 _ => return (eg, cycle),
             }
         } else {
-            let odd_node: Vec<(char, (usize, usize))> = sequence
+            let odd_node: Vec<(N, (usize, usize))> = sequence
                 .into_iter()
                 .filter(|(_, (o, _))| o % 2 != 0)
                 .collect();
             match odd_node.len() {
                 0 => {}
                 2 => {
-                    let ((n1, _), (n2, _)) = (odd_node[0], odd_node[1]);
-                    extra_link = Some((n2, n1));
-                    eg.add_link(n2, n1, true, 0);
-                    eg.color_link(n2, n1, Color::disabled());
+                    let ((n1, _), (n2, _)) = (&odd_node[0], &odd_node[1]);
+                    extra_link = Some((n2.clone(), n1.clone()));
+                    eg.add_link(n2.clone(), n1.clone(), true, W::default());
+                    eg.color_link(n2.clone(), n1.clone(), Color::disabled());
                 }
                 _ => return (eg, cycle),
             }
@@ -54,19 +53,18 @@ impl Eulerian {
 
         let colors = Color::colors();
         let mut it_colors = colors.into_iter().cycle();
-        let mut start = g.nodes()[0];
+        let mut start = g.nodes()[0].clone();
         while !adj.is_empty() {
             let color = it_colors.next().unwrap();
             //eg.color_node(start, color);
             let mut c = Vec::new();
-            let mut prev = start;
-            let mut next = prev;
+            let mut next = start.clone();
 
             loop {
-                c.push(next);
-                prev = next;
-                next = *adj[&prev].keys().next().unwrap();
-                eg.color_link(prev, next, color);
+                c.push(next.clone());
+                let prev = next;
+                next = adj[&prev].keys().next().unwrap().clone();
+                eg.color_link(prev.clone(), next.clone(), color);
                 //eg.color_node(next, color);
                 assert!(adj.get_mut(&prev).unwrap().remove(&next).is_some());
                 if adj.get_mut(&prev).unwrap().is_empty() {
@@ -93,44 +91,44 @@ impl Eulerian {
             }
 
             for node in &cycle {
-                if adj.get(node).is_some() {
-                    start = *node;
+                if adj.contains_key(node) {
+                    start = node.clone();
                     break;
                 }
             }
         }
 
         if let Some((extra_from, extra_to)) = extra_link {
-            let mut prec = None;
+            let mut prec: Option<&N> = None;
             let mut idx = usize::MAX;
             for (i, node) in cycle.iter().enumerate() {
                 if let Some(prec_node) = prec {
-                    if (prec_node == extra_from && *node == extra_to)
-                        || (!directed && prec_node == extra_to && *node == extra_from)
+                    if (*prec_node == extra_from && *node == extra_to)
+                        || (!directed && *prec_node == extra_to && *node == extra_from)
                     {
                         idx = i;
                         break;
                     }
                 }
-                prec = Some(*node);
+                prec = Some(node);
             }
             cycle.rotate_left(idx);
-            eg.delete_link(extra_from, extra_to);
+            eg.delete_link(extra_from.clone(), extra_to.clone());
             if directed {
-                eg.add_link(extra_to, extra_from, false, 0);
+                eg.add_link(extra_to, extra_from, false, W::default());
             }
         } else {
-            cycle.push(cycle[0]);
+            cycle.push(cycle[0].clone());
         }
 
         let color = it_colors.next().unwrap();
         let mut prec = None;
         for node in &cycle {
             if let Some(prec_node) = prec {
-                eg.color_link(prec_node, *node, color);
+                eg.color_link(prec_node, node.clone(), color);
             }
-            //eg.color_node(*node, color);
-            prec = Some(*node);
+            //eg.color_node(node.clone(), color);
+            prec = Some(node.clone());
         }
 
         (eg, cycle)
@@ -0,0 +1,304 @@
+use crate::algo::utils::check::Check;
+use crate::algo::utils::color::Color;
+use crate::graph::{Graph, GraphError, NodeId, Weight};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+
+pub struct Flow;
+
+impl Flow {
+    /// Returns the maximum flow from the source node to the sink node (Edmonds-Karp), the flow of each link and the minimum cut nodes partition (source side, sink side).
+    /// Links values are the capacities (added up for parallel links) and must not be negative; each augmented link is labeled with its residual capacity.
+    /// An error is returned if a capacity is negative, if the source or sink node does not exist, or if they are the same node.
+    #[allow(clippy::type_complexity)]
+    pub fn edmonds_karp<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+        source: N,
+        sink: N,
+    ) -> Result<(Graph<N, W>, W, BTreeMap<(N, N), W>, (Vec<N>, Vec<N>)), GraphError<N>> {
+        let (mut fg, mut residual) = Self::init(g, &source, &sink)?;
+
+        let mut total = W::default();
+        while let Some(path) = residual.shortest_path(&source, &sink) {
+            total = total + residual.augment(&mut fg, &path);
+        }
+
+        Ok(Self::result(fg, residual, source, total))
+    }
+
+    /// Returns the maximum flow from the source node to the sink node (Dinic), the flow of each link and the minimum cut nodes partition (source side, sink side).
+    /// Links values are the capacities (added up for parallel links) and must not be negative; each augmented link is labeled with its residual capacity.
+    /// An error is returned if a capacity is negative, if the source or sink node does not exist, or if they are the same node.
+    #[allow(clippy::type_complexity)]
+    pub fn dinic<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+        source: N,
+        sink: N,
+    ) -> Result<(Graph<N, W>, W, BTreeMap<(N, N), W>, (Vec<N>, Vec<N>)), GraphError<N>> {
+        let (mut fg, mut residual) = Self::init(g, &source, &sink)?;
+
+        let colors = Color::colors();
+        let mut total = W::default();
+        loop {
+            let levels = residual.levels(&source);
+            if !levels.contains_key(&sink) {
+                break;
+            }
+
+            fg.pause();
+            for (node, level) in &levels {
+                fg.color_label(node.clone(), colors[level % colors.len()]);
+            }
+            fg.resume();
+
+            let mut dead_ends = BTreeSet::new();
+            let mut path = Vec::new();
+            while residual.level_path(&source, &sink, &levels, &mut dead_ends, &mut path) {
+                total = total + residual.augment(&mut fg, &path);
+                path.clear();
+            }
+        }
+
+        Ok(Self::result(fg, residual, source, total))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn init<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+        source: &N,
+        sink: &N,
+    ) -> Result<(Graph<N, W>, Residual<N, W>), GraphError<N>> {
+        Check::node(g, source)?;
+        Check::node(g, sink)?;
+        if source == sink {
+            return Err(GraphError::AlgorithmError(format!(
+                "source and sink are the same node {}",
+                source
+            )));
+        }
+        Check::non_negative(g)?;
+        let mut fg = g.copy();
+
+        let residual = Residual::new(&fg);
+        fg.pause();
+        fg.color_node(source.clone(), (0, 192, 0));
+        fg.color_node(sink.clone(), (192, 0, 0));
+        fg.resume();
+
+        Ok((fg, residual))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn result<N: NodeId, W: Weight>(
+        mut fg: Graph<N, W>,
+        residual: Residual<N, W>,
+        source: N,
+        total: W,
+    ) -> (Graph<N, W>, W, BTreeMap<(N, N), W>, (Vec<N>, Vec<N>)) {
+        let source_side: Vec<N> = residual.levels(&source).into_keys().collect();
+        let sink_side: Vec<N> = fg
+            .nodes()
+            .into_iter()
+            .filter(|node| !source_side.contains(node))
+            .collect();
+
+        fg.sleep(1000);
+        fg.pause();
+        for node in &source_side {
+            fg.fill_node(node.clone(), (144, 238, 144));
+        }
+        for node in &sink_side {
+            fg.fill_node(node.clone(), (255, 182, 193));
+        }
+        fg.resume();
+
+        (fg, total, residual.flows(), (source_side, sink_side))
+    }
+}
+
+/// Residual network: the flow of a link is cancelled by the flow of its reverse link, so that only one direction carries a flow.
+struct Residual<N, W> {
+    capacities: BTreeMap<(N, N), W>,
+    flows: BTreeMap<(N, N), W>,
+    neighbors: BTreeMap<N, BTreeSet<N>>,
+}
+
+impl<N: NodeId, W: Weight> Residual<N, W> {
+    fn new(g: &Graph<N, W>) -> Residual<N, W> {
+        let mut capacities = BTreeMap::new();
+        let mut neighbors: BTreeMap<N, BTreeSet<N>> = BTreeMap::new();
+        for (node_from, node_tos) in g.merged_adjacency_list(|a, b| a + b) {
+            neighbors.entry(node_from.clone()).or_default();
+            for (node_to, capacity) in node_tos {
+                capacities.insert((node_from.clone(), node_to.clone()), capacity);
+                neighbors
+                    .entry(node_from.clone())
+                    .or_default()
+                    .insert(node_to.clone());
+                neighbors
+                    .entry(node_to)
+                    .or_default()
+                    .insert(node_from.clone());
+            }
+        }
+
+        Residual {
+            capacities,
+            flows: BTreeMap::new(),
+            neighbors,
+        }
+    }
+
+    fn residual(&self, node_from: &N, node_to: &N) -> W {
+        let capacity = self
+            .capacities
+            .get(&(node_from.clone(), node_to.clone()))
+            .cloned()
+            .unwrap_or_default();
+        capacity - self.flow(node_from, node_to) + self.flow(node_to, node_from)
+    }
+
+    fn levels(&self, source: &N) -> BTreeMap<N, usize> {
+        let mut levels = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        levels.insert(source.clone(), 0);
+        queue.push_back(source.clone());
+        while let Some(node) = queue.pop_front() {
+            for next in &self.neighbors[&node] {
+                if levels.contains_key(next) || self.residual(&node, next) <= W::default() {
+                    continue;
+                }
+                levels.insert(next.clone(), levels[&node] + 1);
+                queue.push_back(next.clone());
+            }
+        }
+        levels
+    }
+
+    fn shortest_path(&self, source: &N, sink: &N) -> Option<Vec<N>> {
+        let mut predecessors: BTreeMap<N, N> = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(source.clone());
+        while let Some(node) = queue.pop_front() {
+            if node == *sink {
+                let mut path = vec![node];
+                while let Some(predecessor) = predecessors.get(path.last().unwrap()) {
+                    path.push(predecessor.clone());
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for next in &self.neighbors[&node] {
+                if next == source
+                    || predecessors.contains_key(next)
+                    || self.residual(&node, next) <= W::default()
+                {
+                    continue;
+                }
+                predecessors.insert(next.clone(), node.clone());
+                queue.push_back(next.clone());
+            }
+        }
+        None
+    }
+
+    fn level_path(
+        &self,
+        node: &N,
+        sink: &N,
+        levels: &BTreeMap<N, usize>,
+        dead_ends: &mut BTreeSet<N>,
+        path: &mut Vec<N>,
+    ) -> bool {
+        path.push(node.clone());
+        if node == sink {
+            return true;
+        }
+        for next in &self.neighbors[node] {
+            if dead_ends.contains(next)
+                || levels.get(next) != Some(&(levels[node] + 1))
+                || self.residual(node, next) <= W::default()
+            {
+                continue;
+            }
+            if self.level_path(next, sink, levels, dead_ends, path) {
+                return true;
+            }
+        }
+        path.pop();
+        dead_ends.insert(node.clone());
+        false
+    }
+
+    fn augment(&mut self, g: &mut Graph<N, W>, path: &[N]) -> W {
+        let mut bottleneck = self.residual(&path[0], &path[1]);
+        for link in path.windows(2) {
+            let residual = self.residual(&link[0], &link[1]);
+            if residual < bottleneck {
+                bottleneck = residual;
+            }
+        }
+
+        g.pause();
+        for link in path.windows(2) {
+            let (node_from, node_to) = self.graph_link(&link[0], &link[1]);
+            g.color_link(node_from.clone(), node_to.clone(), (255, 127, 0));
+            g.color_value(node_from, node_to, (255, 127, 0));
+        }
+        g.resume();
+
+        for link in path.windows(2) {
+            let forward = (link[0].clone(), link[1].clone());
+            let backward = (link[1].clone(), link[0].clone());
+            let cancelled = self.flow(&link[1], &link[0]);
+            if bottleneck <= cancelled {
+                self.flows.insert(backward, cancelled - bottleneck);
+            } else {
+                let flow = self.flow(&link[0], &link[1]);
+                self.flows.insert(backward, W::default());
+                self.flows.insert(forward, flow + (bottleneck - cancelled));
+            }
+        }
+
+        g.pause();
+        for link in path.windows(2) {
+            let (node_from, node_to) = self.graph_link(&link[0], &link[1]);
+            let color = if self.residual(&node_from, &node_to) <= W::default() {
+                (255, 0, 0)
+            } else if self.flow(&node_from, &node_to) > W::default() {
+                (0, 192, 0)
+            } else {
+                Color::default()
+            };
+            let residual = self.residual(&node_from, &node_to);
+            g.color_link(node_from.clone(), node_to.clone(), color);
+            g.color_value(node_from.clone(), node_to.clone(), color);
+            g.set_link_label(node_from, node_to, &residual.to_string());
+        }
+        g.resume();
+
+        bottleneck
+    }
+
+    fn graph_link(&self, node_1: &N, node_2: &N) -> (N, N) {
+        let link = (node_1.clone(), node_2.clone());
+        match self.capacities.contains_key(&link) {
+            true => link,
+            false => (link.1, link.0),
+        }
+    }
+
+    fn flow(&self, node_from: &N, node_to: &N) -> W {
+        self.flows
+            .get(&(node_from.clone(), node_to.clone()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    fn flows(&self) -> BTreeMap<(N, N), W> {
+        let mut flows = self.flows.clone();
+        flows.retain(|_, flow| *flow > W::default());
+        flows
+    }
+}
@@ -0,0 +1,315 @@
+use crate::algo::utils::color::Color;
+use crate::algo::utils::transform::Transform;
+use crate::graph::{Graph, GraphError, NodeId, Weight};
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+
+pub struct Matching;
+
+impl Matching {
+    /// Returns the two sides of the graph if it is bipartite (None otherwise). Links directions are ignored.
+    #[allow(clippy::type_complexity)]
+    pub fn bipartite<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> (Graph<N, W>, Option<(Vec<N>, Vec<N>)>) {
+        let mut mg = Self::copy(g);
+        let adj = mg.adjacency_list();
+        let fills = [(144, 238, 144), (173, 216, 230)];
+
+        let mut sides: BTreeMap<N, usize> = BTreeMap::new();
+        for start in adj.keys() {
+            if sides.contains_key(start) {
+                continue;
+            }
+            sides.insert(start.clone(), 0);
+            mg.fill_node(start.clone(), fills[0]);
+            let mut queue = VecDeque::from([start.clone()]);
+            while let Some(node) = queue.pop_front() {
+                let side = sides[&node];
+                for neighbor in adj[&node].keys() {
+                    match sides.get(neighbor) {
+                        Some(s) if *s == side => {
+                            mg.pause();
+                            mg.color_link(node.clone(), neighbor.clone(), (255, 0, 0));
+                            mg.color_node(node.clone(), (255, 0, 0));
+                            mg.color_node(neighbor.clone(), (255, 0, 0));
+                            mg.resume();
+                            return (mg, None);
+                        }
+                        Some(_) => continue,
+                        None => {}
+                    }
+                    sides.insert(neighbor.clone(), 1 - side);
+                    mg.pause();
+                    mg.color_link(node.clone(), neighbor.clone(), (0, 192, 0));
+                    mg.fill_node(neighbor.clone(), fills[1 - side]);
+                    mg.resume();
+                    queue.push_back(neighbor.clone());
+                }
+            }
+        }
+
+        let (left, right): (Vec<_>, Vec<_>) = sides.into_iter().partition(|(_, side)| *side == 0);
+        let left = left.into_iter().map(|(node, _)| node).collect();
+        let right = right.into_iter().map(|(node, _)| node).collect();
+        (mg, Some((left, right)))
+    }
+
+    /// Returns a maximum cardinality matching (Hopcroft-Karp), as pairs of nodes (left side node, right side node).
+    /// The graph must be bipartite: an error is returned otherwise.
+    #[allow(clippy::type_complexity)]
+    pub fn hopcroft_karp<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> Result<(Graph<N, W>, Vec<(N, N)>), GraphError<N>> {
+        let (mut mg, left, _) = Self::sides(g)?;
+        let adj = mg.adjacency_list();
+
+        let mut pairs: BTreeMap<N, N> = BTreeMap::new();
+        loop {
+            let mut levels: BTreeMap<N, usize> = BTreeMap::new();
+            let mut queue = VecDeque::new();
+            for node in &left {
+                if !pairs.contains_key(node) {
+                    levels.insert(node.clone(), 0);
+                    queue.push_back(node.clone());
+                }
+            }
+            let mut found = false;
+            while let Some(node) = queue.pop_front() {
+                for neighbor in adj[&node].keys() {
+                    match pairs.get(neighbor) {
+                        None => found = true,
+                        Some(next) if !levels.contains_key(next) => {
+                            levels.insert(next.clone(), levels[&node] + 1);
+                            queue.push_back(next.clone());
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+            if !found {
+                break;
+            }
+
+            let mut dead_ends = BTreeSet::new();
+            for node in &left {
+                if pairs.contains_key(node) {
+                    continue;
+                }
+                let mut path = Vec::new();
+                if Self::layered_path(&adj, &pairs, &levels, node, &mut dead_ends, &mut path) {
+                    Self::augment(&mut mg, &mut pairs, &path);
+                }
+            }
+        }
+
+        let matching: Vec<(N, N)> = left
+            .into_iter()
+            .filter_map(|node| pairs.get(&node).cloned().map(|pair| (node, pair)))
+            .collect();
+        Self::highlight(&mut mg, &matching);
+
+        Ok((mg, matching))
+    }
+
+    /// Returns a maximum weight matching (Hungarian algorithm), as the total weight and pairs of nodes (left side node, right side node).
+    /// The graph must be bipartite: an error is returned otherwise. Links with negative values are never matched, and parallel links count with their highest value.
+    #[allow(clippy::type_complexity)]
+    pub fn hungarian<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> Result<(Graph<N, W>, W, Vec<(N, N)>), GraphError<N>> {
+        let (mut mg, left, right) = Self::sides(g)?;
+        let adj = mg.merged_adjacency_list(|a, b| if b > a { b } else { a });
+
+        let n = left.len().max(right.len());
+        let mut costs = vec![vec![0.; n + 1]; n + 1];
+        for (i, node_l) in left.iter().enumerate() {
+            for (j, node_r) in right.iter().enumerate() {
+                if let Some(value) = adj[node_l].get(node_r) {
+                    costs[i + 1][j + 1] = -value.to_f64().max(0.);
+                }
+            }
+        }
+
+        let mut u = vec![0.; n + 1];
+        let mut v = vec![0.; n + 1];
+        let mut p = vec![0; n + 1];
+        let mut way = vec![0; n + 1];
+        for i in 1..=n {
+            if let Some(node) = left.get(i - 1) {
+                mg.color_node(node.clone(), (255, 127, 0));
+            }
+            p[0] = i;
+            let mut j0 = 0;
+            let mut minv = vec![f64::INFINITY; n + 1];
+            let mut used = vec![false; n + 1];
+            loop {
+                used[j0] = true;
+                let i0 = p[j0];
+                let mut delta = f64::INFINITY;
+                let mut j1 = 0;
+                for j in 1..=n {
+                    if used[j] {
+                        continue;
+                    }
+                    let cur = costs[i0][j] - u[i0] - v[j];
+                    if cur < minv[j] {
+                        minv[j] = cur;
+                        way[j] = j0;
+                    }
+                    if minv[j] < delta {
+                        delta = minv[j];
+                        j1 = j;
+                    }
+                }
+                for j in 0..=n {
+                    if used[j] {
+                        u[p[j]] += delta;
+                        v[j] -= delta;
+                    } else {
+                        minv[j] -= delta;
+                    }
+                }
+                j0 = j1;
+                if p[j0] == 0 {
+                    break;
+                }
+            }
+            loop {
+                let j1 = way[j0];
+                p[j0] = p[j1];
+                j0 = j1;
+                if j0 == 0 {
+                    break;
+                }
+            }
+        }
+
+        let mut total = W::default();
+        let mut matching = Vec::new();
+        for (j, i) in p.iter().enumerate().skip(1) {
+            let (node_l, node_r) = match (left.get(i - 1), right.get(j - 1)) {
+                (Some(node_l), Some(node_r)) => (node_l, node_r),
+                _ => continue,
+            };
+            match adj[node_l].get(node_r) {
+                Some(value) if *value >= W::default() => {
+                    total = total + *value;
+                    matching.push((node_l.clone(), node_r.clone()));
+                }
+                _ => {}
+            }
+        }
+        matching.sort();
+        Self::highlight(&mut mg, &matching);
+
+        Ok((mg, total, matching))
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn sides<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+    ) -> Result<(Graph<N, W>, Vec<N>, Vec<N>), GraphError<N>> {
+        match Self::bipartite(g) {
+            (mg, Some((left, right))) => Ok((mg, left, right)),
+            (_, None) => Err(GraphError::AlgorithmError(String::from(
+                "graph is not bipartite",
+            ))),
+        }
+    }
+
+    fn copy<N: NodeId, W: Weight>(g: &Graph<N, W>) -> Graph<N, W> {
+        let mut mg = g.copy();
+        if mg.directed() {
+            mg.pause();
+            Transform::undirect(&mut mg);
+            mg.resume();
+            assert!(!mg.directed());
+        }
+        mg
+    }
+
+    fn layered_path<N: NodeId, W: Weight>(
+        adj: &BTreeMap<N, BTreeMap<N, W>>,
+        pairs: &BTreeMap<N, N>,
+        levels: &BTreeMap<N, usize>,
+        node: &N,
+        dead_ends: &mut BTreeSet<N>,
+        path: &mut Vec<N>,
+    ) -> bool {
+        path.push(node.clone());
+        for neighbor in adj[node].keys() {
+            if path.contains(neighbor) {
+                continue;
+            }
+            match pairs.get(neighbor) {
+                None => {
+                    path.push(neighbor.clone());
+                    return true;
+                }
+                Some(next) => {
+                    if dead_ends.contains(next) || levels.get(next) != Some(&(levels[node] + 1)) {
+                        continue;
+                    }
+                    path.push(neighbor.clone());
+                    if Self::layered_path(adj, pairs, levels, next, dead_ends, path) {
+                        return true;
+                    }
+                    path.pop();
+                }
+            }
+        }
+        path.pop();
+        dead_ends.insert(node.clone());
+        false
+    }
+
+    fn augment<N: NodeId, W: Weight>(mg: &mut Graph<N, W>, pairs: &mut BTreeMap<N, N>, path: &[N]) {
+        mg.pause();
+        for link in path.windows(2) {
+            mg.color_link(link[0].clone(), link[1].clone(), (255, 127, 0));
+        }
+        mg.resume();
+
+        for link in path.chunks(2) {
+            pairs.insert(link[0].clone(), link[1].clone());
+            pairs.insert(link[1].clone(), link[0].clone());
+        }
+
+        mg.pause();
+        for (i, link) in path.windows(2).enumerate() {
+            let color = match i % 2 {
+                0 => (0, 192, 0),
+                _ => Color::default(),
+            };
+            mg.color_link(link[0].clone(), link[1].clone(), color);
+        }
+        mg.resume();
+    }
+
+    fn highlight<N: NodeId, W: Weight>(mg: &mut Graph<N, W>, matching: &[(N, N)]) {
+        let matched: BTreeSet<(N, N)> = matching.iter().cloned().collect();
+        mg.sleep(1000);
+        mg.pause();
+        for (node_from, neighbors) in mg.adjacency_list() {
+            for node_to in neighbors.keys() {
+                if node_from > *node_to {
+                    continue;
+                }
+                let link = (node_from.clone(), node_to.clone());
+                let reversed = (node_to.clone(), node_from.clone());
+                let color = match matched.contains(&link) || matched.contains(&reversed) {
+                    true => (0, 0, 255),
+                    false => Color::disabled(),
+                };
+                mg.color_link(node_from.clone(), node_to.clone(), color);
+            }
+        }
+        for (node_l, node_r) in matching {
+            mg.color_node(node_l.clone(), (0, 0, 255));
+            mg.color_node(node_r.clone(), (0, 0, 255));
+        }
+        mg.resume();
+    }
+}
@@ -0,0 +1,282 @@
+use crate::algo::utils::check::Check;
+use crate::algo::utils::color::Color;
+use crate::graph::{Graph, GraphError, NodeId, Weight};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashSet};
+
+pub struct ShortestPath;
+
+impl ShortestPath {
+    /// Returns the shortest paths from the source node (Dijkstra), as distances and predecessors maps.
+    /// Links values must not be negative: an error is returned otherwise, or if the source node does not exist. Parallel links count with their lowest value.
+    #[allow(clippy::type_complexity)]
+    pub fn dijkstra<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+        source: N,
+    ) -> Result<(Graph<N, W>, BTreeMap<N, W>, BTreeMap<N, N>), GraphError<N>> {
+        Self::best_first(g, source, None)
+    }
+
+    /// Returns the shortest path from the source node to the target node (A*), as distances and predecessors maps of the settled nodes.
+    /// The heuristic is the distance between nodes positions in the SVG graphic context, so it is admissible when links values are not lower than these distances.
+    /// The heuristic does not need to be consistent: a settled node is reopened when a shorter path to it is found.
+    /// Links values must not be negative: an error is returned otherwise, or if the source or target node does not exist. Parallel links count with their lowest value.
+    #[allow(clippy::type_complexity)]
+    pub fn a_star<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+        source: N,
+        target: N,
+    ) -> Result<(Graph<N, W>, BTreeMap<N, W>, BTreeMap<N, N>), GraphError<N>> {
+        Check::node(g, &target)?;
+        let (mut sg, distances, predecessors) =
+            Self::best_first(g, source.clone(), Some(target.clone()))?;
+
+        let path = Self::path(&predecessors, source, target);
+        sg.pause();
+        for node in path.windows(2) {
+            sg.color_link(node[0].clone(), node[1].clone(), (0, 0, 255));
+        }
+        sg.resume();
+
+        Ok((sg, distances, predecessors))
+    }
+
+    /// Returns the shortest paths from the source node (Bellman-Ford), as distances and predecessors maps, and a negative cycle (empty if there is none).
+    /// When a negative cycle is reachable from the source node, it is highlighted and the returned distances are meaningless. Parallel links count with their lowest value.
+    /// An error is returned if the source node does not exist.
+    #[allow(clippy::type_complexity)]
+    pub fn bellman_ford<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+        source: N,
+    ) -> Result<(Graph<N, W>, BTreeMap<N, W>, BTreeMap<N, N>, Vec<N>), GraphError<N>> {
+        Check::node(g, &source)?;
+        let mut sg = g.copy();
+
+        let adj = sg.merged_adjacency_list(|a, b| if b < a { b } else { a });
+        let mut links = Vec::new();
+        for (node_from, neighbors) in &adj {
+            for (node_to, value) in neighbors {
+                links.push((node_from.clone(), node_to.clone(), *value));
+            }
+        }
+
+        let mut distances = BTreeMap::new();
+        let mut predecessors = BTreeMap::new();
+        distances.insert(source.clone(), W::default());
+        sg.pause();
+        sg.color_label(source.clone(), (0, 255, 0));
+        sg.set_node_badge(source.clone(), &W::default().to_string());
+        sg.resume();
+
+        for _ in 1..adj.len() {
+            let mut relaxed = false;
+            for (node_from, node_to, value) in &links {
+                let candidate = match distances.get(node_from) {
+                    Some(distance) => *distance + *value,
+                    None => continue,
+                };
+                if distances.get(node_to).is_some_and(|d| candidate >= *d) {
+                    continue;
+                }
+                relaxed = true;
+                distances.insert(node_to.clone(), candidate);
+                sg.caption(&format!("relaxing link {} - {}", node_from, node_to));
+                sg.pause();
+                if let Some(previous) = predecessors.insert(node_to.clone(), node_from.clone()) {
+                    sg.color_link(previous, node_to.clone(), Color::default());
+                }
+                sg.color_link(node_from.clone(), node_to.clone(), (0, 192, 0));
+                sg.color_label(node_to.clone(), (0, 255, 0));
+                sg.set_node_badge(node_to.clone(), &candidate.to_string());
+                sg.resume();
+            }
+            if !relaxed {
+                break;
+            }
+        }
+
+        let mut cycle = Vec::new();
+        for (node_from, node_to, value) in &links {
+            let candidate = match distances.get(node_from) {
+                Some(distance) => *distance + *value,
+                None => continue,
+            };
+            if candidate >= distances[node_to] {
+                continue;
+            }
+
+            predecessors.insert(node_to.clone(), node_from.clone());
+            let mut node = node_to.clone();
+            for _ in 0..adj.len() {
+                node = predecessors[&node].clone();
+            }
+            let start = node.clone();
+            cycle.push(start.clone());
+            loop {
+                node = predecessors[&node].clone();
+                cycle.push(node.clone());
+                if node == start {
+                    break;
+                }
+            }
+            cycle.reverse();
+            break;
+        }
+
+        match cycle.is_empty() {
+            true => sg.caption("shortest distances found"),
+            false => sg.caption("negative cycle found"),
+        }
+        sg.pause();
+        if cycle.is_empty() {
+            for node in distances.keys() {
+                sg.color_node(node.clone(), (0, 192, 0));
+            }
+        } else {
+            for node in cycle.windows(2) {
+                sg.color_node(node[0].clone(), (255, 0, 0));
+                sg.color_link(node[0].clone(), node[1].clone(), (255, 0, 0));
+            }
+        }
+        sg.resume();
+
+        Ok((sg, distances, predecessors, cycle))
+    }
+
+    /// Returns the path from the source node to the target node along the predecessors map (only the source node if the target node is the source node, empty if the target node has not been reached).
+    pub fn path<N: NodeId>(predecessors: &BTreeMap<N, N>, source: N, target: N) -> Vec<N> {
+        let mut path = vec![target];
+        while let Some(node) = predecessors.get(path.last().unwrap()) {
+            if path.contains(node) {
+                break;
+            }
+            path.push(node.clone());
+        }
+        if path.last() != Some(&source) {
+            return Vec::new();
+        }
+        path.reverse();
+        path
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn best_first<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+        source: N,
+        target: Option<N>,
+    ) -> Result<(Graph<N, W>, BTreeMap<N, W>, BTreeMap<N, N>), GraphError<N>> {
+        Check::node(g, &source)?;
+        Check::non_negative(g)?;
+        let mut sg = g.copy();
+
+        let adj = sg.merged_adjacency_list(|a, b| if b < a { b } else { a });
+
+        let mut heuristics = BTreeMap::new();
+        if let Some(target) = &target {
+            let (x_t, y_t, _) = sg.node_position(target.clone());
+            for node in adj.keys() {
+                let (x, y, _) = sg.node_position(node.clone());
+                let (dx, dy) = ((x - x_t) as f64, (y - y_t) as f64);
+                heuristics.insert(node.clone(), (dx * dx + dy * dy).sqrt());
+            }
+        }
+
+        let mut distances = BTreeMap::new();
+        let mut predecessors: BTreeMap<N, N> = BTreeMap::new();
+        let mut settled = HashSet::new();
+        let mut queue = BinaryHeap::new();
+        let priority =
+            |node: &N, distance: W| distance.to_f64() + heuristics.get(node).cloned().unwrap_or(0.);
+        distances.insert(source.clone(), W::default());
+        queue.push(Reverse(Entry(
+            priority(&source, W::default()),
+            source.clone(),
+        )));
+        sg.pause();
+        sg.color_label(source.clone(), (0, 255, 0));
+        sg.set_node_badge(source, &W::default().to_string());
+        sg.resume();
+
+        while let Some(Reverse(Entry(queued, node))) = queue.pop() {
+            if settled.contains(&node) || queued > priority(&node, distances[&node]) {
+                continue;
+            }
+
+            settled.insert(node.clone());
+            sg.caption(&format!(
+                "settling {} at distance {}",
+                node, distances[&node]
+            ));
+            sg.pause();
+            sg.color_node(node.clone(), (0, 192, 0));
+            if let Some(predecessor) = predecessors.get(&node) {
+                sg.color_link(predecessor.clone(), node.clone(), (0, 192, 0));
+            }
+            sg.resume();
+            if target.as_ref() == Some(&node) {
+                break;
+            }
+
+            let distance = distances[&node];
+            for (neighbor, value) in &adj[&node] {
+                let candidate = distance + *value;
+                if settled.contains(neighbor) && candidate >= distances[neighbor] {
+                    continue;
+                }
+                sg.caption(&format!("relaxing link {} - {}", node, neighbor));
+                sg.color_link(node.clone(), neighbor.clone(), (255, 127, 0));
+
+                if distances.get(neighbor).is_some_and(|d| candidate >= *d) {
+                    sg.color_link(node.clone(), neighbor.clone(), Color::disabled());
+                    continue;
+                }
+                distances.insert(neighbor.clone(), candidate);
+                queue.push(Reverse(Entry(
+                    priority(neighbor, candidate),
+                    neighbor.clone(),
+                )));
+                if let Some(previous) = predecessors.insert(neighbor.clone(), node.clone()) {
+                    sg.color_link(previous, neighbor.clone(), Color::disabled());
+                }
+                sg.pause();
+                if settled.remove(neighbor) {
+                    sg.caption(&format!("reopening {}", neighbor));
+                    sg.color_node(neighbor.clone(), Color::default());
+                }
+                sg.color_label(neighbor.clone(), (0, 255, 0));
+                sg.set_node_badge(neighbor.clone(), &candidate.to_string());
+                sg.resume();
+            }
+        }
+
+        distances.retain(|node, _| settled.contains(node));
+        predecessors.retain(|node, _| settled.contains(node));
+
+        Ok((sg, distances, predecessors))
+    }
+}
+
+/// Queued node with its priority (distance plus heuristic), ordered by priority then by node.
+struct Entry<N>(f64, N);
+
+impl<N: Ord> Ord for Entry<N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0
+            .total_cmp(&other.0)
+            .then_with(|| self.1.cmp(&other.1))
+    }
+}
+
+impl<N: Ord> PartialOrd for Entry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N: Ord> PartialEq for Entry<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N: Ord> Eq for Entry<N> {}
@@ -1,62 +1,64 @@
 use crate::algo::connectivity::Connectivity;
 use crate::algo::utils::transform::Transform;
-use crate::graph::Graph;
+use crate::graph::{Graph, NodeId, Weight};
 use std::cmp::max;
+use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::f64::consts::PI;
 
 pub struct Tree {}
 
 impl Tree {
     // Layouts the graph as a tree
-    pub fn layout_as_tree(g: &Graph, root: char) -> Graph {
+    pub fn layout_as_tree<N: NodeId, W: Weight>(g: &Graph<N, W>, root: N) -> Graph<N, W> {
         Self::bfs(g, root, true)
     }
 
     // Returns a tree from BFS traversing
-    pub fn bfs_tree(g: &Graph, root: char) -> Graph {
+    pub fn bfs_tree<N: NodeId, W: Weight>(g: &Graph<N, W>, root: N) -> Graph<N, W> {
         Self::bfs(g, root, false)
     }
 
-    fn bfs(g: &Graph, root: char, layout: bool) -> Graph {
+    fn bfs<N: NodeId, W: Weight>(g: &Graph<N, W>, root: N, layout: bool) -> Graph<N, W> {
         let (_, components) = Connectivity::components(g);
         assert!(components.len() == 1);
 
-        let mut tree = Graph::new();
-        tree.from_str(&g.to_string());
+        let mut tree = g.copy();
         if tree.directed() {
             tree.pause();
             Transform::undirect(&mut tree);
             tree.resume();
         }
 
-        let mut steps: BTreeMap<i32, BTreeMap<char, Vec<char>>> = BTreeMap::new();
-        let mut parents_order: BTreeMap<i32, Vec<char>> = BTreeMap::new();
+        let mut steps: BTreeMap<i32, BTreeMap<Option<N>, Vec<N>>> = BTreeMap::new();
+        let mut parents_order: BTreeMap<i32, Vec<N>> = BTreeMap::new();
         let mut visited = HashSet::new();
         let mut inqueued = HashSet::new();
         let mut queue = VecDeque::new();
         let mut deleted_links = Vec::new();
-        queue.push_back((0, ' ', root));
-        inqueued.insert(root);
-        tree.color_label(root, (0, 255, 0));
+        queue.push_back((0, None, root.clone()));
+        inqueued.insert(root.clone());
+        tree.color_label(root.clone(), (0, 255, 0));
         while let Some((step, parent, child)) = queue.pop_front() {
             inqueued.remove(&child);
-            visited.insert(child);
-            tree.color_node(child, (0, 192, 0));
+            visited.insert(child.clone());
+            tree.color_node(child.clone(), (0, 192, 0));
 
             steps
                 .entry(step)
                 .or_default()
                 .entry(parent)
                 .or_default()
-                .push(child);
+                .push(child.clone());
             let po_entry = parents_order.entry(step + 1).or_default();
             if !po_entry.contains(&child) {
-                po_entry.push(child);
+                po_entry.push(child.clone());
             }
 
-            for node in tree.neighbors(child) {
+            for node in tree.neighbors(child.clone()) {
                 if node == child {
                     continue;
                 }
@@ -64,76 +66,251 @@ impl Tree {
                     continue;
                 }
                 if inqueued.contains(&node) {
-                    tree.color_link(child, node, (192, 0, 0));
-                    tree.delete_link(child, node);
-                    deleted_links.push((child, node));
+                    tree.color_link(child.clone(), node.clone(), (192, 0, 0));
+                    tree.delete_link(child.clone(), node.clone());
+                    deleted_links.push((child.clone(), node));
                     continue;
                 }
 
-                tree.color_label(node, (0, 255, 0));
-                inqueued.insert(node);
-                queue.push_back((step + 1, child, node));
+                tree.color_label(node.clone(), (0, 255, 0));
+                inqueued.insert(node.clone());
+                queue.push_back((step + 1, Some(child.clone()), node));
             }
         }
 
-        tree.pause();
-        let (x_r, y_r, _) = tree.node_position(root);
-        let d = 60;
-        for (step, parents) in &steps {
-            if *step == 0 {
-                continue;
+        Self::tidy_layout(&mut tree, root);
+
+        if layout {
+            for (node_from, node_to) in deleted_links {
+                tree.add_link(node_from, node_to, true, W::default());
             }
-            let y_c = y_r + step * d;
-            let mut x_c = x_r;
-            for parent in parents_order.get(step).unwrap() {
-                if let Some(childs) = parents.get(parent) {
-                    let (x_p, _, _) = tree.node_position(*parent);
-                    x_c = max(x_c, x_p);
-                    for child in childs {
-                        tree.move_node(*child, (x_c, y_c));
-                        x_c += d;
+        }
+
+        tree
+    }
+
+    // Places the nodes below the root as a tidy tree (Reingold-Tilford): parents are centered above their children,
+    // and subtrees are packed as close as possible. The tree is a BFS spanning tree from the root (links direction is ignored).
+    // The root keeps its position, and the positions of the placed nodes are freezed.
+    pub fn tidy_layout<N: NodeId, W: Weight>(g: &mut Graph<N, W>, root: N) {
+        let (children, order) = Self::spanning_tree(g, &root);
+        let d = Self::level_distance(g);
+
+        // subtrees are placed bottom-up: each one gets its left and right contours (x at each depth, relative to its root)
+        let mut contours: BTreeMap<N, (Vec<f64>, Vec<f64>)> = BTreeMap::new();
+        let mut offsets: BTreeMap<N, f64> = BTreeMap::new();
+        for node in order.iter().rev() {
+            let mut left: Vec<f64> = Vec::new();
+            let mut right: Vec<f64> = Vec::new();
+            let mut xs = Vec::new();
+            for child in &children[node] {
+                let (c_left, c_right) = contours.remove(child).unwrap();
+                let shift = if xs.is_empty() {
+                    0.
+                } else {
+                    right
+                        .iter()
+                        .zip(&c_left)
+                        .map(|(r, l)| r - l + d)
+                        .fold(f64::MIN, f64::max)
+                };
+                for (depth, x) in c_right.iter().enumerate() {
+                    if depth < right.len() {
+                        right[depth] = x + shift;
+                    } else {
+                        right.push(x + shift);
                     }
                 }
+                for x in c_left.iter().skip(left.len()) {
+                    left.push(x + shift);
+                }
+                xs.push(shift);
+            }
+            let middle = match (xs.first(), xs.last()) {
+                (Some(first), Some(last)) => (first + last) / 2.,
+                _ => 0.,
+            };
+            for (child, x) in children[node].iter().zip(xs) {
+                offsets.insert(child.clone(), x - middle);
             }
+            let left = [0.].into_iter().chain(left.iter().map(|x| x - middle));
+            let right = [0.].into_iter().chain(right.iter().map(|x| x - middle));
+            contours.insert(node.clone(), (left.collect(), right.collect()));
         }
 
-        for (step, parents) in steps.iter().rev() {
-            if *step == 0 {
-                continue;
+        let mut positions = BTreeMap::new();
+        positions.insert(root.clone(), (0., 0.));
+        for node in &order {
+            let (x, y) = positions[node];
+            for child in &children[node] {
+                positions.insert(child.clone(), (x + offsets[child], y + d));
             }
+        }
+        Self::place(g, &root, positions);
+    }
 
-            let mut x_prec = 0;
-            for parent in parents_order.get(step).unwrap() {
-                let (mut x_p, y_p, _) = tree.node_position(*parent);
-                if let Some(childs) = parents.get(parent) {
-                    x_p = 0;
-                    for child in childs {
-                        let (x_c, _, _) = tree.node_position(*child);
-                        x_p += x_c;
-                    }
-                    x_p /= childs.len() as i32;
+    // Places the nodes on concentric circles around the root, one circle per depth of a BFS spanning tree from the root.
+    // Each subtree gets an angular sector proportional to its leaves count.
+    // The root keeps its position, and the positions of the placed nodes are freezed.
+    pub fn radial_layout<N: NodeId, W: Weight>(g: &mut Graph<N, W>, root: N) {
+        let (children, order) = Self::spanning_tree(g, &root);
+        let d = Self::level_distance(g);
+        let leaves = Self::leaves_count(&children, &order);
+
+        // sectors are split top-down, then circles are spaced so that the narrowest sector of each depth holds a node
+        let mut sectors: BTreeMap<N, (f64, f64, usize)> = BTreeMap::new();
+        sectors.insert(root.clone(), (-PI / 2., 2. * PI, 0));
+        let mut radius = d;
+        for node in &order {
+            let (mut start, width, depth) = sectors[node];
+            if depth > 0 {
+                radius = radius.max(d / (depth as f64 * width.min(PI)));
+            }
+            for child in &children[node] {
+                let child_width = width * leaves[child] as f64 / leaves[node] as f64;
+                sectors.insert(child.clone(), (start, child_width, depth + 1));
+                start += child_width;
+            }
+        }
+
+        let positions = sectors
+            .into_iter()
+            .map(|(node, (start, width, depth))| {
+                let angle = start + width / 2.;
+                let r = radius * depth as f64;
+                (node, (r * angle.cos(), r * angle.sin()))
+            })
+            .collect();
+        Self::place(g, &root, positions);
+    }
+
+    // Places the nodes below the root as a dendrogram: leaves are evenly spaced on the deepest level of a BFS spanning tree
+    // from the root, and parents are centered above their children.
+    // The root keeps its position, and the positions of the placed nodes are freezed.
+    pub fn dendrogram_layout<N: NodeId, W: Weight>(g: &mut Graph<N, W>, root: N) {
+        let (children, order) = Self::spanning_tree(g, &root);
+        let d = Self::level_distance(g);
+
+        let mut depths = BTreeMap::new();
+        depths.insert(root.clone(), 0);
+        for node in &order {
+            for child in &children[node] {
+                depths.insert(child.clone(), depths[node] + 1);
+            }
+        }
+        let depth_max = depths.values().max().copied().unwrap_or(0);
+
+        // leaves in depth-first order
+        let mut xs: BTreeMap<N, f64> = BTreeMap::new();
+        let mut stack = vec![root.clone()];
+        while let Some(node) = stack.pop() {
+            if children[&node].is_empty() {
+                xs.insert(node, xs.len() as f64 * d);
+            } else {
+                stack.extend(children[&node].iter().rev().cloned());
+            }
+        }
+        for node in order.iter().rev() {
+            if let (Some(first), Some(last)) = (children[node].first(), children[node].last()) {
+                xs.insert(node.clone(), (xs[first] + xs[last]) / 2.);
+            }
+        }
+
+        let positions = xs
+            .into_iter()
+            .map(|(node, x)| {
+                let depth = if children[&node].is_empty() {
+                    depth_max
                 } else {
-                    x_p = max(x_p, x_prec + d);
+                    depths[&node]
+                };
+                (node, (x, depth as f64 * d))
+            })
+            .collect();
+        Self::place(g, &root, positions);
+    }
+
+    // Returns the children of the nodes in a BFS spanning tree from the root, and the reached nodes in BFS order
+    fn spanning_tree<N: NodeId, W: Weight>(
+        g: &Graph<N, W>,
+        root: &N,
+    ) -> (BTreeMap<N, Vec<N>>, Vec<N>) {
+        let mut neighbors: BTreeMap<N, BTreeSet<N>> = BTreeMap::new();
+        for (node, successors) in g.adjacency_list() {
+            for other in successors.into_keys() {
+                if other != node {
+                    neighbors
+                        .entry(other.clone())
+                        .or_default()
+                        .insert(node.clone());
+                    neighbors.entry(node.clone()).or_default().insert(other);
                 }
-                tree.move_node(*parent, (x_p, y_p));
-                x_prec = x_p;
             }
         }
-        tree.resume();
 
-        if layout {
-            for (node_from, node_to) in deleted_links {
-                tree.add_link(node_from, node_to, true, 0);
+        let mut children: BTreeMap<N, Vec<N>> = BTreeMap::new();
+        let mut order = vec![root.clone()];
+        let mut visited = HashSet::new();
+        visited.insert(root.clone());
+        let mut i = 0;
+        while i < order.len() {
+            let node = order[i].clone();
+            let mut childs = Vec::new();
+            for other in neighbors.get(&node).into_iter().flatten() {
+                if visited.insert(other.clone()) {
+                    childs.push(other.clone());
+                    order.push(other.clone());
+                }
             }
+            children.insert(node, childs);
+            i += 1;
         }
 
-        tree
+        (children, order)
+    }
+
+    // Returns the leaves count of each subtree
+    fn leaves_count<N: NodeId>(children: &BTreeMap<N, Vec<N>>, order: &[N]) -> BTreeMap<N, usize> {
+        let mut leaves = BTreeMap::new();
+        for node in order.iter().rev() {
+            let count = children[node].iter().map(|child| leaves[child]).sum();
+            leaves.insert(node.clone(), max(count, 1));
+        }
+        leaves
+    }
+
+    // Distance between two levels, and between two neighbor nodes of a level
+    fn level_distance<N: NodeId, W: Weight>(g: &Graph<N, W>) -> f64 {
+        max(60, 4 * g.node_radius() as i32) as f64
+    }
+
+    // Moves the nodes to their positions relative to the root, in a single animation step
+    fn place<N: NodeId, W: Weight>(
+        g: &mut Graph<N, W>,
+        root: &N,
+        positions: BTreeMap<N, (f64, f64)>,
+    ) {
+        let (x_r, y_r, _) = g.node_position(root.clone());
+        let (x_0, y_0) = positions[root];
+        let paused = g.paused();
+        if !paused {
+            g.pause();
+        }
+        for (node, (x, y)) in positions {
+            let xy = (
+                x_r + (x - x_0).round() as i32,
+                y_r + (y - y_0).round() as i32,
+            );
+            g.move_node(node, xy);
+        }
+        if !paused {
+            g.resume();
+        }
     }
 
     // Returns a minimal spanning tree
-    pub fn minimal_spanning_tree(g: &Graph) -> Graph {
-        let mut stg = Graph::new();
-        stg.from_str(&g.to_string());
+    pub fn minimal_spanning_tree<N: NodeId, W: Weight>(g: &Graph<N, W>) -> Graph<N, W> {
+        let mut stg = g.copy();
 
         if stg.directed() {
             stg.pause();
@@ -146,37 +323,37 @@ impl Tree {
 
         let nodes = stg.nodes();
         assert!(!nodes.is_empty());
-        let mut start = nodes[0];
-        stg.color_node(start, (0, 192, 0));
-        stg.color_label(start, (0, 255, 0));
+        let mut start = nodes[0].clone();
+        stg.color_node(start.clone(), (0, 192, 0));
+        stg.color_label(start.clone(), (0, 255, 0));
 
         let mut links = Vec::new();
-        let adj = stg.adjacency_list();
+        let adj = stg.merged_adjacency_list(|a, b| if b < a { b } else { a });
         for (node_to, link_value) in adj.get(&start).unwrap() {
-            links.push((start, *node_to, *link_value));
-            stg.color_label(*node_to, (0, 255, 0));
+            links.push((start.clone(), node_to.clone(), *link_value));
+            stg.color_label(node_to.clone(), (0, 255, 0));
         }
 
         let mut visited = Vec::new();
         visited.push(start);
         while !links.is_empty() {
-            links.sort_by(|(_, _, a), (_, _, b)| b.cmp(a));
+            links.sort_by(|(_, _, a), (_, _, b)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
             let (node_from, node_to, _) = links.pop().unwrap();
             if visited.contains(&node_to) {
-                stg.color_link(node_from, node_to, (192, 0, 0));
+                stg.color_link(node_from.clone(), node_to.clone(), (192, 0, 0));
                 stg.delete_link(node_from, node_to);
                 continue;
             }
-            stg.color_link(node_from, node_to, (0, 192, 0));
-            stg.color_node(node_to, (0, 196, 0));
-            visited.push(node_to);
+            stg.color_link(node_from, node_to.clone(), (0, 192, 0));
+            stg.color_node(node_to.clone(), (0, 196, 0));
+            visited.push(node_to.clone());
             start = node_to;
             for (node_to, link_value) in adj.get(&start).unwrap() {
-                if visited.contains(&node_to) {
+                if visited.contains(node_to) {
                     continue;
                 }
-                links.push((start, *node_to, *link_value));
-                stg.color_label(*node_to, (0, 255, 0));
+                links.push((start.clone(), node_to.clone(), *link_value));
+                stg.color_label(node_to.clone(), (0, 255, 0));
             }
         }
 
@@ -1,2 +1,3 @@
+pub mod check;
 pub mod color;
 pub mod transform;
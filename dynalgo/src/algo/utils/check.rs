@@ -0,0 +1,21 @@
+use crate::graph::{Graph, GraphError, NodeId, Weight};
+
+pub struct Check;
+
+impl Check {
+    pub fn node<N: NodeId, W: Weight>(g: &Graph<N, W>, node: &N) -> Result<(), GraphError<N>> {
+        match g.adjacency_list().contains_key(node) {
+            true => Ok(()),
+            false => Err(GraphError::NodeMissing(node.clone())),
+        }
+    }
+
+    pub fn non_negative<N: NodeId, W: Weight>(g: &Graph<N, W>) -> Result<(), GraphError<N>> {
+        for (node_from, node_to, _, value) in g.link_entries() {
+            if value < W::default() {
+                return Err(GraphError::NegativeValue(node_from, node_to));
+            }
+        }
+        Ok(())
+    }
+}
@@ -13,6 +13,32 @@ impl Color {
         ]
     }
 
+    /// Returns the color of the given index: the `colors` palette first, then hues spread by the golden angle so that colors never repeat.
+    pub fn distinct(index: usize) -> (u8, u8, u8) {
+        let colors = Self::colors();
+        if index < colors.len() {
+            return colors[index];
+        }
+        let index = index - colors.len();
+        let hue = (index as f64 * 0.618_033_988_75 + 0.1).fract() * 6.;
+        let saturation = [0.85, 0.55, 1.][index / 3 % 3];
+        let value = [0.95, 0.75, 0.55][index % 3];
+
+        let chroma = value * saturation;
+        let x = chroma * (1. - (hue % 2. - 1.).abs());
+        let (r, g, b) = match hue as u8 {
+            0 => (chroma, x, 0.),
+            1 => (x, chroma, 0.),
+            2 => (0., chroma, x),
+            3 => (0., x, chroma),
+            4 => (x, 0., chroma),
+            _ => (chroma, 0., x),
+        };
+        let m = value - chroma;
+        let channel = |c: f64| ((c + m) * 255.).round() as u8;
+        (channel(r), channel(g), channel(b))
+    }
+
     pub fn default() -> (u8, u8, u8) {
         (47, 79, 79)
     }
@@ -1,33 +1,49 @@
-use crate::graph::Graph;
+use crate::graph::{Graph, NodeId, Weight};
+use std::collections::BTreeSet;
 
 pub struct Transform;
 
 impl Transform {
-    pub fn undirect(g: &mut Graph) {
-        let m = g.adjacency_matrix();
-        let nodes = g.nodes();
-        for node_from in nodes.iter() {
-            for node_to in nodes.iter() {
-                let value = m[node_from][node_to];
-                if value.is_some() && (m[node_to][node_from]).is_none() {
-                    g.delete_link(*node_from, *node_to);
-                    g.add_link(*node_to, *node_from, true, value.unwrap());
-                }
-            }
-        }
+    pub fn undirect<N: NodeId, W: Weight>(g: &mut Graph<N, W>) {
+        Self::relink(g, |(node_from, node_to, _, value)| {
+            (node_from, node_to, true, value)
+        });
+    }
+
+    pub fn transpose<N: NodeId, W: Weight>(g: &mut Graph<N, W>) {
+        Self::relink(g, |(node_from, node_to, bidirect, value)| match bidirect {
+            true => (node_from, node_to, bidirect, value),
+            false => (node_to, node_from, bidirect, value),
+        });
     }
 
-    pub fn transpose(g: &mut Graph) {
-        let m = g.adjacency_matrix();
-        let nodes = g.nodes();
-        for node_from in nodes.iter() {
-            for node_to in nodes.iter() {
-                let value = m[node_from][node_to];
-                if value.is_some() && (m[node_to][node_from]).is_none() {
-                    g.delete_link(*node_from, *node_to);
-                    g.add_link(*node_to, *node_from, false, value.unwrap());
-                }
-            }
+    /// Replaces every link between two nodes as soon as one of their links (parallel links included) is changed by `transform`,
+    /// the links being deleted and added again in creation order.
+    fn relink<N: NodeId, W: Weight>(
+        g: &mut Graph<N, W>,
+        transform: impl Fn((N, N, bool, W)) -> (N, N, bool, W),
+    ) {
+        let pair = |node_1: &N, node_2: &N| match node_1 < node_2 {
+            true => (node_1.clone(), node_2.clone()),
+            false => (node_2.clone(), node_1.clone()),
+        };
+        let links = g.link_entries();
+        let pairs: BTreeSet<(N, N)> = links
+            .iter()
+            .filter(|link| transform((*link).clone()) != **link)
+            .map(|(node_from, node_to, _, _)| pair(node_from, node_to))
+            .collect();
+        let links: Vec<(N, N, bool, W)> = links
+            .into_iter()
+            .filter(|(node_from, node_to, _, _)| pairs.contains(&pair(node_from, node_to)))
+            .collect();
+
+        for (node_from, node_to, _, _) in links.iter() {
+            g.delete_link(node_from.clone(), node_to.clone());
+        }
+        for link in links {
+            let (node_from, node_to, bidirect, value) = transform(link);
+            g.add_link(node_from, node_to, bidirect, value);
         }
     }
 }
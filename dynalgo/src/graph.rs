@@ -1,41 +1,297 @@
 //! Basic `graph` structure representation with animation properties.
 
+mod io;
+pub mod layout;
 mod renderer;
 
-use renderer::color::Color;
+use layout::{Layout, Springs};
 use renderer::html::Html;
 use renderer::point::Point;
 use renderer::Renderer;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::error::Error;
 use std::fmt;
 use std::fs::File;
+use std::hash::Hash;
 use std::io::Write;
+use std::ops::{Add, Sub};
+use std::rc::Rc;
 use std::str::FromStr;
 
-struct GraphError {
-    action: String,
-    message: String,
+/// Node identifier of a `Graph` (e.g. `char`, the default, or `String`).
+///
+/// Identifiers are read from graph configurations with `FromStr`, so they can't contain whitespaces or commas.
+pub trait NodeId: Ord + Hash + Clone + fmt::Debug + fmt::Display + FromStr {}
+
+impl<T: Ord + Hash + Clone + fmt::Debug + fmt::Display + FromStr> NodeId for T {}
+
+/// Link value type of a `Graph` (e.g. `i64`, the default, or `f64`).
+///
+/// The default value (zero for numbers) is used for links created without value, and is not displayed.
+pub trait Weight:
+    Copy
+    + PartialOrd
+    + Default
+    + fmt::Debug
+    + fmt::Display
+    + FromStr
+    + Add<Output = Self>
+    + Sub<Output = Self>
+{
+    /// Converts the value to a `f64` (e.g. to compare it with distances in the SVG graphic context).
+    fn to_f64(self) -> f64;
 }
 
-impl fmt::Display for GraphError {
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(impl Weight for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+impl_weight!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
+/// Output format of the animation timeline in HTML pages and SVG documents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationFormat {
+    /// SVG SMIL `<animate>` elements, played natively by the browser.
+    #[default]
+    Smil,
+    /// A compact JSON timeline played by a tiny embedded JavaScript player, for viewers without SMIL support.
+    Script,
+}
+
+/// Text format of the graph structure (see `Graph::to_string_as` and `Graph::from_str_as`).
+///
+/// Positions are kept for freezed nodes. DOT, GraphML and JSON also keep the nodes and links colors differing from the graph style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphFormat {
+    /// Graph configuration, as read by `Graph::from_str` (e.g. `A 0 0, B, A - B 3`, preceded by `* multigraph` for a multigraph).
+    #[default]
+    Config,
+    /// Graphviz DOT language: freezed positions are pinned `pos` attributes (y axis pointing up), link values are labels
+    /// (or weights when read), bidirectional links of a `digraph` have `dir=none`, and a `strict` graph merges its duplicate links.
+    Dot,
+    /// GraphML: nodes have `x`, `y`, `freezed`, `fill` and `stroke` data, links have `value` (or `weight`) and `color` data.
+    GraphMl,
+    /// JSON document:
+    /// `{"nodes": [{"id": "A", "x": 0, "y": 0, "freezed": true, "fill": "#ffffff", "stroke": "#2f4f4f"}, ...],
+    /// "links": [{"from": "A", "to": "B", "value": 3, "directed": false, "color": "#2f4f4f"}, ...]}`.
+    ///
+    /// Only `id`, `from` and `to` are required. Nodes with `x` and `y` are freezed unless `freezed` is false.
+    Json,
+    /// CSV edge list `from,to,value,directed` with a header line: nodes without links have empty `to`, `value` and `directed` fields.
+    /// Positions and colors are not kept.
+    Csv,
+}
+
+/// Shape of a node in the graphic context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Shape {
+    #[default]
+    Circle,
+    Square,
+    Diamond,
+}
+
+/// Visual theme of a graph animation and of the HTML page rendering it (see `Graph::with_style`).
+/// Colors are (red, green, blue) values.
+///
+/// # Example
+/// ```
+/// use dynalgo::graph::{Graph, Shape, Style};
+///
+/// let mut style = Style::dark();
+/// style.node_radius = 16;
+/// style.node_shapes.insert('A', Shape::Diamond);
+///
+/// let mut graph: Graph = Graph::with_style(style);
+/// graph.from_str("A, B, A - B");
+///
+/// assert_eq!(graph.node_radius(), 16);
+/// assert!(graph.to_svg().contains("transform=\"rotate(45"));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Style<N = char> {
+    pub node_fill: (u8, u8, u8),
+    pub node_stroke: (u8, u8, u8),
+    /// Nodes names color, also used for the HTML page text and the captions.
+    pub node_text: (u8, u8, u8),
+    pub link_stroke: (u8, u8, u8),
+    pub link_text: (u8, u8, u8),
+    /// Background of the HTML page, standalone SVG documents and raster images.
+    pub background: (u8, u8, u8),
+    /// Separators of the HTML page.
+    pub border: (u8, u8, u8),
+    pub node_radius: u8,
+    pub node_stroke_width: u8,
+    pub link_stroke_width: u8,
+    /// SVG texts font family (browser default if `None`). Raster images use a built-in bitmap font.
+    pub font_family: Option<String>,
+    /// SVG texts font size (browser default if `None`).
+    pub font_size: Option<u8>,
+    pub node_shape: Shape,
+    /// Shapes overriding `node_shape`, by node.
+    pub node_shapes: BTreeMap<N, Shape>,
+}
+
+impl<N> Default for Style<N> {
+    fn default() -> Style<N> {
+        Style {
+            node_fill: (255, 255, 255),
+            node_stroke: (47, 79, 79),
+            node_text: (0, 0, 139),
+            link_stroke: (47, 79, 79),
+            link_text: (0, 0, 0),
+            background: (255, 255, 255),
+            border: (211, 211, 211),
+            node_radius: 13,
+            node_stroke_width: 2,
+            link_stroke_width: 2,
+            font_family: None,
+            font_size: None,
+            node_shape: Shape::Circle,
+            node_shapes: BTreeMap::new(),
+        }
+    }
+}
+
+impl<N> Style<N> {
+    /// Light colors on a dark background.
+    pub fn dark() -> Style<N> {
+        Style {
+            node_fill: (45, 45, 48),
+            node_stroke: (160, 190, 190),
+            node_text: (135, 206, 250),
+            link_stroke: (160, 190, 190),
+            link_text: (230, 230, 230),
+            background: (30, 30, 30),
+            border: (70, 70, 70),
+            ..Style::default()
+        }
+    }
+}
+
+/// Errors returned by the fallible `Graph` operations (`try_*` functions) and by the algorithms checking their input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphError<N = char> {
+    /// The node already exists.
+    NodeExists(N),
+    /// The node does not exist.
+    NodeMissing(N),
+    /// The link already exists (`(from, to)` of the existing link), in a graph which is not a multigraph.
+    LinkExists(N, N),
+    /// The link does not exist.
+    LinkMissing(N, N),
+    /// A link from a node to itself was requested, in a graph which is not a multigraph.
+    LoopLink(N),
+    /// The graph configuration is invalid (line and column are 1-based).
+    ParseError {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The animation is not in the state required by the action.
+    AnimationStateError(String),
+    /// The link value is negative, while the algorithm requires values that are not.
+    NegativeValue(N, N),
+    /// The graph or the given nodes do not meet the requirements of the algorithm.
+    AlgorithmError(String),
+}
+
+impl<N: fmt::Display> GraphError<N> {
+    fn action_and_cause(&self) -> (&str, String) {
+        match self {
+            GraphError::NodeExists(node) => ("add a node", format!("node {} already exists", node)),
+            GraphError::NodeMissing(node) => {
+                ("use a node", format!("node '{}' does not exist", node))
+            }
+            GraphError::LinkExists(node_from, node_to) => (
+                "add a link",
+                format!("nodes {} and {} are already linked", node_from, node_to),
+            ),
+            GraphError::LinkMissing(node_from, node_to) => (
+                "use a link",
+                format!("link '{} > {}' does not exist", node_from, node_to),
+            ),
+            GraphError::LoopLink(node) => (
+                "add a link",
+                format!("link {} > {} is invalid (loop is not allowed)", node, node),
+            ),
+            GraphError::ParseError {
+                line,
+                column,
+                message,
+            } => (
+                "parse config",
+                format!("line {}, column {}: {}", line, column, message),
+            ),
+            GraphError::AnimationStateError(message) => ("change animation state", message.clone()),
+            GraphError::NegativeValue(node_from, node_to) => (
+                "run an algorithm",
+                format!("link '{} > {}' value is negative", node_from, node_to),
+            ),
+            GraphError::AlgorithmError(message) => ("run an algorithm", message.clone()),
+        }
+    }
+}
+
+impl<N: fmt::Display> fmt::Display for GraphError<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (action, message) = self.action_and_cause();
         write!(
             f,
             "A Dynalgo graph error occurred while trying to {}.\nCause is : \"{}\"",
-            self.action, self.message
+            action, message
         )
     }
 }
 
+impl<N: fmt::Debug + fmt::Display> Error for GraphError<N> {}
+
+fn or_panic<T, N: fmt::Display>(result: Result<T, GraphError<N>>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{}", error),
+    }
+}
+
 #[derive(PartialEq, Clone, Copy)]
 enum AnimState {
     Paused,
     Resumed,
 }
 
-pub struct Graph {
-    renderer: Renderer,
-    adjacency: BTreeMap<char, BTreeMap<char, i8>>,
+// Changes applied in a single animation step, in order.
+enum Change<N, W> {
+    ColorNodes(Vec<N>, (u8, u8, u8)),
+    ColorLinks(Vec<(N, N)>, (u8, u8, u8)),
+    ColorLastLinks(Vec<(N, N)>, (u8, u8, u8)),
+    MoveNodes(Vec<(N, (i32, i32), bool)>),
+    FillNodes(Vec<N>, (u8, u8, u8)),
+    DeleteLinks(Vec<(N, N)>),
+    DeleteNodes(Vec<N>),
+    AddNodes(Vec<(N, Option<(i16, i16)>)>),
+    AddLinks(Vec<(N, N, bool, W)>),
+    ColorNodesText(Vec<N>, (u8, u8, u8)),
+    ColorLinksText(Vec<(N, N)>, (u8, u8, u8)),
+    BadgeNodes(Vec<(N, String)>),
+    LabelLinks(Vec<(N, N, String)>),
+    ValueLinks(Vec<(N, N, W)>),
+    ResizeNodes(Vec<(N, u8)>),
+    WidenLinks(Vec<(N, N, u8)>),
+}
+
+pub struct Graph<N: NodeId = char, W: Weight = i64> {
+    renderer: Renderer<N>,
+    style: Style<N>,
+    layout: Rc<dyn Layout<N>>,
+    adjacency: BTreeMap<N, BTreeMap<N, W>>,
+    link_list: Vec<(N, N, bool, W)>,
     anim_state: AnimState,
     layout_on_resume: bool,
     duration_on_resume: u32,
@@ -45,50 +301,72 @@ pub struct Graph {
     p_duration_move: u32,
     p_duration_color: u32,
     p_radius: u8,
+    p_multigraph: bool,
+    p_incremental_layout: bool,
+    p_layout_seed: Option<u64>,
 }
 
-impl fmt::Display for Graph {
+impl<N: NodeId, W: Weight> fmt::Display for Graph<N, W> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.graph_config())
     }
 }
 
-impl fmt::Debug for Graph {
+impl<N: NodeId, W: Weight> fmt::Debug for Graph<N, W> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.graph_config())
     }
 }
 
-impl FromStr for Graph {
-    type Err = String;
-    fn from_str(s: &str) -> Result<Graph, String> {
-        let mut graph = Graph::new();
-        graph.from_str(s);
+impl<N: NodeId, W: Weight> FromStr for Graph<N, W> {
+    type Err = GraphError<N>;
+    fn from_str(s: &str) -> Result<Graph<N, W>, GraphError<N>> {
+        let mut graph = Graph::default();
+        graph.try_from_str(s)?;
         Ok(graph)
     }
 }
 
 impl Graph {
-    /// Creates an empty graph structure.
+    /// Creates an empty graph structure with `char` nodes identifiers and `i64` links values.
+    ///
+    /// Use `Graph::<String>::default()` (or any other `NodeId` type) to get readable nodes names,
+    /// and `Graph::<char, f64>::default()` (or any other `Weight` type) to change the links values type.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::<String, f64>::default();
+    /// graph.from_str("Paris, Lyon, Paris - Lyon 465.3");
+    ///
+    /// assert!(graph.neighbors(String::from("Paris")) == vec![String::from("Lyon")]);
+    /// assert!(graph.adjacency_list()["Paris"]["Lyon"] == 465.3);
+    /// ```
     pub fn new() -> Graph {
+        Graph::default()
+    }
+}
+
+impl<N: NodeId, W: Weight> Default for Graph<N, W> {
+    fn default() -> Graph<N, W> {
+        Graph::with_style(Style::default())
+    }
+}
+
+impl<N: NodeId, W: Weight> Graph<N, W> {
+    /// Creates an empty graph structure rendered with the given visual style.
+    pub fn with_style(style: Style<N>) -> Graph<N, W> {
         let animation_speed_factor = 1.0;
-        let node_radius = 13;
         let display_link_value = true;
         let display_node_name = true;
-        let renderer = Renderer::new(
-            display_link_value,
-            display_node_name,
-            Color::new(255, 255, 255),
-            Color::new(47, 79, 79),
-            Color::new(47, 79, 79),
-            Color::new(0, 0, 139),
-            Color::new(0, 0, 0),
-            node_radius,
-        );
+        let renderer = Renderer::new(display_link_value, display_node_name, &style);
 
-        let graph = Graph {
+        Graph {
             renderer,
+            layout: Rc::new(Springs),
             adjacency: BTreeMap::new(),
+            link_list: Vec::new(),
             anim_state: AnimState::Resumed,
             layout_on_resume: false,
             duration_on_resume: 1,
@@ -97,10 +375,12 @@ impl Graph {
             p_duration_delete: 300,
             p_duration_move: 900,
             p_duration_color: 600,
-            p_radius: node_radius,
-        };
-
-        graph
+            p_radius: style.node_radius,
+            p_multigraph: false,
+            p_incremental_layout: false,
+            p_layout_seed: None,
+            style,
+        }
     }
 
     /// Appends graph structure elements from a graph configuration.
@@ -116,257 +396,305 @@ impl Graph {
     /// assert!(graph.nodes() == vec!['A', 'B']);
     /// ```
     pub fn from_str(&mut self, graph_config: &str) {
+        or_panic(self.try_from_str(graph_config))
+    }
+
+    /// Appends graph structure elements from a graph configuration, returning an error instead of panicking.
+    /// Elements preceding the invalid command are kept in the graph.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::{Graph, GraphError};
+    ///
+    /// let mut graph = Graph::new();
+    /// let result = graph.try_from_str("A, B\nA - B 0, A ? B");
+    ///
+    /// assert!(matches!(result, Err(GraphError::ParseError { line: 2, column: 10, .. })));
+    /// ```
+    pub fn try_from_str(&mut self, graph_config: &str) -> Result<(), GraphError<N>> {
         let anim_state_init = self.anim_state;
         if anim_state_init == AnimState::Resumed {
             self.pause();
         }
 
-        for line in graph_config.lines() {
+        let result = self.parse_config(graph_config);
+
+        if anim_state_init == AnimState::Resumed {
+            self.resume();
+        }
+        result
+    }
+
+    fn parse_config(&mut self, graph_config: &str) -> Result<(), GraphError<N>> {
+        for (l, line) in graph_config.lines().enumerate() {
+            let mut offset = 0;
             for cmd in line.split(',') {
-                let fields: Vec<&str> = cmd.trim().split_whitespace().collect();
+                let column = offset + cmd.chars().take_while(|c| c.is_whitespace()).count() + 1;
+                offset += cmd.chars().count() + 1;
+
+                let fields: Vec<&str> = cmd.split_whitespace().collect();
                 if fields.is_empty() {
                     continue;
                 }
-                match fields.as_slice() {
-                    [node_1, "-", node_2, value] => {
-                        self.link_add_from(node_1, node_2, true, value);
-                    }
-                    [node_1, "-", node_2] => {
-                        self.link_add_from(node_1, node_2, true, "_");
+                let result = match fields.as_slice() {
+                    ["*", "multigraph"] => {
+                        self.multigraph(true);
+                        Ok(())
                     }
+                    [node_1, "-", node_2, value] => self.link_add_from(node_1, node_2, true, value),
+                    [node_1, "-", node_2] => self.link_add_from(node_1, node_2, true, "_"),
                     [node_from, ">", node_to, value] => {
-                        self.link_add_from(node_from, node_to, false, value);
-                    }
-                    [node_from, ">", node_to] => {
-                        self.link_add_from(node_from, node_to, false, "_");
+                        self.link_add_from(node_from, node_to, false, value)
                     }
+                    [node_from, ">", node_to] => self.link_add_from(node_from, node_to, false, "_"),
                     [node_to, "<", node_from, value] => {
-                        self.link_add_from(node_from, node_to, false, value);
+                        self.link_add_from(node_from, node_to, false, value)
                     }
-                    [node_to, "<", node_from] => {
-                        self.link_add_from(node_from, node_to, false, "_");
-                    }
-                    [node, cx, cy] => {
-                        self.node_add_from(node, cx, cy);
-                    }
-                    [node] => {
-                        self.node_add_from(node, "_", "_");
-                    }
-                    _ => panic!(
-                        "{}",
-                        GraphError {
-                            action: String::from("parse config"),
-                            message: format!("line '{} / {}' is invalid", line, cmd),
-                        }
-                    ),
+                    [node_to, "<", node_from] => self.link_add_from(node_from, node_to, false, "_"),
+                    [node, cx, cy] => self.node_add_from(node, cx, cy),
+                    [node] => self.node_add_from(node, "_", "_"),
+                    _ => Err(format!("command '{}' is invalid", cmd.trim())),
+                };
+                if let Err(message) = result {
+                    return Err(GraphError::ParseError {
+                        line: l + 1,
+                        column,
+                        message,
+                    });
                 }
             }
         }
 
-        if anim_state_init == AnimState::Resumed {
-            self.resume();
-        }
+        Ok(())
     }
 
-    fn node_add_from(&mut self, name: &str, cx: &str, cy: &str) {
-        if name.chars().count() != 1 {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("add a node"),
-                    message: format!("'{}' is an invalid node name (char type requiered)", name),
-                }
-            )
-        }
-        let name = name.chars().next().unwrap();
-
-        if cx != "_" {
-            let cx = match cx.parse::<i16>() {
-                Ok(v) => v,
-                Err(_) => panic!(
-                    "{}",
-                    GraphError {
-                        action: String::from("add a node"),
-                        message: format!("'{}' is an invalid x coordinate for node {}", cx, name),
-                    }
-                ),
-            };
-
-            let cy = match cy.parse::<i16>() {
-                Ok(v) => v,
-                Err(_) => panic!(
-                    "{}",
-                    GraphError {
-                        action: String::from("add a node"),
-                        message: format!("'{}' is an invalid y coordinate for node {}", cy, name),
-                    }
-                ),
-            };
-
-            self.add_node(name, Some((cx, cy)));
+    fn node_add_from(&mut self, name: &str, cx: &str, cy: &str) -> Result<(), String> {
+        let name = Self::node_name_from(name)?;
+
+        let xy = if cx != "_" {
+            let cx = cx
+                .parse::<i16>()
+                .map_err(|_| format!("'{}' is an invalid x coordinate for node {}", cx, name))?;
+            let cy = cy
+                .parse::<i16>()
+                .map_err(|_| format!("'{}' is an invalid y coordinate for node {}", cy, name))?;
+            Some((cx, cy))
         } else {
-            self.add_node(name, None);
-        }
-    }
+            None
+        };
 
-    fn link_add_from(&mut self, node_from: &str, node_to: &str, bidirect: bool, value: &str) {
-        if node_from.chars().count() != 1 {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("add a link"),
-                    message: format!(
-                        "'{}' is an invalid node name (char type requiered)",
-                        node_from
-                    ),
-                }
-            )
-        }
-        if node_to.chars().count() != 1 {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("add a link"),
-                    message: format!(
-                        "'{}' is an invalid node name (char type requiered)",
-                        node_to
-                    ),
-                }
-            )
-        }
+        self.try_add_node(name, xy)
+            .map_err(|e| e.action_and_cause().1)
+    }
 
-        let node_from = node_from.chars().next().unwrap();
-        let node_to = node_to.chars().next().unwrap();
+    fn link_add_from(
+        &mut self,
+        node_from: &str,
+        node_to: &str,
+        bidirect: bool,
+        value: &str,
+    ) -> Result<(), String> {
+        let node_from = Self::node_name_from(node_from)?;
+        let node_to = Self::node_name_from(node_to)?;
         let value = if value == "_" {
-            0
+            W::default()
         } else {
-            match value.parse::<i8>() {
-                Ok(v) => v,
-                Err(_) => panic!(
-                    "{}",
-                    GraphError {
-                        action: String::from("add a link"),
-                        message: format!(
-                            "'{}' is an invalid value for link {}{}",
-                            value, node_from, node_to
-                        ),
-                    }
-                ),
-            }
+            value.parse::<W>().map_err(|_| {
+                format!(
+                    "'{}' is an invalid value for link {} > {}",
+                    value, node_from, node_to
+                )
+            })?
         };
 
-        self.add_link(node_from, node_to, bidirect, value);
+        self.try_add_link(node_from, node_to, bidirect, value)
+            .map_err(|e| e.action_and_cause().1)
+    }
+
+    fn node_name_from(name: &str) -> Result<N, String> {
+        name.parse::<N>()
+            .map_err(|_| format!("'{}' is an invalid node name", name))
     }
 
     /// Adds a node to the graph structure, with an optional (x,y) freezed position (freezed coords of the element representation in SVG context). So the (x,y) position won't change when automatic layout algo runs.
-    pub fn add_node(&mut self, name: char, xy: Option<(i16, i16)>) {
-        self.node_check_not_exist(name);
+    pub fn add_node(&mut self, name: N, xy: Option<(i16, i16)>) {
+        or_panic(self.try_add_node(name, xy))
+    }
+
+    /// Adds a node to the graph structure, returning an error if the node already exists.
+    pub fn try_add_node(&mut self, name: N, xy: Option<(i16, i16)>) -> Result<(), GraphError<N>> {
+        self.node_check_not_exist(&name)?;
 
         self.bulk_changes(
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            Some(vec![(name, xy)]),
-            None,
-            None,
-            None,
+            vec![Change::AddNodes(vec![(name, xy)])],
             self.p_duration_add,
         );
 
         self.need_layout();
+        Ok(())
     }
 
     /// Deletes a node from the graph structure.
-    pub fn delete_node(&mut self, node: char) {
-        self.node_check_exists(node);
+    pub fn delete_node(&mut self, node: N) {
+        or_panic(self.try_delete_node(node))
+    }
 
-        let links = self.node_links(node);
+    /// Deletes a node from the graph structure, returning an error if the node does not exist.
+    pub fn try_delete_node(&mut self, node: N) -> Result<(), GraphError<N>> {
+        self.node_check_exists(&node)?;
 
-        let links_to_delete: Vec<(char, char)> = links
-            .iter()
-            .map(|(node_from, node_to, _, _)| (*node_from, *node_to))
+        let links_to_delete: Vec<(N, N)> = self
+            .node_links(&node)
+            .into_iter()
+            .map(|(node_from, node_to, _, _)| (node_from, node_to))
             .collect();
 
         self.bulk_changes(
-            None,
-            None,
-            None,
-            None,
-            Some(links_to_delete),
-            Some(vec![node]),
-            None,
-            None,
-            None,
-            None,
+            vec![
+                Change::DeleteLinks(links_to_delete),
+                Change::DeleteNodes(vec![node]),
+            ],
             self.p_duration_delete,
         );
 
         self.need_layout();
+        Ok(())
+    }
+
+    /// Allows (or forbids, the default) parallel links, antiparallel directed links and loops.
+    /// Links between the same nodes are drawn as curves on both sides, and loops as circles above the node.
+    /// Functions taking a `(node_from, node_to)` pair use the oldest matching link, whose value is the one of the adjacency list.
+    /// The graph configuration of a multigraph starts with a `* multigraph` command, which enables it when the configuration is read.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.multigraph(true);
+    /// graph.from_str("A, B, A > B 1, B > A 2, A - B 3, A > A 4");
+    ///
+    /// assert_eq!(graph.adjacency_list()[&'A'][&'B'], 1);
+    /// assert_eq!(graph.adjacency_list()[&'B'][&'A'], 2);
+    /// assert_eq!(graph.adjacency_list()[&'A'][&'A'], 4);
+    ///
+    /// graph.delete_link('A', 'B');
+    /// assert_eq!(graph.adjacency_list()[&'A'][&'B'], 3);
+    /// assert_eq!(graph.to_string(), "* multigraph\nA\nB\nA > A 4\nA - B 3\nB > A 2\n");
+    /// ```
+    pub fn multigraph(&mut self, enabled: bool) {
+        self.p_multigraph = enabled;
     }
 
     /// Adds a link between two nodes. The link can be defined as bidirectional or not.
-    pub fn add_link(&mut self, node_from: char, node_to: char, bidirectional: bool, value: i8) {
-        self.link_check_not_exist(node_from, node_to);
+    pub fn add_link(&mut self, node_from: N, node_to: N, bidirectional: bool, value: W) {
+        or_panic(self.try_add_link(node_from, node_to, bidirectional, value))
+    }
+
+    /// Adds a link between two nodes, returning an error if a node is missing or if the link already exists.
+    pub fn try_add_link(
+        &mut self,
+        node_from: N,
+        node_to: N,
+        bidirectional: bool,
+        value: W,
+    ) -> Result<(), GraphError<N>> {
+        self.link_check_not_exist(&node_from, &node_to)?;
 
         self.bulk_changes(
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            Some(vec![(node_from, node_to, bidirectional, value)]),
-            None,
-            None,
+            vec![Change::AddLinks(vec![(
+                node_from,
+                node_to,
+                bidirectional,
+                value,
+            )])],
             self.p_duration_add,
         );
 
         self.need_layout();
+        Ok(())
     }
 
     /// Deletes a link.
-    pub fn delete_link(&mut self, node_from: char, node_to: char) {
-        self.link_check_exists(node_from, node_to);
+    pub fn delete_link(&mut self, node_from: N, node_to: N) {
+        or_panic(self.try_delete_link(node_from, node_to))
+    }
+
+    /// Deletes a link, returning an error if the link does not exist.
+    pub fn try_delete_link(&mut self, node_from: N, node_to: N) -> Result<(), GraphError<N>> {
+        self.link_check_exists(&node_from, &node_to)?;
 
         self.bulk_changes(
-            None,
-            None,
-            None,
-            None,
-            Some(vec![(node_from, node_to)]),
-            None,
-            None,
-            None,
-            None,
-            None,
+            vec![Change::DeleteLinks(vec![(node_from, node_to)])],
             self.p_duration_delete,
         );
 
         self.need_layout();
+        Ok(())
     }
 
     /// Returns the links names list.
-    pub fn links(&self) -> Vec<(char, char)> {
+    pub fn links(&self) -> Vec<(N, N)> {
         let mut links = Vec::new();
         let directed = self.directed();
-        for (node_from, neihgbors) in &self.adjacency {
-            for (node_to, _) in neihgbors {
-                let link = (*node_from, *node_to);
-                if directed || !links.contains(&(*node_to, *node_from)) {
-                    links.push(link);
-                }
+        for (node_from, node_to, bidirect, _) in self.sorted_links() {
+            if directed && bidirect && node_from != node_to {
+                links.push((node_to.clone(), node_from.clone()));
             }
+            links.push((node_from, node_to));
         }
+        links.sort();
 
         links
     }
 
+    /// Returns the links as (from, to, bidirectional, value), parallel links included, in creation order.
+    pub(crate) fn link_entries(&self) -> Vec<(N, N, bool, W)> {
+        self.link_list.clone()
+    }
+
+    /// Returns a new graph with the same structure, style and settings (layout, speed, labels...), but without the animation history.
+    /// Algorithms animate such a copy of their input graph.
+    pub(crate) fn copy(&self) -> Graph<N, W> {
+        let mut graph = Graph::with_style(self.style.clone());
+        graph.layout = Rc::clone(&self.layout);
+        graph.renderer.settings_from(&self.renderer);
+        graph.p_speed_factor = self.p_speed_factor;
+        graph.p_incremental_layout = self.p_incremental_layout;
+        graph.p_layout_seed = self.p_layout_seed;
+        graph.from_str(&self.to_string());
+        graph
+    }
+
+    /// Returns the adjacency list where the values of parallel links are merged (e.g. their minimum), instead of being the value of the oldest link.
+    pub(crate) fn merged_adjacency_list(
+        &self,
+        merge: impl Fn(W, W) -> W,
+    ) -> BTreeMap<N, BTreeMap<N, W>> {
+        let mut adjacency: BTreeMap<N, BTreeMap<N, W>> = self
+            .adjacency
+            .keys()
+            .map(|node| (node.clone(), BTreeMap::new()))
+            .collect();
+        for (node_from, node_to, bidirect, value) in &self.link_list {
+            let mut directions = vec![(node_from, node_to)];
+            if *bidirect && node_from != node_to {
+                directions.push((node_to, node_from));
+            }
+            for (node_1, node_2) in directions {
+                adjacency
+                    .get_mut(node_1)
+                    .unwrap()
+                    .entry(node_2.clone())
+                    .and_modify(|merged| *merged = merge(*merged, *value))
+                    .or_insert(*value);
+            }
+        }
+        adjacency
+    }
+
     /// Returns the nodes names list.
-    pub fn nodes(&self) -> Vec<char> {
+    pub fn nodes(&self) -> Vec<N> {
         self.adjacency.keys().cloned().collect()
     }
 
@@ -388,26 +716,13 @@ impl Graph {
     ///     }
     /// }
     /// ```
-    pub fn adjacency_list(&self) -> BTreeMap<char, BTreeMap<char, i8>> {
+    pub fn adjacency_list(&self) -> BTreeMap<N, BTreeMap<N, W>> {
         self.adjacency.clone()
     }
 
     /// Returns True if the graph is directed
     pub fn directed(&self) -> bool {
-        for (node_from, neighbors) in &self.adjacency {
-            for (node_to, _) in neighbors {
-                if self
-                    .adjacency
-                    .get(node_to)
-                    .unwrap()
-                    .get(node_from)
-                    .is_none()
-                {
-                    return true;
-                }
-            }
-        }
-        false
+        self.link_list.iter().any(|(_, _, bidirect, _)| !bidirect)
     }
 
     /// Returns the adjacency matrix.
@@ -432,16 +747,16 @@ impl Graph {
     ///     }
     /// }
     /// ```
-    pub fn adjacency_matrix(&self) -> BTreeMap<char, BTreeMap<char, Option<i8>>> {
+    pub fn adjacency_matrix(&self) -> BTreeMap<N, BTreeMap<N, Option<W>>> {
         let mut matrix = BTreeMap::new();
 
-        for node in self.nodes().into_iter() {
-            for other in self.nodes().into_iter() {
+        for (node, neighbors) in &self.adjacency {
+            for other in self.adjacency.keys() {
                 matrix
-                    .entry(node)
+                    .entry(node.clone())
                     .or_insert(BTreeMap::new())
-                    .entry(other)
-                    .or_insert(self.adjacency.get(&node).unwrap().get(&other).cloned());
+                    .entry(other.clone())
+                    .or_insert(neighbors.get(other).cloned());
             }
         }
 
@@ -464,293 +779,401 @@ impl Graph {
     ///     println!("Can go from 'A' to {}.", neighbor);
     /// }
     /// ```
-    pub fn neighbors(&self, node: char) -> Vec<char> {
-        self.node_check_exists(node);
+    pub fn neighbors(&self, node: N) -> Vec<N> {
+        or_panic(self.node_check_exists(&node));
 
-        let neighbors = self
-            .adjacency
-            .get(&node)
-            .unwrap()
-            .iter()
-            .map(|(k, _)| (*k))
-            .collect();
-
-        neighbors
+        self.adjacency[&node].keys().cloned().collect()
     }
 
     /// Swap two nodes in the graph structure and its graphic representation.
-    pub fn swap_nodes(&mut self, node_1: char, node_2: char) {
-        self.node_check_exists(node_1);
-        self.node_check_exists(node_2);
+    pub fn swap_nodes(&mut self, node_1: N, node_2: N) {
+        or_panic(self.node_check_exists(&node_1));
+        or_panic(self.node_check_exists(&node_2));
 
-        let (x1, y1, freezed_1) = self.node_position(node_1);
-        let (x2, y2, freezed_2) = self.node_position(node_2);
+        let (x1, y1, freezed_1) = self.node_position(node_1.clone());
+        let (x2, y2, freezed_2) = self.node_position(node_2.clone());
 
-        let mut links = self.node_links(node_1);
-        let links_2 = self.node_links(node_2);
-        links.extend(&links_2);
-
-        let mut links_to_delete = Vec::new();
-        for (node_from, node_to, bidirect, _) in &links {
-            if *bidirect && links_to_delete.contains(&(*node_to, *node_from))
-                || links_to_delete.contains(&(*node_from, *node_to))
-            {
-                continue;
-            }
-            links_to_delete.push((*node_from, *node_to));
-        }
+        let links: Vec<(N, N, bool, W)> = self
+            .link_list
+            .iter()
+            .filter(|(node_from, node_to, _, _)| {
+                [node_from, node_to]
+                    .iter()
+                    .any(|node| **node == node_1 || **node == node_2)
+            })
+            .cloned()
+            .collect();
+        let links_to_delete: Vec<(N, N)> = links
+            .iter()
+            .map(|(node_from, node_to, _, _)| (node_from.clone(), node_to.clone()))
+            .collect();
 
-        let mut links_to_add = Vec::new();
-        for (node_from, node_to, bidirect, value) in links {
-            let node_from = match node_from {
-                n if n == node_1 => node_2,
-                n if n == node_2 => node_1,
-                n => n,
-            };
-            let node_to = match node_to {
-                n if n == node_1 => node_2,
-                n if n == node_2 => node_1,
-                n => n,
-            };
-            if bidirect && links_to_add.contains(&(node_to, node_from, bidirect, value))
-                || links_to_add.contains(&(node_from, node_to, bidirect, value))
-            {
-                continue;
-            }
-            links_to_add.push((node_from, node_to, bidirect, value));
-        }
+        let swap = |n: N| match n {
+            n if n == node_1 => node_2.clone(),
+            n if n == node_2 => node_1.clone(),
+            n => n,
+        };
+        let links_to_add: Vec<(N, N, bool, W)> = links
+            .into_iter()
+            .map(|(node_from, node_to, bidirect, value)| {
+                (swap(node_from), swap(node_to), bidirect, value)
+            })
+            .collect();
 
         self.bulk_changes(
-            None,
-            None,
-            None,
-            None,
-            Some(links_to_delete),
-            None,
-            None,
-            None,
-            None,
-            None,
+            vec![Change::DeleteLinks(links_to_delete)],
             self.p_duration_delete,
         );
 
         self.bulk_changes(
-            None,
-            None,
-            Some(vec![
+            vec![Change::MoveNodes(vec![
                 (node_1, (x2, y2), freezed_2),
                 (node_2, (x1, y1), freezed_1),
-            ]),
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
+            ])],
             self.p_duration_move,
         );
 
-        self.bulk_changes(
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            Some(links_to_add),
-            None,
-            None,
-            self.p_duration_add,
-        );
+        self.bulk_changes(vec![Change::AddLinks(links_to_add)], self.p_duration_add);
 
         self.need_layout();
     }
 
-    /// Returns a formatted string describing the graph structure.
-    fn graph_config(&self) -> String {
-        let mut config = String::new();
-
-        for (node_from, _) in &self.adjacency {
-            let (x, y, freezed) = self.node_position(*node_from);
-            let line = match freezed {
-                true => format!("{} {} {}\n", node_from, x, y),
-                false => format!("{}\n", node_from),
-            };
-            config.push_str(&line);
+    /// Returns the graph structure in the given text format.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::{Graph, GraphFormat};
+    ///
+    /// let graph: Graph = "A 0 0, B, A > B 3".parse().unwrap();
+    /// let dot = graph.to_string_as(GraphFormat::Dot);
+    ///
+    /// assert_eq!(dot, "digraph {\n    A [pos=\"0,0!\"];\n    B;\n    A -> B [label=3];\n}\n");
+    /// ```
+    pub fn to_string_as(&self, format: GraphFormat) -> String {
+        match format {
+            GraphFormat::Config => self.graph_config(),
+            GraphFormat::Dot => io::write_dot(&self.document()),
+            GraphFormat::GraphMl => io::write_graphml(&self.document()),
+            GraphFormat::Json => io::write_json(&self.document()),
+            GraphFormat::Csv => io::write_csv(&self.document()),
         }
+    }
 
-        for (node_from, neighbors) in &self.adjacency {
-            for (node_to, value) in neighbors {
-                let bidirect = match self.adjacency[&node_to].get(&node_from) {
-                    Some(_) => "-",
-                    None => ">",
-                };
-                if bidirect == "-" && node_from > node_to {
-                    continue;
-                }
-                let line = format!("{} {} {} {}\n", node_from, bidirect, node_to, value);
-                config.push_str(&line);
-            }
+    /// Appends graph structure elements read in the given text format.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::{Graph, GraphFormat};
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str_as(
+    ///     r##"{"nodes": [{"id": "A", "x": 0, "y": 0}, {"id": "B", "fill": "#ff0000"}],
+    ///         "links": [{"from": "A", "to": "B", "value": 3, "directed": true}]}"##,
+    ///     GraphFormat::Json,
+    /// );
+    ///
+    /// assert_eq!(graph.to_string(), "A 0 0\nB\nA > B 3\n");
+    /// ```
+    pub fn from_str_as(&mut self, text: &str, format: GraphFormat) {
+        or_panic(self.try_from_str_as(text, format))
+    }
+
+    /// Same as `from_str_as()`, returning an error instead of panicking.
+    /// Elements preceding the invalid one are kept in the graph.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::{Graph, GraphError, GraphFormat};
+    ///
+    /// let mut graph = Graph::new();
+    /// let result = graph.try_from_str_as("graph {\n  A -- B [color=\"#12\"]\n}", GraphFormat::Dot);
+    ///
+    /// assert!(matches!(result, Err(GraphError::ParseError { line: 2, column: 11, .. })));
+    /// ```
+    pub fn try_from_str_as(
+        &mut self,
+        text: &str,
+        format: GraphFormat,
+    ) -> Result<(), GraphError<N>> {
+        let document = match format {
+            GraphFormat::Config => return self.try_from_str(text),
+            GraphFormat::Dot => io::read_dot(text),
+            GraphFormat::GraphMl => io::read_graphml(text),
+            GraphFormat::Json => io::read_json(text),
+            GraphFormat::Csv => io::read_csv(text),
         }
+        .map_err(|failure| GraphError::ParseError {
+            line: failure.line,
+            column: failure.column,
+            message: failure.message,
+        })?;
 
-        config
-    }
+        let anim_state_init = self.anim_state;
+        if anim_state_init == AnimState::Resumed {
+            self.pause();
+        }
 
-    fn node_links(&self, node: char) -> Vec<(char, char, bool, i8)> {
-        let mut links = Vec::new();
+        let result = self.load(&document);
 
-        for (neighbor, value) in self.adjacency.get(&node).unwrap().iter() {
-            let bidirect = self.adjacency.get(&neighbor).unwrap().get(&node).is_some();
-            links.push((node, *neighbor, bidirect, *value));
+        if anim_state_init == AnimState::Resumed {
+            self.resume();
         }
-        for (node_other, neighbors_other) in self.adjacency.iter() {
-            if *node_other == node {
-                continue;
-            };
-            if let Some(value) = neighbors_other.get(&node) {
-                if self
-                    .adjacency
-                    .get(&node)
-                    .unwrap()
-                    .get(&node_other)
-                    .is_none()
-                {
-                    links.push((*node_other, node, false, *value));
-                }
-            }
+        result
+    }
+
+    /// Returns the graph structure with its colors differing from the style, to be written in a text format.
+    fn document(&self) -> io::Document {
+        let color = |c: renderer::color::Color| (c.r(), c.g(), c.b());
+        let mut document = io::Document::default();
+        for name in self.adjacency.keys() {
+            let (x, y, freezed) = self.node_position(name.clone());
+            let (fill, stroke) = self.renderer.node_colors(name);
+            document.nodes.push(io::NodeEntry {
+                name: name.to_string(),
+                position: Some((x, y)),
+                freezed,
+                fill: Some(color(fill)).filter(|c| *c != self.style.node_fill),
+                stroke: Some(color(stroke)).filter(|c| *c != self.style.node_stroke),
+                at: (0, 0),
+            });
         }
 
-        links
+        // parallel links are matched with their graphic representations in creation order
+        let mut parallels: BTreeMap<(N, N), usize> = BTreeMap::new();
+        for (node_from, node_to, bidirect, value) in self.sorted_links() {
+            let rank = parallels
+                .entry((node_from.clone(), node_to.clone()))
+                .or_default();
+            let stroke = self
+                .renderer
+                .link_colors(&node_from, &node_to)
+                .get(*rank)
+                .map(|c| color(*c));
+            *rank += 1;
+            document.links.push(io::LinkEntry {
+                from: node_from.to_string(),
+                to: node_to.to_string(),
+                value: Some(value.to_string()).filter(|_| value != W::default()),
+                bidirect,
+                stroke: stroke.filter(|c| *c != self.style.link_stroke),
+                at: (0, 0),
+            });
+        }
+        document
     }
 
-    fn node_check_exists(&self, node: char) {
-        if self.adjacency.get(&node).is_none() {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("use a node"),
-                    message: format!("node '{}' does not exist", node),
-                }
-            );
+    fn load(&mut self, document: &io::Document) -> Result<(), GraphError<N>> {
+        let error = |(line, column): (usize, usize), message| GraphError::ParseError {
+            line,
+            column,
+            message,
         };
+        for node in &document.nodes {
+            self.node_load(node)
+                .map_err(|message| error(node.at, message))?;
+        }
+        for link in &document.links {
+            self.link_load(link)
+                .map_err(|message| error(link.at, message))?;
+        }
+        Ok(())
     }
 
-    fn node_check_not_exist(&self, node: char) {
-        if self.adjacency.get(&node).is_some() {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("add a node"),
-                    message: format!("node {} already exists", node),
-                }
-            );
+    fn node_load(&mut self, node: &io::NodeEntry) -> Result<(), String> {
+        let name = Self::node_name_from(&node.name)?;
+        let xy = match node.position.filter(|_| node.freezed) {
+            Some((x, y)) => {
+                let x = i16::try_from(x)
+                    .map_err(|_| format!("'{}' is an invalid x coordinate for node {}", x, name))?;
+                let y = i16::try_from(y)
+                    .map_err(|_| format!("'{}' is an invalid y coordinate for node {}", y, name))?;
+                Some((x, y))
+            }
+            None => None,
         };
+        self.try_add_node(name.clone(), xy)
+            .map_err(|e| e.action_and_cause().1)?;
+
+        if let Some(fill) = node.fill {
+            self.fill_node(name.clone(), fill);
+        }
+        if let Some(stroke) = node.stroke {
+            self.color_node(name, stroke);
+        }
+        Ok(())
     }
 
-    fn link_check_exists(&self, node_from: char, node_to: char) {
-        self.node_check_exists(node_from);
-        self.node_check_exists(node_to);
-        if self
-            .adjacency
-            .get(&node_from)
-            .unwrap()
-            .get(&node_to)
-            .is_none()
-        {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("use a link"),
-                    message: format!("link '{}{}' does not exist", node_from, node_to),
-                }
+    fn link_load(&mut self, link: &io::LinkEntry) -> Result<(), String> {
+        let value = link.value.as_deref().unwrap_or("_");
+        self.link_add_from(&link.from, &link.to, link.bidirect, value)?;
+
+        // the link just added, which is not the oldest one between its nodes in a multigraph
+        if let Some(stroke) = link.stroke {
+            let node_from = Self::node_name_from(&link.from)?;
+            let node_to = Self::node_name_from(&link.to)?;
+            self.bulk_changes(
+                vec![Change::ColorLastLinks(vec![(node_from, node_to)], stroke)],
+                self.p_duration_color,
             );
-        };
+        }
+        Ok(())
     }
 
-    fn link_check_not_exist(&self, node_from: char, node_to: char) {
-        self.node_check_exists(node_from);
-        self.node_check_exists(node_to);
+    /// Returns a formatted string describing the graph structure.
+    fn graph_config(&self) -> String {
+        let mut config = String::new();
+        if self.p_multigraph {
+            config.push_str("* multigraph\n");
+        }
 
-        if node_from == node_to {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("add a link"),
-                    message: format!(
-                        "link {}{} is invalid (loop is not allowed)",
-                        node_from, node_to
-                    ),
-                }
-            );
+        for node_from in self.adjacency.keys() {
+            let (x, y, freezed) = self.node_position(node_from.clone());
+            let line = match freezed {
+                true => format!("{} {} {}\n", node_from, x, y),
+                false => format!("{}\n", node_from),
+            };
+            config.push_str(&line);
         }
 
-        if self
-            .adjacency
-            .get(&node_from)
-            .unwrap()
-            .get(&node_to)
-            .is_some()
-        {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("add a link"),
-                    message: format!("link {}{} already exists", node_from, node_to),
-                }
-            );
-        };
+        for (node_from, node_to, bidirect, value) in self.sorted_links() {
+            let bidirect = match bidirect {
+                true => "-",
+                false => ">",
+            };
+            let line = format!("{} {} {} {}\n", node_from, bidirect, node_to, value);
+            config.push_str(&line);
+        }
 
-        if self
-            .adjacency
-            .get(&node_to)
-            .unwrap()
-            .get(&node_from)
-            .is_some()
-        {
-            panic!(
-                "{}",
-                GraphError {
-                    action: String::from("add a link"),
-                    message: format!("directed link {}{} already exists", node_to, node_from),
-                }
-            );
-        };
+        config
+    }
+
+    /// Links ordered by nodes, parallel links in creation order.
+    fn sorted_links(&self) -> Vec<(N, N, bool, W)> {
+        let mut links = self.link_list.clone();
+        links.sort_by(|(from_1, to_1, _, _), (from_2, to_2, _, _)| {
+            (from_1, to_1).cmp(&(from_2, to_2))
+        });
+        links
+    }
+
+    fn node_links(&self, node: &N) -> Vec<(N, N, bool, W)> {
+        self.link_list
+            .iter()
+            .filter(|(node_from, node_to, _, _)| node_from == node || node_to == node)
+            .cloned()
+            .collect()
+    }
+
+    /// Position of the oldest link from `node_from` to `node_to` (or between them if bidirectional) in the links list.
+    fn link_position(&self, node_from: &N, node_to: &N) -> Option<usize> {
+        self.link_list.iter().position(|(from, to, bidirect, _)| {
+            from == node_from && to == node_to || *bidirect && from == node_to && to == node_from
+        })
+    }
+
+    /// Updates the adjacency list entries between two nodes: the oldest link of each direction gives the value.
+    fn link_sync(&mut self, node_1: &N, node_2: &N) {
+        self.adjacency.get_mut(node_1).unwrap().remove(node_2);
+        self.adjacency.get_mut(node_2).unwrap().remove(node_1);
+        for (node_from, node_to, bidirect, value) in &self.link_list {
+            if !(node_from == node_1 && node_to == node_2
+                || node_from == node_2 && node_to == node_1)
+            {
+                continue;
+            }
+            self.adjacency
+                .get_mut(node_from)
+                .unwrap()
+                .entry(node_to.clone())
+                .or_insert(*value);
+            if *bidirect {
+                self.adjacency
+                    .get_mut(node_to)
+                    .unwrap()
+                    .entry(node_from.clone())
+                    .or_insert(*value);
+            }
+        }
+    }
+
+    fn node_check_exists(&self, node: &N) -> Result<(), GraphError<N>> {
+        match self.adjacency.contains_key(node) {
+            true => Ok(()),
+            false => Err(GraphError::NodeMissing(node.clone())),
+        }
+    }
+
+    fn node_check_not_exist(&self, node: &N) -> Result<(), GraphError<N>> {
+        match self.adjacency.contains_key(node) {
+            true => Err(GraphError::NodeExists(node.clone())),
+            false => Ok(()),
+        }
+    }
+
+    fn link_check_exists(&self, node_from: &N, node_to: &N) -> Result<(), GraphError<N>> {
+        self.node_check_exists(node_from)?;
+        self.node_check_exists(node_to)?;
+        match self.adjacency[node_from].contains_key(node_to) {
+            true => Ok(()),
+            false => Err(GraphError::LinkMissing(node_from.clone(), node_to.clone())),
+        }
+    }
+
+    fn link_check_not_exist(&self, node_from: &N, node_to: &N) -> Result<(), GraphError<N>> {
+        self.node_check_exists(node_from)?;
+        self.node_check_exists(node_to)?;
+
+        if self.p_multigraph {
+            return Ok(());
+        }
+        if node_from == node_to {
+            return Err(GraphError::LoopLink(node_from.clone()));
+        }
+        if self.adjacency[node_from].contains_key(node_to) {
+            return Err(GraphError::LinkExists(node_from.clone(), node_to.clone()));
+        }
+        if self.adjacency[node_to].contains_key(node_from) {
+            return Err(GraphError::LinkExists(node_to.clone(), node_from.clone()));
+        }
+        Ok(())
     }
 
     /// Reactivates the `auto animation` option.
     /// When this option is activated, each graph structure change causes graphic animation (the animations are rendered one after the other). By default, the option is activated when a graph is created.
     /// When this option is deactivated, all pending animations occur during the same period (i.e. not one after the other) when you manually call the `anim_step()` or `anim_resume()`functions.
     pub fn resume(&mut self) {
+        or_panic(self.try_resume())
+    }
+
+    /// Reactivates the `auto animation` option, returning an error if the animation has not been paused previously.
+    pub fn try_resume(&mut self) -> Result<(), GraphError<N>> {
         match self.anim_state {
             AnimState::Paused => {
-                self.step_speed(self.duration_on_resume, self.p_speed_factor);
+                self.step_speed(self.duration_on_resume, self.p_speed_factor)?;
                 self.anim_state = AnimState::Resumed;
+                Ok(())
             }
-            _ => panic!(
-                "{}",
-                GraphError {
-                    action: String::from("resume animation"),
-                    message: "animation has not been paused previoulsy".to_string(),
-                }
-            ),
+            _ => Err(GraphError::AnimationStateError(
+                "animation has not been paused previoulsy".to_string(),
+            )),
         }
     }
 
     /// Deactivates the `auto animation` option.
     pub fn pause(&mut self) {
+        or_panic(self.try_pause())
+    }
+
+    /// Deactivates the `auto animation` option, returning an error if the animation is already paused.
+    pub fn try_pause(&mut self) -> Result<(), GraphError<N>> {
         match self.anim_state {
             AnimState::Resumed => {
                 self.anim_state = AnimState::Paused;
+                Ok(())
             }
-            _ => panic!(
-                "{}",
-                GraphError {
-                    action: String::from("pause animation"),
-                    message: "animation has not been resumed previoulsy".to_string(),
-                }
-            ),
+            _ => Err(GraphError::AnimationStateError(
+                "animation has not been resumed previoulsy".to_string(),
+            )),
         }
     }
 
@@ -758,19 +1181,19 @@ impl Graph {
     /// The `anim_pause()` function must have been called previously.
     /// After calling `anim_step()` function,  `auto animation` option still is deactivated.
     pub fn step(&mut self, duration_ms: u32) {
-        self.step_speed(duration_ms, 1.);
+        or_panic(self.try_step(duration_ms))
     }
 
-    fn step_speed(&mut self, duration_ms: u32, speed_factor: f64) {
-        match self.anim_state {
-            AnimState::Paused => {}
-            _ => panic!(
-                "{}",
-                GraphError {
-                    action: String::from("step animation"),
-                    message: "animation has not been paused previously".to_string(),
-                }
-            ),
+    /// Same as `step()`, returning an error if the animation has not been paused previously.
+    pub fn try_step(&mut self, duration_ms: u32) -> Result<(), GraphError<N>> {
+        self.step_speed(duration_ms, 1.)
+    }
+
+    fn step_speed(&mut self, duration_ms: u32, speed_factor: f64) -> Result<(), GraphError<N>> {
+        if self.anim_state != AnimState::Paused {
+            return Err(GraphError::AnimationStateError(
+                "animation has not been paused previously".to_string(),
+            ));
         }
         if self.layout_on_resume {
             self.layout();
@@ -778,6 +1201,25 @@ impl Graph {
         }
         self.animate(duration_ms, speed_factor);
         self.duration_on_resume = 1;
+        Ok(())
+    }
+
+    /// Attaches an explanatory text to the animation from now on, until the next caption (an empty text clears it).
+    /// Captions are shown above the graph, and listed in the HTML player where a click on a caption jumps to it.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A, B, A - B 3");
+    /// graph.caption("relaxing link A - B");
+    /// graph.color_link('A', 'B', (0, 192, 0));
+    ///
+    /// assert!(graph.to_svg().contains(">relaxing link A - B</text>"));
+    /// ```
+    pub fn caption(&mut self, text: &str) {
+        self.renderer.caption(text);
     }
 
     /// Delay the next animation.
@@ -792,23 +1234,53 @@ impl Graph {
 
     /// Renders the graph animation in SVG SMIL format into a HTML file.
     pub fn render(&self, html_file_name: &str) -> Result<(), std::io::Error> {
-        Self::to_html(vec![(html_file_name, vec![self])])
+        self.render_as(html_file_name, AnimationFormat::Smil)
+    }
+
+    /// Renders the graph animation in the given format into a HTML file.
+    pub fn render_as(
+        &self,
+        html_file_name: &str,
+        format: AnimationFormat,
+    ) -> Result<(), std::io::Error> {
+        Self::to_html_as(vec![(html_file_name, vec![self])], format)
     }
 
     /// Renders graphs animations in SVG SMIL format into multiple HTML files.
-    /// Each HTML page contains a menu to access other pages (if there is more than one page).
-    pub fn to_html(pages: Vec<(&str, Vec<&Graph>)>) -> Result<(), std::io::Error> {
+    /// Each HTML page contains a menu to access other pages (if there is more than one page), and a player under each animation (play/pause, step backward/forward, timeline scrubbing and speed selection).
+    pub fn to_html(pages: Vec<(&str, Vec<&Graph<N, W>>)>) -> Result<(), std::io::Error> {
+        Self::to_html_as(pages, AnimationFormat::Smil)
+    }
+
+    /// Renders graphs animations in the given format into multiple HTML files (see `to_html`).
+    pub fn to_html_as(
+        pages: Vec<(&str, Vec<&Graph<N, W>>)>,
+        format: AnimationFormat,
+    ) -> Result<(), std::io::Error> {
         let titles: Vec<String> = pages.iter().map(|(title, _)| title.to_string()).collect();
         let mut file_names: Vec<String> = Vec::new();
         for title in &titles {
             file_names.push(title.replace(" ", "_"));
         }
+        let style = match pages.iter().find_map(|(_, graphs)| graphs.first()) {
+            Some(graph) => graph.style.clone(),
+            None => Style::default(),
+        };
         for (i, (_title, graphs)) in pages.iter().enumerate() {
             let mut svgs = Vec::new();
             for graph in graphs {
-                svgs.push(graph.animation());
+                let mut svg = graph.animation(false, format);
+                if !svg.is_empty() {
+                    svg.push_str(&Html::player(
+                        &graph.step_starts(),
+                        graph.duration(),
+                        graph.renderer.paused_on_click(),
+                        &graph.renderer.captions(),
+                    ));
+                }
+                svgs.push(svg);
             }
-            let html = Html::render_flexbox(&titles, i, &file_names, svgs);
+            let html = Html::render_flexbox(&titles, i, &file_names, svgs, &style);
             write!(
                 File::create(format!("{}.{}", file_names[i], "html"))?,
                 "{}",
@@ -818,73 +1290,155 @@ impl Graph {
         Ok(())
     }
 
+    /// Renders the graph animation into a standalone SVG file (e.g. to embed it in slides or documents).
+    pub fn render_svg(&self, svg_file_name: &str) -> Result<(), std::io::Error> {
+        self.render_svg_as(svg_file_name, AnimationFormat::Smil)
+    }
+
+    /// Renders the graph animation in the given format into a standalone SVG file.
+    pub fn render_svg_as(
+        &self,
+        svg_file_name: &str,
+        format: AnimationFormat,
+    ) -> Result<(), std::io::Error> {
+        write!(
+            File::create(format!("{}.{}", svg_file_name.replace(" ", "_"), "svg"))?,
+            "{}",
+            self.to_svg_as(format)
+        )
+    }
+
+    /// Returns the graph animation as a standalone SVG document (XML namespace, width and height, and pause on click script if enabled).
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A, B, A - B");
+    /// graph.pause_on_click(false);
+    /// let svg = graph.to_svg();
+    ///
+    /// assert!(svg.contains("xmlns=\"http://www.w3.org/2000/svg\""));
+    /// assert!(!svg.contains("<script>"));
+    /// ```
+    pub fn to_svg(&self) -> String {
+        self.to_svg_as(AnimationFormat::Smil)
+    }
+
+    /// Returns the graph animation as a standalone SVG document in the given format.
+    /// With `AnimationFormat::Script`, the timeline is a JSON array played by an embedded script instead of SMIL elements.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::{AnimationFormat, Graph};
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A, B, A - B");
+    /// graph.color_node('A', (255, 0, 0));
+    /// let svg = graph.to_svg_as(AnimationFormat::Script);
+    ///
+    /// assert!(svg.contains("dynalgoPlay("));
+    /// assert!(!svg.contains("<animate"));
+    /// ```
+    pub fn to_svg_as(&self, format: AnimationFormat) -> String {
+        self.animation(true, format)
+    }
+
+    /// Renders the graph animation into PNG images, sampled at `fps` frames per second (clamped between 1 and 100) (`{file_name_prefix}_00000.png`, `{file_name_prefix}_00001.png`, ...).
+    pub fn render_png_frames(
+        &self,
+        file_name_prefix: &str,
+        fps: u32,
+    ) -> Result<(), std::io::Error> {
+        let prefix = file_name_prefix.replace(" ", "_");
+        for (i, png) in self.to_png_frames(fps).iter().enumerate() {
+            File::create(format!("{}_{:05}.{}", prefix, i, "png"))?.write_all(png)?;
+        }
+        Ok(())
+    }
+
+    /// Renders the graph animation into an animated GIF file, sampled at `fps` frames per second (clamped between 1 and 100).
+    pub fn render_gif(&self, gif_file_name: &str, fps: u32) -> Result<(), std::io::Error> {
+        File::create(format!("{}.{}", gif_file_name.replace(" ", "_"), "gif"))?
+            .write_all(&self.to_gif(fps))
+    }
+
+    /// Returns the graph animation as PNG images, sampled at `fps` frames per second (clamped between 1 and 100).
+    /// A graph without nodes gives blank 1x1 images.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A, B, A - B");
+    /// let frames = graph.to_png_frames(10);
+    ///
+    /// assert!(frames.len() as u32 == graph.duration() * 10 / 1000 + 1);
+    /// assert!(frames[0].starts_with(&[0x89, b'P', b'N', b'G']));
+    /// ```
+    pub fn to_png_frames(&self, fps: u32) -> Vec<Vec<u8>> {
+        self.renderer.png_frames(fps.clamp(1, 100))
+    }
+
+    /// Returns the graph animation as an animated GIF, sampled at `fps` frames per second (clamped between 1 and 100).
+    /// Identical consecutive frames are merged, and repeated when their delay would exceed the GIF maximum (about 655 seconds).
+    pub fn to_gif(&self, fps: u32) -> Vec<u8> {
+        self.renderer.gif(fps.clamp(1, 100))
+    }
+
+    /// Enables (default) or disables pausing and resuming the animation when clicking on it.
+    pub fn pause_on_click(&mut self, enabled: bool) {
+        self.renderer.pause_on_click(enabled);
+    }
+
     /// Returns the total duration in milliseconds of rendered animations since the graph was created.
     pub fn duration(&self) -> u32 {
         self.renderer.duration()
     }
 
+    /// Returns the start time in milliseconds of each rendered animation step, in chronological order.
+    /// The HTML player uses them to jump from step to step.
+    pub fn step_starts(&self) -> Vec<u32> {
+        self.renderer.step_starts()
+    }
+
     /// Returns the current x,y coords (and freezed tag) of the node in the SVG graphic context.
-    pub fn node_position(&self, node: char) -> (i32, i32, bool) {
-        self.node_check_exists(node);
+    pub fn node_position(&self, node: N) -> (i32, i32, bool) {
+        or_panic(self.node_check_exists(&node));
 
-        let point = self.renderer.node_center(node);
-        let freezed = self.renderer.node_center_freezed(node);
+        let point = self.renderer.node_center(&node);
+        let freezed = self.renderer.node_center_freezed(&node);
         (point.x(), point.y(), freezed)
     }
 
     /// Changes and freezes the x,y coords of the SVG node representation. The node position will not change when automatic layout algo runs.
-    pub fn move_node(&mut self, node: char, xy: (i32, i32)) {
-        self.node_check_exists(node);
+    pub fn move_node(&mut self, node: N, xy: (i32, i32)) {
+        or_panic(self.node_check_exists(&node));
 
         self.bulk_changes(
-            None,
-            None,
-            Some(vec![(node, xy, true)]),
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
+            vec![Change::MoveNodes(vec![(node, xy, true)])],
             self.p_duration_move,
         )
     }
 
     /// Changes the node fill color.
-    pub fn fill_node(&mut self, node: char, color: (u8, u8, u8)) {
-        self.node_check_exists(node);
+    pub fn fill_node(&mut self, node: N, color: (u8, u8, u8)) {
+        or_panic(self.node_check_exists(&node));
 
         self.bulk_changes(
-            None,
-            None,
-            None,
-            Some((vec![node], color)),
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
+            vec![Change::FillNodes(vec![node], color)],
             self.p_duration_color,
         )
     }
 
     /// Changes the node stroke color.
-    pub fn color_node(&mut self, node: char, color: (u8, u8, u8)) {
-        self.node_check_exists(node);
+    pub fn color_node(&mut self, node: N, color: (u8, u8, u8)) {
+        or_panic(self.node_check_exists(&node));
 
         self.bulk_changes(
-            Some((vec![node], color)),
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
+            vec![Change::ColorNodes(vec![node], color)],
             self.p_duration_color,
         )
     }
@@ -895,80 +1449,223 @@ impl Graph {
     }
 
     /// Changes the node label color.
-    pub fn color_label(&mut self, node: char, color: (u8, u8, u8)) {
-        self.node_check_exists(node);
+    pub fn color_label(&mut self, node: N, color: (u8, u8, u8)) {
+        or_panic(self.node_check_exists(&node));
 
         self.bulk_changes(
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            Some((vec![node], color)),
-            None,
+            vec![Change::ColorNodesText(vec![node], color)],
             self.p_duration_color,
         )
     }
 
     /// Unfreezes the node position (coords in SVG graphic context), so the current position will change  when automatic layout algo runs.
-    pub fn unfreeze_node(&mut self, node: char) {
-        self.node_check_exists(node);
-        self.renderer.node_freezed(node, false);
+    pub fn unfreeze_node(&mut self, node: N) {
+        or_panic(self.node_check_exists(&node));
+        self.renderer.node_freezed(&node, false);
         self.need_layout();
     }
 
+    /// Draws single links as curves (or as straight lines, the default), bent on the right side of their direction.
+    /// Parallel and antiparallel links are always curved.
+    pub fn curve_links(&mut self, enabled: bool) {
+        if self.renderer.curve_links(enabled) && !self.paused() {
+            self.animate(self.p_duration_move, self.p_speed_factor);
+        }
+    }
+
     /// Changes the link stroke color.
-    pub fn color_link(&mut self, node_from: char, node_to: char, color: (u8, u8, u8)) {
-        self.link_check_exists(node_from, node_to);
+    pub fn color_link(&mut self, node_from: N, node_to: N, color: (u8, u8, u8)) {
+        or_panic(self.link_check_exists(&node_from, &node_to));
 
         self.bulk_changes(
-            None,
-            Some((vec![(node_from, node_to)], color)),
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
+            vec![Change::ColorLinks(vec![(node_from, node_to)], color)],
             self.p_duration_color,
         )
     }
 
     /// Changes the link value color.
-    pub fn color_value(&mut self, node_from: char, node_to: char, color: (u8, u8, u8)) {
-        self.link_check_exists(node_from, node_to);
+    pub fn color_value(&mut self, node_from: N, node_to: N, color: (u8, u8, u8)) {
+        or_panic(self.link_check_exists(&node_from, &node_to));
+
+        self.bulk_changes(
+            vec![Change::ColorLinksText(vec![(node_from, node_to)], color)],
+            self.p_duration_color,
+        )
+    }
+
+    /// Shows a text badge next to the node (e.g. a distance, a rank or a level), replacing the previous one with an animation.
+    /// An empty text removes the badge.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A, B, A - B 3");
+    /// graph.set_node_badge('B', "d=3");
+    ///
+    /// assert!(graph.to_svg().contains("d=3</text>"));
+    /// ```
+    pub fn set_node_badge(&mut self, node: N, text: &str) {
+        or_panic(self.node_check_exists(&node));
 
         self.bulk_changes(
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            None,
-            Some((vec![(node_from, node_to)], color)),
+            vec![Change::BadgeNodes(vec![(node, text.to_string())])],
             self.p_duration_color,
         )
     }
 
+    /// Shows a text label along the link (e.g. a flow), replacing the previous one with an animation.
+    /// The label is displayed above the link value. An empty text removes the label.
+    pub fn set_link_label(&mut self, node_from: N, node_to: N, text: &str) {
+        or_panic(self.link_check_exists(&node_from, &node_to));
+
+        self.bulk_changes(
+            vec![Change::LabelLinks(vec![(
+                node_from,
+                node_to,
+                text.to_string(),
+            )])],
+            self.p_duration_color,
+        )
+    }
+
+    /// Changes the link value, animating the displayed value change (the link is neither deleted nor created again).
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A, B, A - B 3");
+    /// graph.set_link_value('B', 'A', 7);
+    ///
+    /// assert_eq!(graph.adjacency_list()[&'A'][&'B'], 7);
+    /// assert_eq!(graph.adjacency_list()[&'B'][&'A'], 7);
+    /// assert!(graph.to_string().contains("A - B 7"));
+    /// ```
+    pub fn set_link_value(&mut self, node_from: N, node_to: N, value: W) {
+        or_panic(self.link_check_exists(&node_from, &node_to));
+
+        self.bulk_changes(
+            vec![Change::ValueLinks(vec![(node_from, node_to, value)])],
+            self.p_duration_color,
+        )
+    }
+
+    /// Changes the node radius (e.g. to encode a node weight), animating the size change. Other nodes radius are unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A 0 0, B 100 0, A - B");
+    /// graph.resize_node('A', 26);
+    ///
+    /// assert!(graph.to_svg().contains("attributeName=\"r\" from=\"13\" to=\"26\""));
+    /// ```
+    pub fn resize_node(&mut self, node: N, radius: u8) {
+        or_panic(self.node_check_exists(&node));
+
+        self.bulk_changes(
+            vec![Change::ResizeNodes(vec![(node, radius)])],
+            self.p_duration_color,
+        )
+    }
+
+    /// Changes the link stroke width (e.g. to encode a flow), animating the width change.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A, B, A > B");
+    /// graph.set_link_width('A', 'B', 6);
+    ///
+    /// assert!(graph.to_svg().contains("attributeName=\"stroke-width\" from=\"2\" to=\"6\""));
+    /// ```
+    pub fn set_link_width(&mut self, node_from: N, node_to: N, width: u8) {
+        or_panic(self.link_check_exists(&node_from, &node_to));
+
+        self.bulk_changes(
+            vec![Change::WidenLinks(vec![(node_from, node_to, width)])],
+            self.p_duration_color,
+        )
+    }
+
+    /// Changes the algorithm placing the nodes which are not freezed (`layout::Springs` by default), and applies it.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::layout::Grid;
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.from_str("A, B, C, D, A - B, C - D");
+    /// graph.set_layout(Grid { columns: Some(2) });
+    ///
+    /// assert_eq!(graph.node_position('A'), (-33, -33, false));
+    /// assert_eq!(graph.node_position('D'), (33, 33, false));
+    /// ```
+    pub fn set_layout(&mut self, layout: impl Layout<N> + 'static) {
+        self.layout = Rc::new(layout);
+        if !self.adjacency.is_empty() {
+            self.need_layout();
+        }
+    }
+
+    /// Enables or disables the incremental layout (disabled by default).
+    /// When enabled, nodes keep their current position as initial state, and only new nodes and nodes whose links changed are moved by the layout algorithm.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let mut graph = Graph::new();
+    /// graph.incremental_layout(true);
+    /// graph.from_str("A, B, C, A - B, B - C");
+    /// let (a, b) = (graph.node_position('A'), graph.node_position('B'));
+    ///
+    /// graph.add_node('D', None);
+    /// graph.add_link('C', 'D', true, 0);
+    ///
+    /// assert_eq!(graph.node_position('A'), a);
+    /// assert_eq!(graph.node_position('B'), b);
+    /// ```
+    pub fn incremental_layout(&mut self, enabled: bool) {
+        self.p_incremental_layout = enabled;
+    }
+
+    /// Sets the seed of the initial positions given to nodes by the layout algorithm (`None` by default: nodes are initially put on a circle).
+    /// A given seed always gives the same layout.
+    ///
+    /// # Example
+    /// ```
+    /// use dynalgo::graph::layout::FruchtermanReingold;
+    /// use dynalgo::graph::Graph;
+    ///
+    /// let config = "A, B, C, D, A - B, B - C, C - D, D - A";
+    /// let mut positions = Vec::new();
+    /// for _ in 0..2 {
+    ///     let mut graph = Graph::new();
+    ///     graph.layout_seed(Some(42));
+    ///     graph.set_layout(FruchtermanReingold::default());
+    ///     graph.from_str(config);
+    ///     positions.push(graph.node_position('A'));
+    /// }
+    ///
+    /// assert_eq!(positions[0], positions[1]);
+    /// ```
+    pub fn layout_seed(&mut self, seed: Option<u64>) {
+        self.p_layout_seed = seed;
+    }
+
     /// Changes the animation speed (from 0.1 to 10.0).
     /// Default value is 1.0
     pub fn speed(&mut self, speed_factor: f64) {
-        let speed_factor = if speed_factor < 0.1 {
-            0.1
-        } else if speed_factor > 10. {
-            10.
-        } else {
-            speed_factor
-        };
-        self.p_speed_factor = speed_factor;
+        self.p_speed_factor = speed_factor.clamp(0.1, 10.);
     }
 
     fn animate(&mut self, duration_ms: u32, speed_factor: f64) {
@@ -989,158 +1686,153 @@ impl Graph {
     }
 
     fn layout(&mut self) {
-        let mut adjacency = self.adjacency_list();
-        for (node_from, neighbors) in &self.adjacency {
-            for (node_to, value) in neighbors {
-                if self
-                    .adjacency
-                    .get(node_to)
-                    .unwrap()
-                    .get(node_from)
-                    .is_none()
-                {
-                    adjacency
-                        .get_mut(node_to)
-                        .unwrap()
-                        .insert(*node_from, *value);
-                }
-            }
-        }
+        let adjacency: BTreeMap<N, BTreeSet<N>> = self
+            .adjacency
+            .iter()
+            .map(|(node, neighbors)| {
+                let successors = neighbors.keys().filter(|n| *n != node).cloned();
+                (node.clone(), successors.collect())
+            })
+            .collect();
 
-        self.renderer.layout(adjacency);
+        self.renderer.layout(
+            self.layout.as_ref(),
+            &adjacency,
+            self.p_incremental_layout,
+            self.p_layout_seed,
+        );
 
         if !self.paused() {
             self.animate(self.p_duration_move, self.p_speed_factor);
         }
     }
 
-    fn animation(&self) -> String {
-        self.renderer.animation()
+    fn animation(&self, standalone: bool, format: AnimationFormat) -> String {
+        self.renderer.animation(standalone, format)
     }
 
-    fn bulk_changes(
-        &mut self,
-        nodes_colered: Option<(Vec<char>, (u8, u8, u8))>,
-        links_colered: Option<(Vec<(char, char)>, (u8, u8, u8))>,
-        nodes_move: Option<Vec<(char, (i32, i32), bool)>>,
-        nodes_filled: Option<(Vec<char>, (u8, u8, u8))>,
-        links_deleted: Option<Vec<(char, char)>>,
-        nodes_deleted: Option<Vec<char>>,
-        nodes_added: Option<Vec<(char, Option<(i16, i16)>)>>,
-        links_added: Option<Vec<(char, char, bool, i8)>>,
-        nodes_text_colered: Option<(Vec<char>, (u8, u8, u8))>,
-        links_text_colered: Option<(Vec<(char, char)>, (u8, u8, u8))>,
-        duration_ms: u32,
-    ) {
+    fn bulk_changes(&mut self, changes: Vec<Change<N, W>>, duration_ms: u32) {
         let anim_state_init = self.anim_state;
         if anim_state_init == AnimState::Resumed {
             self.pause();
         }
 
-        match nodes_colered {
-            Some((nodes, color)) => {
-                for node in nodes {
-                    self.renderer.node_stroke_color(node, color);
+        for change in changes {
+            match change {
+                Change::ColorNodes(nodes, color) => {
+                    for node in nodes {
+                        self.renderer.node_stroke_color(&node, color);
+                    }
                 }
-            }
-            None => {}
-        }
-        match links_colered {
-            Some((links, color)) => {
-                for (node_from, node_to) in links {
-                    self.renderer.link_stroke_color(node_from, node_to, color);
+                Change::ColorLinks(links, color) => {
+                    for (node_from, node_to) in links {
+                        self.renderer.link_stroke_color(&node_from, &node_to, color);
+                    }
                 }
-            }
-            None => {}
-        }
-        match nodes_move {
-            Some(moves) => {
-                for (node, (cx, cy), freezed) in moves {
-                    self.renderer.node_move(node, Point::new(cx, cy), freezed);
+                Change::ColorLastLinks(links, color) => {
+                    for (node_from, node_to) in links {
+                        self.renderer
+                            .last_link_stroke_color(&node_from, &node_to, color);
+                    }
                 }
-            }
-            None => {}
-        }
-        match nodes_filled {
-            Some((nodes, color)) => {
-                for node in nodes {
-                    self.renderer.node_fill_color(node, color);
+                Change::MoveNodes(moves) => {
+                    for (node, (cx, cy), freezed) in moves {
+                        self.renderer.node_move(&node, Point::new(cx, cy), freezed);
+                    }
                 }
-            }
-            None => {}
-        }
-        match links_deleted {
-            Some(links) => {
-                for (node_from, node_to) in links {
-                    self.adjacency.get_mut(&node_from).unwrap().remove(&node_to);
-                    self.adjacency.get_mut(&node_to).unwrap().remove(&node_from);
+                Change::FillNodes(nodes, color) => {
+                    for node in nodes {
+                        self.renderer.node_fill_color(&node, color);
+                    }
+                }
+                Change::DeleteLinks(links) => {
+                    for (node_from, node_to) in links {
+                        if let Some(position) = self.link_position(&node_from, &node_to) {
+                            self.link_list.remove(position);
+                        }
+                        self.link_sync(&node_from, &node_to);
 
-                    self.renderer.delete_link(node_from, node_to);
+                        self.renderer.delete_link(&node_from, &node_to);
+                    }
                 }
-            }
-            None => {}
-        }
-        match nodes_deleted {
-            Some(nodes) => {
-                for node in nodes {
-                    self.adjacency.remove(&node);
-                    self.renderer.delete_node(node);
+                Change::DeleteNodes(nodes) => {
+                    for node in nodes {
+                        self.adjacency.remove(&node);
+                        self.renderer.delete_node(&node);
+                    }
                 }
-            }
-            None => {}
-        }
-        match nodes_added {
-            Some(nodes) => {
-                for (node, xy) in nodes {
-                    self.adjacency.insert(node, BTreeMap::new());
-
-                    let point = match xy {
-                        Some((x, y)) => Some(Point::new(x as i32, y as i32)),
-                        None => None,
-                    };
-                    self.renderer.add_node(node, point);
+                Change::AddNodes(nodes) => {
+                    for (node, xy) in nodes {
+                        self.adjacency.insert(node.clone(), BTreeMap::new());
+
+                        let point = xy.map(|(x, y)| Point::new(x as i32, y as i32));
+                        self.renderer.add_node(node, point);
+                    }
                 }
-            }
-            None => {}
-        }
-        match links_added {
-            Some(links) => {
-                for (node_from, node_to, bidirect, value) in links {
-                    self.adjacency
-                        .get_mut(&node_from)
-                        .unwrap()
-                        .insert(node_to, value);
-                    if bidirect {
-                        self.adjacency
-                            .get_mut(&node_to)
-                            .unwrap()
-                            .insert(node_from, value);
+                Change::AddLinks(links) => {
+                    for (node_from, node_to, bidirect, value) in links {
+                        let link = match bidirect && node_to < node_from {
+                            true => (node_to.clone(), node_from.clone(), bidirect, value),
+                            false => (node_from.clone(), node_to.clone(), bidirect, value),
+                        };
+                        self.link_list.push(link);
+                        self.link_sync(&node_from, &node_to);
+                        let label = match value == W::default() {
+                            true => String::new(),
+                            false => value.to_string(),
+                        };
+                        self.renderer
+                            .add_link(&node_from, &node_to, bidirect, label);
                     }
-                    self.renderer.add_link(node_from, node_to, bidirect, value);
                 }
-            }
-            None => {}
-        }
-
-        match nodes_text_colered {
-            Some((nodes, color)) => {
-                for node in nodes {
-                    self.renderer.node_text_color(node, color);
+                Change::ColorNodesText(nodes, color) => {
+                    for node in nodes {
+                        self.renderer.node_text_color(&node, color);
+                    }
                 }
-            }
-            None => {}
-        }
-        match links_text_colered {
-            Some((links, color)) => {
-                for (node_from, node_to) in links {
-                    self.renderer.link_text_color(node_from, node_to, color);
+                Change::ColorLinksText(links, color) => {
+                    for (node_from, node_to) in links {
+                        self.renderer.link_text_color(&node_from, &node_to, color);
+                    }
+                }
+                Change::BadgeNodes(nodes) => {
+                    for (node, text) in nodes {
+                        self.renderer.node_badge(&node, text);
+                    }
+                }
+                Change::LabelLinks(links) => {
+                    for (node_from, node_to, text) in links {
+                        self.renderer.link_label(&node_from, &node_to, text);
+                    }
+                }
+                Change::ValueLinks(links) => {
+                    for (node_from, node_to, value) in links {
+                        if let Some(position) = self.link_position(&node_from, &node_to) {
+                            self.link_list[position].3 = value;
+                        }
+                        self.link_sync(&node_from, &node_to);
+                        let label = match value == W::default() {
+                            true => String::new(),
+                            false => value.to_string(),
+                        };
+                        self.renderer.link_value(&node_from, &node_to, label);
+                    }
+                }
+                Change::ResizeNodes(nodes) => {
+                    for (node, radius) in nodes {
+                        self.renderer.node_resize(&node, radius);
+                    }
+                }
+                Change::WidenLinks(links) => {
+                    for (node_from, node_to, width) in links {
+                        self.renderer.link_width(&node_from, &node_to, width);
+                    }
                 }
             }
-            None => {}
         }
 
         if anim_state_init == AnimState::Resumed {
-            self.step_speed(duration_ms, self.p_speed_factor);
+            or_panic(self.step_speed(duration_ms, self.p_speed_factor));
             self.resume();
         } else {
             self.duration_on_resume = duration_ms;
@@ -1152,22 +1844,27 @@ impl Graph {
         self.p_radius
     }
 
-    /// Returns the graph sequence (outdegree increasing order).
-    pub fn sequence(&self) -> Vec<(char, (usize, usize))> {
+    /// Returns the visual style of the graph.
+    pub fn style(&self) -> &Style<N> {
+        &self.style
+    }
+
+    /// Returns the graph sequence as (outdegree, indegree) of each node, in outdegree decreasing order.
+    pub fn sequence(&self) -> Vec<(N, (usize, usize))> {
         let mut degrees = BTreeMap::new();
 
         for (node_from, neighbors) in &self.adjacency {
-            degrees.insert(*node_from, (neighbors.len(), 0));
+            degrees.insert(node_from.clone(), (neighbors.len(), 0));
         }
 
-        for (_, neighbors) in &self.adjacency {
+        for neighbors in self.adjacency.values() {
             for node_to in neighbors.keys() {
                 let (_, indegree) = degrees.get_mut(node_to).unwrap();
                 *indegree += 1;
             }
         }
 
-        let mut sequence: Vec<(char, (usize, usize))> = degrees.into_iter().collect();
+        let mut sequence: Vec<(N, (usize, usize))> = degrees.into_iter().collect();
         sequence.sort_by(|(_, (a, _)), (_, (b, _))| b.cmp(a));
         sequence
     }
@@ -0,0 +1,1469 @@
+//! Readers and writers of the graph structure in DOT, GraphML, JSON and CSV formats (see `GraphFormat`).
+//!
+//! Formats are exchanged through a `Document`, where node names and link values are kept as text:
+//! the graph parses them with `FromStr`, as it does for graph configurations.
+
+use std::collections::BTreeMap;
+
+/// (red, green, blue) color.
+pub type Rgb = (u8, u8, u8);
+
+/// (line, column) in a text (1-based).
+pub type At = (usize, usize);
+
+/// Graph structure read from or written to a text format.
+#[derive(Default)]
+pub struct Document {
+    pub nodes: Vec<NodeEntry>,
+    pub links: Vec<LinkEntry>,
+}
+
+pub struct NodeEntry {
+    pub name: String,
+    /// Position in the SVG graphic context, kept by the graph when the node is freezed.
+    pub position: Option<(i32, i32)>,
+    pub freezed: bool,
+    /// Colors which differ from the graph style.
+    pub fill: Option<Rgb>,
+    pub stroke: Option<Rgb>,
+    /// Position of the node in the text.
+    pub at: At,
+}
+
+pub struct LinkEntry {
+    pub from: String,
+    pub to: String,
+    /// Link value, the default value of the graph links if `None`.
+    pub value: Option<String>,
+    pub bidirect: bool,
+    pub stroke: Option<Rgb>,
+    /// Position of the link in the text.
+    pub at: At,
+}
+
+/// Invalid text (line and column are 1-based).
+pub struct ParseFailure {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl NodeEntry {
+    fn new(name: &str, at: At) -> NodeEntry {
+        NodeEntry {
+            name: name.to_string(),
+            position: None,
+            freezed: false,
+            fill: None,
+            stroke: None,
+            at,
+        }
+    }
+}
+
+impl LinkEntry {
+    fn new(from: &str, to: &str, bidirect: bool, at: At) -> LinkEntry {
+        LinkEntry {
+            from: from.to_string(),
+            to: to.to_string(),
+            value: None,
+            bidirect,
+            stroke: None,
+            at,
+        }
+    }
+}
+
+// Document under construction: nodes are declared by their first occurrence, links ends included.
+#[derive(Default)]
+struct Builder {
+    document: Document,
+    index: BTreeMap<String, usize>,
+}
+
+impl Builder {
+    fn node(&mut self, name: &str, at: At) -> &mut NodeEntry {
+        let nodes = &mut self.document.nodes;
+        let i = *self.index.entry(name.to_string()).or_insert_with(|| {
+            nodes.push(NodeEntry::new(name, at));
+            nodes.len() - 1
+        });
+        &mut self.document.nodes[i]
+    }
+
+    fn link(&mut self, link: LinkEntry) {
+        self.node(&link.from, link.at);
+        self.node(&link.to, link.at);
+        self.document.links.push(link);
+    }
+}
+
+// Characters of a text, with the line and column of the current one.
+struct Cursor {
+    chars: Vec<char>,
+    i: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor {
+    fn new(text: &str) -> Cursor {
+        Cursor {
+            chars: text.chars().collect(),
+            i: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.i).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.i + offset).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.i += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(c)
+    }
+
+    fn at(&self) -> At {
+        (self.line, self.column)
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        text.chars()
+            .enumerate()
+            .all(|(k, c)| self.peek_at(k) == Some(c))
+    }
+
+    // Consumes the text if it comes next.
+    fn eat(&mut self, text: &str) -> bool {
+        let found = self.starts_with(text);
+        if found {
+            text.chars().for_each(|_| {
+                self.next();
+            });
+        }
+        found
+    }
+
+    // Consumes the characters up to the end of the text, which is consumed too.
+    fn skip_past(&mut self, end: &str) -> Result<(), ParseFailure> {
+        let at = self.at();
+        while !self.eat(end) {
+            if self.next().is_none() {
+                return Err(failure(at, format!("'{}' is missing", end)));
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn fail<T>(&self, message: String) -> Result<T, ParseFailure> {
+        Err(failure(self.at(), message))
+    }
+
+    fn unexpected<T>(&self) -> Result<T, ParseFailure> {
+        match self.peek() {
+            Some(c) => self.fail(format!("character '{}' is unexpected", c)),
+            None => self.fail("text ends unexpectedly".to_string()),
+        }
+    }
+}
+
+fn failure((line, column): At, message: String) -> ParseFailure {
+    ParseFailure {
+        line,
+        column,
+        message,
+    }
+}
+
+fn hex((red, green, blue): Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", red, green, blue)
+}
+
+// Reads a color given as `#rrggbb`, `#rrggbbaa` (alpha is ignored), `#rgb` or as a basic color name.
+fn color_from(text: &str) -> Result<Rgb, String> {
+    let invalid = || format!("'{}' is an invalid color", text);
+    let digits = |s: &str| u8::from_str_radix(s, 16).map_err(|_| invalid());
+    let text = text.trim();
+    if let Some(code) = text.strip_prefix('#') {
+        if !code.is_ascii() {
+            return Err(invalid());
+        }
+        return match code.len() {
+            6 | 8 => Ok((
+                digits(&code[0..2])?,
+                digits(&code[2..4])?,
+                digits(&code[4..6])?,
+            )),
+            3 => Ok((
+                digits(&code[0..1])? * 17,
+                digits(&code[1..2])? * 17,
+                digits(&code[2..3])? * 17,
+            )),
+            _ => Err(invalid()),
+        };
+    }
+    match text.to_lowercase().as_str() {
+        "black" => Ok((0, 0, 0)),
+        "white" => Ok((255, 255, 255)),
+        "red" => Ok((255, 0, 0)),
+        "green" => Ok((0, 255, 0)),
+        "blue" => Ok((0, 0, 255)),
+        "yellow" => Ok((255, 255, 0)),
+        "cyan" => Ok((0, 255, 255)),
+        "magenta" => Ok((255, 0, 255)),
+        "orange" => Ok((255, 165, 0)),
+        "purple" => Ok((160, 32, 240)),
+        "gray" | "grey" => Ok((192, 192, 192)),
+        _ => Err(invalid()),
+    }
+}
+
+fn coordinate_from(text: &str) -> Result<i32, String> {
+    match text.trim().parse::<f64>() {
+        Ok(c) if c.is_finite() && c.abs() <= i32::MAX as f64 => Ok(c.round() as i32),
+        _ => Err(format!("'{}' is an invalid coordinate", text.trim())),
+    }
+}
+
+fn bool_from(text: &str) -> Result<bool, String> {
+    match text.trim().to_lowercase().as_str() {
+        "true" | "1" | "yes" => Ok(true),
+        "false" | "0" | "no" | "" => Ok(false),
+        _ => Err(format!("'{}' is an invalid boolean", text.trim())),
+    }
+}
+
+/// Writes the document in the Graphviz DOT language.
+///
+/// Freezed positions are pinned (y axis pointing up, as in Graphviz), link values are labels.
+/// A graph with directed links is a `digraph`, where bidirectional links have `dir=none`.
+pub fn write_dot(document: &Document) -> String {
+    let directed = document.links.iter().any(|link| !link.bidirect);
+    let mut dot = String::from(if directed { "digraph {\n" } else { "graph {\n" });
+    for node in &document.nodes {
+        let mut attributes = Vec::new();
+        if let (true, Some((x, y))) = (node.freezed, node.position) {
+            attributes.push(format!("pos=\"{},{}!\"", x, -y));
+        }
+        if let Some(fill) = node.fill {
+            attributes.push("style=filled".to_string());
+            attributes.push(format!("fillcolor=\"{}\"", hex(fill)));
+        }
+        if let Some(stroke) = node.stroke {
+            attributes.push(format!("color=\"{}\"", hex(stroke)));
+        }
+        dot.push_str(&format!(
+            "    {}{};\n",
+            dot_id(&node.name),
+            dot_attributes(&attributes)
+        ));
+    }
+    for link in &document.links {
+        let mut attributes = Vec::new();
+        if let Some(value) = &link.value {
+            attributes.push(format!("label={}", dot_id(value)));
+        }
+        if directed && link.bidirect {
+            attributes.push("dir=none".to_string());
+        }
+        if let Some(stroke) = link.stroke {
+            attributes.push(format!("color=\"{}\"", hex(stroke)));
+        }
+        dot.push_str(&format!(
+            "    {} {} {}{};\n",
+            dot_id(&link.from),
+            if directed { "->" } else { "--" },
+            dot_id(&link.to),
+            dot_attributes(&attributes)
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn dot_attributes(attributes: &[String]) -> String {
+    match attributes.is_empty() {
+        true => String::new(),
+        false => format!(" [{}]", attributes.join(", ")),
+    }
+}
+
+// Quotes the identifier unless it is a DOT identifier or numeral.
+fn dot_id(id: &str) -> String {
+    let identifier = id.chars().next().is_some_and(|c| !c.is_ascii_digit())
+        && id.chars().all(|c| c == '_' || c.is_alphanumeric());
+    let numeral = id.parse::<f64>().is_ok()
+        && id
+            .chars()
+            .enumerate()
+            .all(|(k, c)| c.is_ascii_digit() || c == '.' || c == '-' && k == 0);
+    let keyword = ["node", "edge", "graph", "digraph", "subgraph", "strict"]
+        .contains(&id.to_lowercase().as_str());
+    if (identifier || numeral) && !keyword {
+        id.to_string()
+    } else {
+        format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+    }
+}
+
+#[derive(PartialEq)]
+enum DotKind {
+    Id,
+    Quoted,
+    Symbol,
+}
+
+struct DotToken {
+    kind: DotKind,
+    text: String,
+    at: At,
+}
+
+fn dot_tokens(text: &str) -> Result<Vec<DotToken>, ParseFailure> {
+    let mut cursor = Cursor::new(text);
+    let mut tokens = Vec::new();
+    let mut line_start = true;
+    loop {
+        while let Some(c) = cursor.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            line_start |= c == '\n';
+            cursor.next();
+        }
+        let at = cursor.at();
+        let token = |kind, text| DotToken { kind, text, at };
+        let c = match cursor.peek() {
+            Some(c) => c,
+            None => return Ok(tokens),
+        };
+        if line_start && c == '#' || cursor.starts_with("//") {
+            while cursor.peek().is_some_and(|c| c != '\n') {
+                cursor.next();
+            }
+            continue;
+        }
+        line_start = false;
+        if cursor.eat("/*") {
+            cursor.skip_past("*/")?;
+        } else if cursor.eat("->") {
+            tokens.push(token(DotKind::Symbol, "->".to_string()));
+        } else if cursor.eat("--") {
+            tokens.push(token(DotKind::Symbol, "--".to_string()));
+        } else if "{}[]=;,:".contains(c) {
+            cursor.next();
+            tokens.push(token(DotKind::Symbol, c.to_string()));
+        } else if c == '"' {
+            cursor.next();
+            let mut quoted = String::new();
+            loop {
+                match cursor.next() {
+                    Some('"') => break,
+                    Some('\\') if cursor.peek() == Some('"') => quoted.extend(cursor.next()),
+                    Some('\\') if cursor.peek() == Some('\n') => {
+                        cursor.next();
+                    }
+                    Some(c) => quoted.push(c),
+                    None => return Err(failure(at, "string is not closed".to_string())),
+                }
+            }
+            tokens.push(token(DotKind::Quoted, quoted));
+        } else if c == '<' {
+            // HTML string, between balanced angle brackets
+            cursor.next();
+            let mut html = String::new();
+            let mut depth = 1;
+            loop {
+                match cursor.next() {
+                    Some('>') if depth == 1 => break,
+                    Some(c) => {
+                        depth += (c == '<') as i32 - (c == '>') as i32;
+                        html.push(c);
+                    }
+                    None => return Err(failure(at, "HTML string is not closed".to_string())),
+                }
+            }
+            tokens.push(token(DotKind::Quoted, html));
+        } else if c == '_' || c.is_alphabetic() || !c.is_ascii() {
+            let mut id = String::new();
+            while let Some(c) = cursor.peek().filter(|c| *c == '_' || c.is_alphanumeric()) {
+                id.push(c);
+                cursor.next();
+            }
+            tokens.push(token(DotKind::Id, id));
+        } else if c.is_ascii_digit() || c == '.' || c == '-' {
+            let mut numeral = String::new();
+            if c == '-' {
+                numeral.push(c);
+                cursor.next();
+            }
+            while let Some(c) = cursor.peek().filter(|c| c.is_ascii_digit() || *c == '.') {
+                numeral.push(c);
+                cursor.next();
+            }
+            if numeral.parse::<f64>().is_err() {
+                return Err(failure(at, format!("'{}' is an invalid numeral", numeral)));
+            }
+            tokens.push(token(DotKind::Id, numeral));
+        } else {
+            return cursor.unexpected();
+        }
+    }
+}
+
+struct DotParser {
+    tokens: Vec<DotToken>,
+    i: usize,
+    end: At,
+    directed: bool,
+    strict: bool,
+    builder: Builder,
+}
+
+impl DotParser {
+    fn peek_symbol(&self, symbol: &str) -> bool {
+        self.tokens
+            .get(self.i)
+            .is_some_and(|t| t.kind == DotKind::Symbol && t.text == symbol)
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        self.tokens
+            .get(self.i)
+            .is_some_and(|t| t.kind == DotKind::Id && t.text.eq_ignore_ascii_case(keyword))
+    }
+
+    fn fail<T>(&self, message: String) -> Result<T, ParseFailure> {
+        let at = self.tokens.get(self.i).map_or(self.end, |t| t.at);
+        Err(failure(at, message))
+    }
+
+    fn unexpected<T>(&self) -> Result<T, ParseFailure> {
+        match self.tokens.get(self.i) {
+            Some(token) => self.fail(format!("'{}' is unexpected", token.text)),
+            None => self.fail("text ends unexpectedly".to_string()),
+        }
+    }
+
+    fn expect(&mut self, symbol: &str) -> Result<(), ParseFailure> {
+        if !self.peek_symbol(symbol) {
+            return self.fail(format!("'{}' is expected", symbol));
+        }
+        self.i += 1;
+        Ok(())
+    }
+
+    // Returns an identifier (or a numeral, or a quoted string).
+    fn id(&mut self) -> Result<(String, At), ParseFailure> {
+        match self.tokens.get(self.i) {
+            Some(token) if token.kind != DotKind::Symbol => {
+                self.i += 1;
+                Ok((token.text.clone(), token.at))
+            }
+            _ => self.unexpected(),
+        }
+    }
+
+    fn graph(&mut self) -> Result<(), ParseFailure> {
+        if self.peek_keyword("strict") {
+            self.strict = true;
+            self.i += 1;
+        }
+        if self.peek_keyword("digraph") {
+            self.directed = true;
+        } else if !self.peek_keyword("graph") {
+            return self.fail("'graph' or 'digraph' is expected".to_string());
+        }
+        self.i += 1;
+        if !self.peek_symbol("{") {
+            self.id()?;
+        }
+        self.expect("{")?;
+        self.statements()?;
+        if self.i < self.tokens.len() {
+            return self.unexpected();
+        }
+        Ok(())
+    }
+
+    // Statements up to the closing brace, subgraphs being flattened.
+    fn statements(&mut self) -> Result<(), ParseFailure> {
+        loop {
+            if self.peek_symbol("}") {
+                self.i += 1;
+                return Ok(());
+            }
+            if self.i == self.tokens.len() {
+                return self.fail("'}' is expected".to_string());
+            }
+            self.statement()?;
+            if self.peek_symbol(";") {
+                self.i += 1;
+            }
+        }
+    }
+
+    fn statement(&mut self) -> Result<(), ParseFailure> {
+        if ["graph", "node", "edge"]
+            .iter()
+            .any(|k| self.peek_keyword(k))
+        {
+            // default attributes are not kept
+            self.i += 1;
+            self.attributes()?;
+            return Ok(());
+        }
+        if self.peek_keyword("subgraph") {
+            self.i += 1;
+            if !self.peek_symbol("{") {
+                self.id()?;
+            }
+        }
+        if self.peek_symbol("{") {
+            self.i += 1;
+            return self.statements();
+        }
+
+        let (name, at) = self.id()?;
+        if self.peek_symbol("=") {
+            // graph attribute
+            self.i += 1;
+            self.id()?;
+            return Ok(());
+        }
+        self.port()?;
+        let mut ends = vec![name];
+        while self.peek_symbol("->") || self.peek_symbol("--") {
+            let operator = if self.directed { "->" } else { "--" };
+            if !self.peek_symbol(operator) {
+                return self.fail(format!("links of this graph are written '{}'", operator));
+            }
+            self.i += 1;
+            if self.peek_symbol("{") || self.peek_keyword("subgraph") {
+                return self.fail("subgraphs as link ends are not supported".to_string());
+            }
+            ends.push(self.id()?.0);
+            self.port()?;
+        }
+        let attributes = self.attributes()?;
+
+        if ends.len() == 1 {
+            let node = self.builder.node(&ends[0], at);
+            for (key, value, at) in attributes {
+                let result = match key.as_str() {
+                    "pos" => dot_position(&value).map(|(x, y)| {
+                        node.position = Some((x, y));
+                        node.freezed = true;
+                    }),
+                    "fillcolor" => color_from(&value).map(|color| node.fill = Some(color)),
+                    "color" => color_from(&value).map(|color| node.stroke = Some(color)),
+                    _ => Ok(()),
+                };
+                result.map_err(|message| failure(at, message))?;
+            }
+            return Ok(());
+        }
+
+        let mut bidirect = !self.directed;
+        let mut value = None;
+        let mut weight = None;
+        let mut stroke = None;
+        for (key, text, at) in attributes {
+            match key.as_str() {
+                "label" => value = Some(text),
+                "weight" => weight = Some(text),
+                "dir" => bidirect |= text == "none" || text == "both",
+                "color" => {
+                    stroke = Some(color_from(&text).map_err(|message| failure(at, message))?)
+                }
+                _ => {}
+            }
+        }
+        let value = value.or(weight);
+        for pair in ends.windows(2) {
+            if let Some(link) = self.strict_link(&pair[0], &pair[1]) {
+                link.value = value.clone().or(link.value.take());
+                link.stroke = stroke.or(link.stroke);
+                continue;
+            }
+            let mut link = LinkEntry::new(&pair[0], &pair[1], bidirect, at);
+            link.value = value.clone();
+            link.stroke = stroke;
+            self.builder.link(link);
+        }
+        Ok(())
+    }
+
+    // Link already declared between the nodes of a strict graph (in either order if undirected).
+    fn strict_link(&mut self, from: &str, to: &str) -> Option<&mut LinkEntry> {
+        if !self.strict {
+            return None;
+        }
+        let directed = self.directed;
+        self.builder.document.links.iter_mut().find(|link| {
+            link.from == from && link.to == to || !directed && link.from == to && link.to == from
+        })
+    }
+
+    // Skips the port of a node (`:port` or `:port:compass`).
+    fn port(&mut self) -> Result<(), ParseFailure> {
+        for _ in 0..2 {
+            if self.peek_symbol(":") {
+                self.i += 1;
+                self.id()?;
+            }
+        }
+        Ok(())
+    }
+
+    // Attributes lists `[key = value, ...]`, a key without value being `true`.
+    fn attributes(&mut self) -> Result<Vec<(String, String, At)>, ParseFailure> {
+        let mut attributes = Vec::new();
+        while self.peek_symbol("[") {
+            self.i += 1;
+            while !self.peek_symbol("]") {
+                let (key, at) = self.id()?;
+                let value = if self.peek_symbol("=") {
+                    self.i += 1;
+                    self.id()?.0
+                } else {
+                    "true".to_string()
+                };
+                attributes.push((key, value, at));
+                if self.peek_symbol(",") || self.peek_symbol(";") {
+                    self.i += 1;
+                }
+            }
+            self.i += 1;
+        }
+        Ok(attributes)
+    }
+}
+
+// Reads a Graphviz position `x,y` or `x,y!` (y axis pointing up).
+fn dot_position(text: &str) -> Result<(i32, i32), String> {
+    let coordinates = text.trim().trim_end_matches('!');
+    match coordinates.split(',').collect::<Vec<&str>>().as_slice() {
+        [x, y] => Ok((coordinate_from(x)?, -coordinate_from(y)?)),
+        _ => Err(format!("'{}' is an invalid position", text)),
+    }
+}
+
+/// Reads a graph in the Graphviz DOT language (see `write_dot`).
+///
+/// Nodes with a position are freezed. Subgraphs are flattened, and default attributes are ignored.
+/// A link value is read from its `label`, or else from its `weight`. The links between the same nodes of a `strict` graph are merged.
+pub fn read_dot(text: &str) -> Result<Document, ParseFailure> {
+    let mut cursor = Cursor::new(text);
+    while cursor.next().is_some() {}
+    let mut parser = DotParser {
+        tokens: dot_tokens(text)?,
+        i: 0,
+        end: cursor.at(),
+        directed: false,
+        strict: false,
+        builder: Builder::default(),
+    };
+    parser.graph()?;
+    Ok(parser.builder.document)
+}
+
+/// Writes the document in GraphML.
+///
+/// Nodes have `x`, `y`, `freezed`, `fill` and `stroke` data, links have `value` and `color` data.
+pub fn write_graphml(document: &Document) -> String {
+    let mut xml = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">
+  <key id=\"x\" for=\"node\" attr.name=\"x\" attr.type=\"int\"/>
+  <key id=\"y\" for=\"node\" attr.name=\"y\" attr.type=\"int\"/>
+  <key id=\"freezed\" for=\"node\" attr.name=\"freezed\" attr.type=\"boolean\"/>
+  <key id=\"fill\" for=\"node\" attr.name=\"fill\" attr.type=\"string\"/>
+  <key id=\"stroke\" for=\"node\" attr.name=\"stroke\" attr.type=\"string\"/>
+  <key id=\"value\" for=\"edge\" attr.name=\"value\" attr.type=\"string\"/>
+  <key id=\"color\" for=\"edge\" attr.name=\"color\" attr.type=\"string\"/>
+  <graph id=\"G\" edgedefault=\"undirected\">
+",
+    );
+    let data =
+        |key: &str, value: &str| format!("<data key=\"{}\">{}</data>", key, xml_escape(value));
+    for node in &document.nodes {
+        let mut datas = Vec::new();
+        if let Some((x, y)) = node.position {
+            datas.push(data("x", &x.to_string()));
+            datas.push(data("y", &y.to_string()));
+        }
+        datas.push(data("freezed", &node.freezed.to_string()));
+        if let Some(fill) = node.fill {
+            datas.push(data("fill", &hex(fill)));
+        }
+        if let Some(stroke) = node.stroke {
+            datas.push(data("stroke", &hex(stroke)));
+        }
+        xml.push_str(&format!(
+            "    <node id=\"{}\">{}</node>\n",
+            xml_escape(&node.name),
+            datas.concat()
+        ));
+    }
+    for link in &document.links {
+        let mut datas = Vec::new();
+        if let Some(value) = &link.value {
+            datas.push(data("value", value));
+        }
+        if let Some(stroke) = link.stroke {
+            datas.push(data("color", &hex(stroke)));
+        }
+        let directed = if link.bidirect {
+            ""
+        } else {
+            " directed=\"true\""
+        };
+        xml.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\"{}>{}</edge>\n",
+            xml_escape(&link.from),
+            xml_escape(&link.to),
+            directed,
+            datas.concat()
+        ));
+    }
+    xml.push_str("  </graph>\n</graphml>\n");
+    xml
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// XML element, with local names (namespaces prefixes are dropped).
+struct Element {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<Element>,
+    text: String,
+    at: At,
+}
+
+impl Element {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn required(&self, name: &str) -> Result<&str, ParseFailure> {
+        self.attribute(name).ok_or_else(|| {
+            failure(
+                self.at,
+                format!("attribute '{}' of element '{}' is missing", name, self.name),
+            )
+        })
+    }
+
+    fn elements<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+}
+
+fn local_name(name: &str) -> String {
+    name.rsplit(':').next().unwrap_or(name).to_string()
+}
+
+fn xml_name(cursor: &mut Cursor) -> Result<String, ParseFailure> {
+    let mut name = String::new();
+    while let Some(c) = cursor
+        .peek()
+        .filter(|c| c.is_alphanumeric() || "_:.-".contains(*c))
+    {
+        name.push(c);
+        cursor.next();
+    }
+    match name.is_empty() {
+        true => cursor.unexpected(),
+        false => Ok(name),
+    }
+}
+
+// Reads the text up to the delimiter (excluded), decoding the entities.
+fn xml_text(cursor: &mut Cursor, delimiter: char) -> Result<String, ParseFailure> {
+    let mut text = String::new();
+    while let Some(c) = cursor.peek().filter(|c| *c != delimiter) {
+        let at = cursor.at();
+        cursor.next();
+        if c != '&' {
+            text.push(c);
+            continue;
+        }
+        let mut entity = String::new();
+        while let Some(c) = cursor.next().filter(|c| *c != ';') {
+            entity.push(c);
+        }
+        let decoded = match entity.as_str() {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                Some(code) => u32::from_str_radix(code, 16).ok().and_then(char::from_u32),
+                None => entity
+                    .strip_prefix('#')
+                    .and_then(|code| code.parse().ok())
+                    .and_then(char::from_u32),
+            },
+        };
+        match decoded {
+            Some(c) => text.push(c),
+            None => return Err(failure(at, format!("entity '&{};' is unknown", entity))),
+        }
+    }
+    Ok(text)
+}
+
+// Skips the comments, processing instructions and declarations, returning whether one was skipped.
+fn xml_skip_markup(cursor: &mut Cursor) -> Result<bool, ParseFailure> {
+    if cursor.eat("<!--") {
+        cursor.skip_past("-->")?;
+    } else if cursor.eat("<?") {
+        cursor.skip_past("?>")?;
+    } else if cursor.eat("<!") {
+        let mut depth = 0;
+        loop {
+            match cursor.next() {
+                Some('[') => depth += 1,
+                Some(']') => depth -= 1,
+                Some('>') if depth == 0 => break,
+                Some(_) => {}
+                None => return cursor.fail("'>' is missing".to_string()),
+            }
+        }
+    } else {
+        return Ok(false);
+    }
+    Ok(true)
+}
+
+fn xml_element(cursor: &mut Cursor) -> Result<Element, ParseFailure> {
+    let at = cursor.at();
+    if !cursor.eat("<") {
+        return cursor.unexpected();
+    }
+    let name = xml_name(cursor)?;
+    let mut element = Element {
+        name: local_name(&name),
+        attributes: Vec::new(),
+        children: Vec::new(),
+        text: String::new(),
+        at,
+    };
+    loop {
+        cursor.skip_whitespaces();
+        if cursor.eat("/>") {
+            return Ok(element);
+        }
+        if cursor.eat(">") {
+            break;
+        }
+        let attribute = xml_name(cursor)?;
+        cursor.skip_whitespaces();
+        if !cursor.eat("=") {
+            return cursor.unexpected();
+        }
+        cursor.skip_whitespaces();
+        let quote = match cursor.next() {
+            Some(quote) if quote == '"' || quote == '\'' => quote,
+            _ => return cursor.fail("attribute value must be quoted".to_string()),
+        };
+        let value = xml_text(cursor, quote)?;
+        if cursor.next().is_none() {
+            return cursor.fail("attribute value is not closed".to_string());
+        }
+        element.attributes.push((local_name(&attribute), value));
+    }
+
+    loop {
+        if cursor.eat("</") {
+            let end = xml_name(cursor)?;
+            cursor.skip_whitespaces();
+            if end != name || !cursor.eat(">") {
+                return Err(failure(at, format!("element '{}' is not closed", name)));
+            }
+            return Ok(element);
+        }
+        if cursor.eat("<![CDATA[") {
+            while !cursor.eat("]]>") {
+                match cursor.next() {
+                    Some(c) => element.text.push(c),
+                    None => return cursor.fail("']]>' is missing".to_string()),
+                }
+            }
+        } else if cursor.starts_with("<!") || cursor.starts_with("<?") {
+            xml_skip_markup(cursor)?;
+        } else if cursor.peek() == Some('<') {
+            element.children.push(xml_element(cursor)?);
+        } else if cursor.peek().is_some() {
+            let text = xml_text(cursor, '<')?;
+            element.text.push_str(&text);
+        } else {
+            return Err(failure(at, format!("element '{}' is not closed", name)));
+        }
+    }
+}
+
+fn xml_document(text: &str) -> Result<Element, ParseFailure> {
+    let mut cursor = Cursor::new(text);
+    cursor.eat("\u{feff}");
+    loop {
+        cursor.skip_whitespaces();
+        if !xml_skip_markup(&mut cursor)? {
+            break;
+        }
+    }
+    let root = xml_element(&mut cursor)?;
+    loop {
+        cursor.skip_whitespaces();
+        if !xml_skip_markup(&mut cursor)? {
+            break;
+        }
+    }
+    match cursor.peek() {
+        Some(_) => cursor.unexpected(),
+        None => Ok(root),
+    }
+}
+
+/// Reads a graph in GraphML (see `write_graphml`), from the first `graph` element.
+///
+/// Nodes with `x` and `y` data are freezed unless their `freezed` data is false. `weight` data is also read as link value.
+pub fn read_graphml(text: &str) -> Result<Document, ParseFailure> {
+    let root = xml_document(text)?;
+    if root.name != "graphml" {
+        return Err(failure(
+            root.at,
+            "element 'graphml' is expected".to_string(),
+        ));
+    }
+
+    // (attribute name, default value) of the data keys
+    let mut keys: BTreeMap<String, (String, Option<String>)> = BTreeMap::new();
+    for key in root.elements("key") {
+        let id = key.required("id")?;
+        let name = key.attribute("attr.name").unwrap_or(id).to_lowercase();
+        let default = key.elements("default").next().map(|d| d.text.clone());
+        keys.insert(id.to_string(), (name, default));
+    }
+    let graph = root
+        .elements("graph")
+        .next()
+        .ok_or_else(|| failure(root.at, "element 'graph' is missing".to_string()))?;
+    let directed = graph.attribute("edgedefault") == Some("directed");
+
+    // data of an element by attribute name, defaults included
+    let datas = |element: &Element| -> Result<BTreeMap<String, (String, At)>, ParseFailure> {
+        let mut datas = BTreeMap::new();
+        for (name, default) in keys.values() {
+            if let Some(default) = default {
+                datas.insert(name.clone(), (default.clone(), element.at));
+            }
+        }
+        for data in element.elements("data") {
+            let key = data.required("key")?;
+            let name = keys
+                .get(key)
+                .map_or(key.to_lowercase(), |(name, _)| name.clone());
+            datas.insert(name, (data.text.trim().to_string(), data.at));
+        }
+        Ok(datas)
+    };
+
+    let mut builder = Builder::default();
+    for element in graph.elements("node") {
+        let datas = datas(element)?;
+        let node = builder.node(element.required("id")?, element.at);
+        let read = |name: &str| datas.get(name);
+        let mut position = (None, None);
+        for (name, (value, at)) in &datas {
+            let result = match name.as_str() {
+                "x" => coordinate_from(value).map(|x| position.0 = Some(x)),
+                "y" => coordinate_from(value).map(|y| position.1 = Some(y)),
+                "fill" => color_from(value).map(|color| node.fill = Some(color)),
+                "stroke" => color_from(value).map(|color| node.stroke = Some(color)),
+                _ => Ok(()),
+            };
+            result.map_err(|message| failure(*at, message))?;
+        }
+        if let (Some(x), Some(y)) = position {
+            node.position = Some((x, y));
+            node.freezed = match read("freezed") {
+                Some((value, at)) => bool_from(value).map_err(|message| failure(*at, message))?,
+                None => true,
+            };
+        }
+    }
+    for element in graph.elements("edge") {
+        let datas = datas(element)?;
+        let bidirect = match element.attribute("directed") {
+            Some(value) => !bool_from(value).map_err(|message| failure(element.at, message))?,
+            None => !directed,
+        };
+        let from = element.required("source")?;
+        let to = element.required("target")?;
+        let mut link = LinkEntry::new(from, to, bidirect, element.at);
+        for (name, (value, at)) in &datas {
+            match name.as_str() {
+                "value" | "weight" => link.value = Some(value.clone()),
+                "color" | "stroke" => {
+                    link.stroke = Some(color_from(value).map_err(|message| failure(*at, message))?)
+                }
+                _ => {}
+            }
+        }
+        builder.link(link);
+    }
+    Ok(builder.document)
+}
+
+/// Writes the document in JSON:
+/// `{"nodes": [{"id", "x", "y", "freezed", "fill"?, "stroke"?}, ...], "links": [{"from", "to", "value"?, "directed", "color"?}, ...]}`.
+///
+/// Values are numbers when they are valid JSON numbers, strings otherwise. Colors are `#rrggbb` strings.
+pub fn write_json(document: &Document) -> String {
+    let mut json = String::from("{\n  \"nodes\": [");
+    for (k, node) in document.nodes.iter().enumerate() {
+        let mut fields = vec![format!("\"id\": {}", json_string(&node.name))];
+        if let Some((x, y)) = node.position {
+            fields.push(format!("\"x\": {}, \"y\": {}", x, y));
+        }
+        fields.push(format!("\"freezed\": {}", node.freezed));
+        if let Some(fill) = node.fill {
+            fields.push(format!("\"fill\": \"{}\"", hex(fill)));
+        }
+        if let Some(stroke) = node.stroke {
+            fields.push(format!("\"stroke\": \"{}\"", hex(stroke)));
+        }
+        let separator = if k == 0 { "" } else { "," };
+        json.push_str(&format!("{}\n    {{{}}}", separator, fields.join(", ")));
+    }
+    json.push_str("\n  ],\n  \"links\": [");
+    for (k, link) in document.links.iter().enumerate() {
+        let mut fields = vec![
+            format!("\"from\": {}", json_string(&link.from)),
+            format!("\"to\": {}", json_string(&link.to)),
+        ];
+        if let Some(value) = &link.value {
+            let value = match json_number(value) {
+                true => value.clone(),
+                false => json_string(value),
+            };
+            fields.push(format!("\"value\": {}", value));
+        }
+        fields.push(format!("\"directed\": {}", !link.bidirect));
+        if let Some(stroke) = link.stroke {
+            fields.push(format!("\"color\": \"{}\"", hex(stroke)));
+        }
+        let separator = if k == 0 { "" } else { "," };
+        json.push_str(&format!("{}\n    {{{}}}", separator, fields.join(", ")));
+    }
+    json.push_str("\n  ]\n}\n");
+    json
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\t' => json.push_str("\\t"),
+            '\r' => json.push_str("\\r"),
+            c if (c as u32) < 0x20 => {
+                json.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+// Whether the text follows the JSON number grammar.
+fn json_number(text: &str) -> bool {
+    let mut cursor = Cursor::new(text);
+    json_number_text(&mut cursor).is_ok() && cursor.peek().is_none()
+}
+
+fn json_number_text(cursor: &mut Cursor) -> Result<String, ParseFailure> {
+    let start = cursor.i;
+    let digits = |cursor: &mut Cursor| {
+        let mut count = 0;
+        while cursor.peek().is_some_and(|c| c.is_ascii_digit()) {
+            cursor.next();
+            count += 1;
+        }
+        count
+    };
+    cursor.eat("-");
+    if !cursor.eat("0") && digits(cursor) == 0 {
+        return cursor.unexpected();
+    }
+    if cursor.eat(".") && digits(cursor) == 0 {
+        return cursor.unexpected();
+    }
+    if cursor.eat("e") || cursor.eat("E") {
+        let _ = cursor.eat("+") || cursor.eat("-");
+        if digits(cursor) == 0 {
+            return cursor.unexpected();
+        }
+    }
+    Ok(cursor.chars[start..cursor.i].iter().collect())
+}
+
+enum JsonKind {
+    Null,
+    Bool(bool),
+    // numbers are kept as written
+    Number(String),
+    Text(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+struct Json {
+    kind: JsonKind,
+    at: At,
+}
+
+impl Json {
+    fn field(&self, name: &str) -> Option<&Json> {
+        match &self.kind {
+            JsonKind::Object(fields) => fields.iter().find(|(n, _)| n == name).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn fail<T>(&self, message: String) -> Result<T, ParseFailure> {
+        Err(failure(self.at, message))
+    }
+
+    // Text of a string or a number.
+    fn text(&self, name: &str) -> Result<String, ParseFailure> {
+        match &self.kind {
+            JsonKind::Text(text) | JsonKind::Number(text) => Ok(text.clone()),
+            _ => self.fail(format!("field '{}' must be a string or a number", name)),
+        }
+    }
+
+    fn required(&self, name: &str) -> Result<&Json, ParseFailure> {
+        self.field(name)
+            .ok_or_else(|| failure(self.at, format!("field '{}' is missing", name)))
+    }
+}
+
+fn json_value(cursor: &mut Cursor) -> Result<Json, ParseFailure> {
+    cursor.skip_whitespaces();
+    let at = cursor.at();
+    let kind = match cursor.peek() {
+        Some('{') => {
+            cursor.next();
+            let mut fields = Vec::new();
+            cursor.skip_whitespaces();
+            if !cursor.eat("}") {
+                loop {
+                    cursor.skip_whitespaces();
+                    if cursor.peek() != Some('"') {
+                        return cursor.unexpected();
+                    }
+                    let name = json_string_text(cursor)?;
+                    cursor.skip_whitespaces();
+                    if !cursor.eat(":") {
+                        return cursor.unexpected();
+                    }
+                    fields.push((name, json_value(cursor)?));
+                    cursor.skip_whitespaces();
+                    if cursor.eat("}") {
+                        break;
+                    }
+                    if !cursor.eat(",") {
+                        return cursor.unexpected();
+                    }
+                }
+            }
+            JsonKind::Object(fields)
+        }
+        Some('[') => {
+            cursor.next();
+            let mut values = Vec::new();
+            cursor.skip_whitespaces();
+            if !cursor.eat("]") {
+                loop {
+                    values.push(json_value(cursor)?);
+                    cursor.skip_whitespaces();
+                    if cursor.eat("]") {
+                        break;
+                    }
+                    if !cursor.eat(",") {
+                        return cursor.unexpected();
+                    }
+                }
+            }
+            JsonKind::Array(values)
+        }
+        Some('"') => JsonKind::Text(json_string_text(cursor)?),
+        Some(c) if c == '-' || c.is_ascii_digit() => JsonKind::Number(json_number_text(cursor)?),
+        _ if cursor.eat("true") => JsonKind::Bool(true),
+        _ if cursor.eat("false") => JsonKind::Bool(false),
+        _ if cursor.eat("null") => JsonKind::Null,
+        _ => return cursor.unexpected(),
+    };
+    Ok(Json { kind, at })
+}
+
+fn json_string_text(cursor: &mut Cursor) -> Result<String, ParseFailure> {
+    let at = cursor.at();
+    cursor.next();
+    let mut text = String::new();
+    loop {
+        match cursor.next() {
+            Some('"') => return Ok(text),
+            Some('\\') => {
+                let escaped = match cursor.next() {
+                    Some('"') => '"',
+                    Some('\\') => '\\',
+                    Some('/') => '/',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{c}',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('u') => {
+                        let code: String = (0..4).filter_map(|_| cursor.next()).collect();
+                        let mut code = u32::from_str_radix(&code, 16).ok();
+                        // surrogate pair
+                        if let Some(high) = code.filter(|c| (0xD800..0xDC00).contains(c)) {
+                            if cursor.eat("\\u") {
+                                let low: String = (0..4).filter_map(|_| cursor.next()).collect();
+                                code = u32::from_str_radix(&low, 16)
+                                    .ok()
+                                    .map(|low| 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00));
+                            }
+                        }
+                        match code.and_then(char::from_u32) {
+                            Some(c) => c,
+                            None => return cursor.fail("unicode escape is invalid".to_string()),
+                        }
+                    }
+                    _ => return cursor.fail("escape is invalid".to_string()),
+                };
+                text.push(escaped);
+            }
+            Some(c) => text.push(c),
+            None => return Err(failure(at, "string is not closed".to_string())),
+        }
+    }
+}
+
+/// Reads a graph in JSON (see `write_json`).
+///
+/// Nodes with `x` and `y` are freezed unless `freezed` is false. Links are bidirectional unless `directed` is true.
+pub fn read_json(text: &str) -> Result<Document, ParseFailure> {
+    let mut cursor = Cursor::new(text);
+    let root = json_value(&mut cursor)?;
+    cursor.skip_whitespaces();
+    if cursor.peek().is_some() {
+        return cursor.unexpected();
+    }
+    if !matches!(root.kind, JsonKind::Object(_)) {
+        return root.fail("an object is expected".to_string());
+    }
+
+    let array = |name: &str| -> Result<&[Json], ParseFailure> {
+        match root.field(name).map(|field| &field.kind) {
+            None => Ok(&[]),
+            Some(JsonKind::Array(values)) => Ok(values),
+            Some(_) => root.fail(format!("field '{}' must be an array", name)),
+        }
+    };
+    let color = |value: &Json, name: &str| -> Result<Option<Rgb>, ParseFailure> {
+        match &value.kind {
+            JsonKind::Text(text) => color_from(text)
+                .map(Some)
+                .map_err(|message| failure(value.at, message)),
+            JsonKind::Null => Ok(None),
+            _ => value.fail(format!("field '{}' must be a color string", name)),
+        }
+    };
+    let boolean = |value: &Json, name: &str| match value.kind {
+        JsonKind::Bool(b) => Ok(b),
+        _ => value.fail(format!("field '{}' must be a boolean", name)),
+    };
+    let coordinate = |value: &Json, name: &str| match &value.kind {
+        JsonKind::Number(number) => {
+            coordinate_from(number).map_err(|message| failure(value.at, message))
+        }
+        _ => value.fail(format!("field '{}' must be a number", name)),
+    };
+
+    let mut builder = Builder::default();
+    for value in array("nodes")? {
+        let name = value.required("id")?.text("id")?;
+        let node = builder.node(&name, value.at);
+        if let (Some(x), Some(y)) = (value.field("x"), value.field("y")) {
+            node.position = Some((coordinate(x, "x")?, coordinate(y, "y")?));
+            node.freezed = true;
+        }
+        if let Some(freezed) = value.field("freezed") {
+            node.freezed = boolean(freezed, "freezed")? && node.position.is_some();
+        }
+        if let Some(fill) = value.field("fill") {
+            node.fill = color(fill, "fill")?;
+        }
+        if let Some(stroke) = value.field("stroke") {
+            node.stroke = color(stroke, "stroke")?;
+        }
+    }
+    for value in array("links")? {
+        let from = value.required("from")?.text("from")?;
+        let to = value.required("to")?.text("to")?;
+        let directed = match value.field("directed") {
+            Some(directed) => boolean(directed, "directed")?,
+            None => false,
+        };
+        let mut link = LinkEntry::new(&from, &to, !directed, value.at);
+        if let Some(v) = value
+            .field("value")
+            .filter(|v| !matches!(v.kind, JsonKind::Null))
+        {
+            link.value = Some(v.text("value")?);
+        }
+        if let Some(stroke) = value.field("color") {
+            link.stroke = color(stroke, "color")?;
+        }
+        builder.link(link);
+    }
+    Ok(builder.document)
+}
+
+/// Writes the document as a CSV edge list `from,to,value,directed`, with a header line.
+///
+/// Nodes without links are written with empty `to`, `value` and `directed` fields. Positions and colors are not written.
+pub fn write_csv(document: &Document) -> String {
+    let mut csv = String::from("from,to,value,directed\n");
+    for link in &document.links {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            csv_field(&link.from),
+            csv_field(&link.to),
+            csv_field(link.value.as_deref().unwrap_or("")),
+            !link.bidirect
+        ));
+    }
+    for node in &document.nodes {
+        let linked = document
+            .links
+            .iter()
+            .any(|link| link.from == node.name || link.to == node.name);
+        if !linked {
+            csv.push_str(&format!("{},,,\n", csv_field(&node.name)));
+        }
+    }
+    csv
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) || text.trim() != text {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Reads a CSV edge list `from,to[,value[,directed]]` (see `write_csv`).
+///
+/// A header line is skipped when its first field is `from` or `source`. Links are bidirectional unless `directed` is true.
+/// Records with an empty `to` field declare nodes.
+pub fn read_csv(text: &str) -> Result<Document, ParseFailure> {
+    let mut cursor = Cursor::new(text);
+    let mut builder = Builder::default();
+    let mut first = true;
+    while cursor.peek().is_some() {
+        // record fields, with their positions
+        let mut fields: Vec<(String, At)> = Vec::new();
+        loop {
+            let at = cursor.at();
+            let mut field = String::new();
+            if cursor.eat("\"") {
+                loop {
+                    match cursor.next() {
+                        Some('"') if cursor.eat("\"") => field.push('"'),
+                        Some('"') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(failure(at, "field is not closed".to_string())),
+                    }
+                }
+                while cursor.peek().is_some_and(|c| c != ',' && c != '\n') {
+                    cursor.next();
+                }
+            } else {
+                while let Some(c) = cursor.peek().filter(|c| *c != ',' && *c != '\n') {
+                    field.push(c);
+                    cursor.next();
+                }
+                field = field.trim().to_string();
+            }
+            fields.push((field, at));
+            if !cursor.eat(",") {
+                cursor.next();
+                break;
+            }
+        }
+
+        let header = first && ["from", "source"].contains(&fields[0].0.to_lowercase().as_str());
+        first = false;
+        if header || fields.iter().all(|(field, _)| field.is_empty()) {
+            continue;
+        }
+        let field = |k: usize| fields.get(k).map_or("", |(field, _)| field.as_str());
+        let (from, at) = &fields[0];
+        if from.is_empty() {
+            return Err(failure(*at, "field 'from' is empty".to_string()));
+        }
+        if field(1).is_empty() {
+            builder.node(from, *at);
+            continue;
+        }
+        let directed = match fields.get(3) {
+            Some((value, at)) => bool_from(value).map_err(|message| failure(*at, message))?,
+            None => false,
+        };
+        let mut link = LinkEntry::new(from, field(1), !directed, *at);
+        if !field(2).is_empty() {
+            link.value = Some(field(2).to_string());
+        }
+        builder.link(link);
+    }
+    Ok(builder.document)
+}
@@ -0,0 +1,1159 @@
+//! Layout algorithms computing the nodes positions in the SVG graphic context (see `Graph::set_layout`).
+//!
+//! Layouts only move the nodes which are not freezed (see `Graph::move_node`, `Graph::unfreeze_node`).
+
+use super::NodeId;
+use std::cmp::max;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+
+/// Position and size of a node, as given to layout algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    pub x: i32,
+    pub y: i32,
+    /// Freezed nodes keep their position.
+    pub freezed: bool,
+    /// The node has a position from a previous layout, used as initial position (see `Graph::incremental_layout`).
+    pub placed: bool,
+    pub radius: u8,
+}
+
+/// Algorithm placing the nodes of a graph.
+///
+/// # Example
+/// ```
+/// use dynalgo::graph::layout::{Layout, Placement};
+/// use dynalgo::graph::Graph;
+/// use std::collections::{BTreeMap, BTreeSet};
+///
+/// // nodes on a horizontal line
+/// struct Line;
+///
+/// impl Layout<char> for Line {
+///     fn arrange(
+///         &self,
+///         nodes: &BTreeMap<char, Placement>,
+///         _adjacency: &BTreeMap<char, BTreeSet<char>>,
+///         _seed: Option<u64>,
+///     ) -> BTreeMap<char, (i32, i32)> {
+///         nodes.keys().enumerate().map(|(i, node)| (*node, (60 * i as i32, 0))).collect()
+///     }
+/// }
+///
+/// let mut graph = Graph::new();
+/// graph.set_layout(Line);
+/// graph.from_str("A, B, C, A - B, B - C");
+///
+/// assert_eq!(graph.node_position('C'), (120, 0, false));
+/// ```
+pub trait Layout<N> {
+    /// Returns the centers of the nodes. Centers of freezed nodes are ignored.
+    ///
+    /// `adjacency` gives the successors of each node (bidirect links appear in both directions, loops are omitted).
+    /// `seed` asks for pseudo-random but reproducible initial positions (see `Graph::layout_seed`).
+    fn arrange(
+        &self,
+        nodes: &BTreeMap<N, Placement>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        seed: Option<u64>,
+    ) -> BTreeMap<N, (i32, i32)>;
+
+    /// Whether the layout starts from the previous positions of the nodes (see `Graph::incremental_layout`).
+    /// Layouts computing the positions from the graph structure only return `false`.
+    fn incremental(&self) -> bool {
+        true
+    }
+}
+
+/// Default layout: nodes are moved by imaginary springs forces (the spring length growing with the nodes degrees).
+///
+/// Beyond 100 nodes, the springs between unlinked nodes are approximated with a Barnes-Hut quadtree and the iterations are bounded,
+/// so that graphs of thousands of nodes are arranged in seconds.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Springs;
+
+/// Force-directed layout of Fruchterman and Reingold: linked nodes attract each other, all nodes repel each other.
+///
+/// Repulsions are approximated with a Barnes-Hut quadtree, so that graphs of thousands of nodes are arranged in seconds.
+#[derive(Debug, Clone, Copy)]
+pub struct FruchtermanReingold {
+    pub iterations: usize,
+    /// Barnes-Hut accuracy: a group of distant nodes repels as a single node when its cell size divided by its distance is below `theta`.
+    /// Zero computes the exact repulsions between all nodes pairs.
+    pub theta: f64,
+}
+
+/// Force-directed layout of Kamada and Kawai: the distance between two nodes tends to be proportional to their graph theoretic distance.
+#[derive(Debug, Clone, Copy)]
+pub struct KamadaKawai {
+    pub iterations: usize,
+}
+
+/// Nodes placed on a circle, in their identifiers order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Circular;
+
+/// Nodes placed on a grid, row by row in their identifiers order.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Grid {
+    /// Columns count (the nearest square grid if `None`).
+    pub columns: Option<usize>,
+}
+
+/// Layered layout of Sugiyama for directed graphs: links point downward (except links reversed to break cycles), and crossings are reduced.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sugiyama;
+
+impl Default for FruchtermanReingold {
+    fn default() -> FruchtermanReingold {
+        FruchtermanReingold {
+            iterations: 300,
+            theta: 0.8,
+        }
+    }
+}
+
+impl Default for KamadaKawai {
+    fn default() -> KamadaKawai {
+        KamadaKawai { iterations: 1000 }
+    }
+}
+
+// Nodes count above which the `Springs` layout approximates the forces of the unlinked nodes.
+const SPRINGS_EXACT_MAX: usize = 100;
+
+// Pseudo-random numbers generator (splitmix64), so that seeded layouts are reproducible.
+struct Random(u64);
+
+impl Random {
+    // Returns a number in [0, 1).
+    fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// Nodes indexed in their identifiers order, with the undirected links between them.
+struct Frame<N> {
+    names: Vec<N>,
+    freezed: Vec<bool>,
+    placed: Vec<bool>,
+    radius: Vec<f64>,
+    positions: Vec<(f64, f64)>,
+    links: Vec<(usize, usize)>,
+    spacing: f64,
+}
+
+impl<N: NodeId> Frame<N> {
+    fn new(nodes: &BTreeMap<N, Placement>, adjacency: &BTreeMap<N, BTreeSet<N>>) -> Frame<N> {
+        let names: Vec<N> = nodes.keys().cloned().collect();
+        let index: BTreeMap<&N, usize> = names.iter().enumerate().map(|(i, n)| (n, i)).collect();
+        let mut links = BTreeSet::new();
+        for (from, successors) in adjacency {
+            for to in successors {
+                let (i, j) = (index[from], index[to]);
+                if i != j {
+                    links.insert((i.min(j), i.max(j)));
+                }
+            }
+        }
+        let radius_max = nodes.values().map(|p| p.radius).max().unwrap_or(0);
+
+        Frame {
+            freezed: nodes.values().map(|p| p.freezed).collect(),
+            placed: nodes.values().map(|p| p.placed).collect(),
+            radius: nodes.values().map(|p| p.radius as f64).collect(),
+            positions: nodes.values().map(|p| (p.x as f64, p.y as f64)).collect(),
+            links: links.into_iter().collect(),
+            spacing: 5. * max(radius_max, 1) as f64,
+            names,
+        }
+    }
+
+    // Gives initial positions to the nodes which are neither freezed nor placed, as a starting point of force-directed layouts.
+    // Nodes linked to positioned nodes start next to them, others on a circle (or at random in a disc when seeded).
+    fn seed(&mut self, seed: Option<u64>) {
+        let fixed: Vec<bool> = (0..self.names.len())
+            .map(|i| self.freezed[i] || self.placed[i])
+            .collect();
+        let mut neighbors = vec![Vec::new(); self.names.len()];
+        for (i, j) in &self.links {
+            neighbors[*i].push(*j);
+            neighbors[*j].push(*i);
+        }
+        let mut random = seed.map(Random);
+        let count = fixed.iter().filter(|f| !**f).count();
+        let radius = (self.spacing * count as f64 / (2. * PI)).max(self.spacing);
+        let angle = 2. * PI / count as f64;
+        for (k, i) in (0..self.names.len()).filter(|i| !fixed[*i]).enumerate() {
+            let anchors: Vec<usize> = neighbors[i].iter().copied().filter(|j| fixed[*j]).collect();
+            let a = match &mut random {
+                Some(random) => 2. * PI * random.next(),
+                None => k as f64 * angle - PI / 2.,
+            };
+            self.positions[i] = if !anchors.is_empty() {
+                let x = anchors.iter().map(|j| self.positions[*j].0).sum::<f64>()
+                    / anchors.len() as f64;
+                let y = anchors.iter().map(|j| self.positions[*j].1).sum::<f64>()
+                    / anchors.len() as f64;
+                (x + self.spacing * a.cos(), y + self.spacing * a.sin())
+            } else if let Some(random) = &mut random {
+                let r = radius * random.next().sqrt();
+                (r * a.cos(), r * a.sin())
+            } else {
+                (radius * a.cos(), radius * a.sin())
+            };
+        }
+    }
+
+    // Pushes overlapping nodes apart. Nodes are bucketed in cells as large as the largest gap,
+    // so that only nodes of neighboring cells are compared.
+    fn separate(&mut self) {
+        let count = self.names.len();
+        let size = 3. * self.radius.iter().fold(1., |max: f64, r| max.max(*r));
+        for _ in 0..50 {
+            let mut buckets = Buckets::new(size);
+            for (i, position) in self.positions.iter().enumerate() {
+                buckets.insert(i, *position);
+            }
+            let mut overlap = false;
+            for i in 0..count {
+                let others: Vec<usize> = buckets
+                    .near(self.positions[i])
+                    .copied()
+                    .filter(|j| *j > i)
+                    .collect();
+                for j in others {
+                    overlap |= self.push_apart(i, j);
+                }
+            }
+            if !overlap {
+                break;
+            }
+        }
+    }
+
+    // Moves two overlapping nodes away from each other, returning whether they overlapped.
+    fn push_apart(&mut self, i: usize, j: usize) -> bool {
+        if self.freezed[i] && self.freezed[j] {
+            return false;
+        }
+        let gap = 1.5 * (self.radius[i] + self.radius[j]);
+        let (mut dx, mut dy) = (
+            self.positions[j].0 - self.positions[i].0,
+            self.positions[j].1 - self.positions[i].1,
+        );
+        let mut d = (dx * dx + dy * dy).sqrt();
+        if d >= gap {
+            return false;
+        }
+        if d < 0.01 {
+            // same centers: deterministic direction
+            let a = (i + j) as f64;
+            (dx, dy, d) = (a.cos(), a.sin(), 1.);
+        }
+        let push = (gap - d) / d;
+        let (share_i, share_j) = match (self.freezed[i], self.freezed[j]) {
+            (true, _) => (0., 1.),
+            (_, true) => (1., 0.),
+            _ => (0.5, 0.5),
+        };
+        self.positions[i].0 -= dx * push * share_i;
+        self.positions[i].1 -= dy * push * share_i;
+        self.positions[j].0 += dx * push * share_j;
+        self.positions[j].1 += dy * push * share_j;
+        true
+    }
+
+    fn centers(&self) -> BTreeMap<N, (i32, i32)> {
+        self.names
+            .iter()
+            .zip(&self.positions)
+            .map(|(name, (x, y))| (name.clone(), (x.round() as i32, y.round() as i32)))
+            .collect()
+    }
+
+    // Shortest paths lengths (links count), unreachable nodes being one link farther than the farthest reachable one.
+    fn distances(&self) -> Vec<Vec<f64>> {
+        let count = self.names.len();
+        let mut neighbors = vec![Vec::new(); count];
+        for (i, j) in &self.links {
+            neighbors[*i].push(*j);
+            neighbors[*j].push(*i);
+        }
+        let mut distances = vec![vec![usize::MAX; count]; count];
+        for (source, row) in distances.iter_mut().enumerate() {
+            row[source] = 0;
+            let mut queue = std::collections::VecDeque::from([source]);
+            while let Some(node) = queue.pop_front() {
+                for next in &neighbors[node] {
+                    if row[*next] == usize::MAX {
+                        row[*next] = row[node] + 1;
+                        queue.push_back(*next);
+                    }
+                }
+            }
+        }
+        let farthest = distances
+            .iter()
+            .flatten()
+            .filter(|d| **d != usize::MAX)
+            .max()
+            .copied()
+            .unwrap_or(0);
+        distances
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|d| match d {
+                        usize::MAX => (farthest + 1) as f64,
+                        d => d as f64,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+// Nodes bucketed in a grid of square cells: nodes closer than the cell size to a position are in the 9 cells around it.
+struct Buckets<K> {
+    size: f64,
+    cells: HashMap<(i64, i64), Vec<K>>,
+}
+
+impl<K: PartialEq> Buckets<K> {
+    fn new(size: f64) -> Buckets<K> {
+        Buckets {
+            size,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, (x, y): (f64, f64)) -> (i64, i64) {
+        (
+            (x / self.size).floor() as i64,
+            (y / self.size).floor() as i64,
+        )
+    }
+
+    fn insert(&mut self, key: K, position: (f64, f64)) {
+        let cell = self.cell(position);
+        self.cells.entry(cell).or_default().push(key);
+    }
+
+    fn remove(&mut self, key: &K, position: (f64, f64)) {
+        let cell = self.cell(position);
+        if let Some(keys) = self.cells.get_mut(&cell) {
+            keys.retain(|k| k != key);
+        }
+    }
+
+    // Keys of the cell of the position and of its neighboring cells.
+    fn near(&self, position: (f64, f64)) -> impl Iterator<Item = &K> {
+        let (x, y) = self.cell(position);
+        [-1, 0, 1]
+            .into_iter()
+            .flat_map(move |dx| [-1, 0, 1].map(|dy| (x + dx, y + dy)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+    }
+}
+
+// Quadtree of the nodes positions, each cell knowing the count and the mass center of its nodes (Barnes-Hut approximation).
+struct QuadTree {
+    cells: Vec<Cell>,
+}
+
+struct Cell {
+    center: (f64, f64),
+    half_size: f64,
+    count: usize,
+    mass: (f64, f64),
+    node: Option<usize>,
+    children: Option<[usize; 4]>,
+}
+
+impl Cell {
+    fn new(center: (f64, f64), half_size: f64) -> Cell {
+        Cell {
+            center,
+            half_size,
+            count: 0,
+            mass: (0., 0.),
+            node: None,
+            children: None,
+        }
+    }
+
+    fn quadrant(&self, (x, y): (f64, f64)) -> usize {
+        (x >= self.center.0) as usize + 2 * (y >= self.center.1) as usize
+    }
+}
+
+impl QuadTree {
+    fn new(positions: &[(f64, f64)]) -> QuadTree {
+        let (mut x_min, mut x_max, mut y_min, mut y_max) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+        for (x, y) in positions {
+            (x_min, x_max) = (x_min.min(*x), x_max.max(*x));
+            (y_min, y_max) = (y_min.min(*y), y_max.max(*y));
+        }
+        let half_size = ((x_max - x_min).max(y_max - y_min) / 2.).max(1.);
+        let center = ((x_min + x_max) / 2., (y_min + y_max) / 2.);
+        let mut tree = QuadTree {
+            cells: vec![Cell::new(center, half_size)],
+        };
+        for (i, position) in positions.iter().enumerate() {
+            tree.insert(i, *position, positions);
+        }
+        tree
+    }
+
+    fn insert(&mut self, node: usize, position: (f64, f64), positions: &[(f64, f64)]) {
+        let mut cell = 0;
+        // nodes sharing the same position end in the same deepest cell
+        for depth in 0.. {
+            let current = &mut self.cells[cell];
+            current.count += 1;
+            current.mass.0 += position.0;
+            current.mass.1 += position.1;
+            if let Some(children) = current.children {
+                cell = children[current.quadrant(position)];
+                continue;
+            }
+            match current.node {
+                None if current.count == 1 => current.node = Some(node),
+                Some(other) if depth < 32 => {
+                    let (center, half) = (current.center, current.half_size / 2.);
+                    let first = self.cells.len();
+                    for quadrant in 0..4 {
+                        let x = center.0 + if quadrant % 2 == 1 { half } else { -half };
+                        let y = center.1 + if quadrant / 2 == 1 { half } else { -half };
+                        self.cells.push(Cell::new((x, y), half));
+                    }
+                    let children = [first, first + 1, first + 2, first + 3];
+                    let current = &mut self.cells[cell];
+                    current.node = None;
+                    current.children = Some(children);
+                    let next = children[current.quadrant(position)];
+                    let moved = children[current.quadrant(positions[other])];
+                    let moved = &mut self.cells[moved];
+                    moved.count = 1;
+                    moved.mass = positions[other];
+                    moved.node = Some(other);
+                    cell = next;
+                    continue;
+                }
+                _ => {}
+            }
+            break;
+        }
+    }
+
+    // Sum of the repulsions applying to the node (intensity `strength` / distance^`power`).
+    fn repulsion(
+        &self,
+        node: usize,
+        (x, y): (f64, f64),
+        strength: f64,
+        power: i32,
+        theta: f64,
+    ) -> (f64, f64) {
+        let mut force = (0., 0.);
+        let mut stack = vec![0];
+        while let Some(cell) = stack.pop() {
+            let cell = &self.cells[cell];
+            if cell.count == 0 || cell.node == Some(node) {
+                continue;
+            }
+            let count = cell.count as f64;
+            let (dx, dy) = (x - cell.mass.0 / count, y - cell.mass.1 / count);
+            let d = (dx * dx + dy * dy).sqrt();
+            // a cell containing the node is never approximated
+            let inside = (x - cell.center.0).abs() <= cell.half_size
+                && (y - cell.center.1).abs() <= cell.half_size;
+            match cell.children {
+                Some(children) if inside || 2. * cell.half_size >= theta * d => {
+                    stack.extend(children)
+                }
+                _ => {
+                    let d = d.max(0.01);
+                    force.0 += dx / d * strength * count / d.powi(power);
+                    force.1 += dy / d * strength * count / d.powi(power);
+                }
+            }
+        }
+        force
+    }
+}
+
+impl<N: NodeId> Layout<N> for Springs {
+    fn arrange(
+        &self,
+        nodes: &BTreeMap<N, Placement>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        seed: Option<u64>,
+    ) -> BTreeMap<N, (i32, i32)> {
+        let mut adja: BTreeMap<N, BTreeSet<N>> = adjacency.clone();
+        for (node_from, neighbors) in adjacency {
+            for node_to in neighbors {
+                adja.get_mut(node_to).unwrap().insert(node_from.clone());
+            }
+        }
+
+        if adja.is_empty() {
+            return BTreeMap::new();
+        }
+
+        let distance = |x1: i64, y1: i64, x2: i64, y2: i64| {
+            (((x2 - x1).pow(2) + (y2 - y1).pow(2)) as f64).sqrt() as u64
+        };
+        // nodes may have distinct radii: two nodes collide when their centers are closer than the sum of their radii
+        let radii: BTreeMap<N, u64> = nodes
+            .iter()
+            .map(|(name, node)| (name.clone(), node.radius as u64))
+            .collect();
+        let gap = |node: &N, other: &N| radii[node] + radii[other];
+        // colliding nodes are in neighboring buckets
+        let bucket_size = (2 * radii.values().max().copied().unwrap_or(0) + 1) as f64;
+        let collides = |buckets: &Buckets<N>,
+                        positions: &BTreeMap<N, (bool, i64, i64)>,
+                        node: &N,
+                        x: i64,
+                        y: i64| {
+            buckets.near((x as f64, y as f64)).any(|other| {
+                let (_, o_x, o_y) = positions[other];
+                other != node && distance(x, y, o_x, o_y) <= gap(node, other)
+            })
+        };
+
+        // nodes keeping their position as initial state (incremental layout), unless they collide with another node
+        let mut kept: BTreeSet<N> = BTreeSet::new();
+        let mut anchors = Buckets::new(bucket_size);
+        for (node, placement) in nodes.iter().filter(|(_, placement)| placement.freezed) {
+            anchors.insert(node.clone(), (placement.x as f64, placement.y as f64));
+        }
+        for (node, placement) in nodes {
+            if !placement.placed || placement.freezed {
+                continue;
+            }
+            let (x, y) = (placement.x as i64, placement.y as i64);
+            let collision = anchors.near((x as f64, y as f64)).any(|other| {
+                let o = &nodes[other];
+                other != node && distance(x, y, o.x as i64, o.y as i64) < gap(node, other)
+            });
+            if !collision {
+                kept.insert(node.clone());
+                anchors.insert(node.clone(), (x as f64, y as f64));
+            }
+        }
+
+        let mut avg_x: i64 = 0;
+        let mut avg_y: i64 = 0;
+        let mut freezed_count = 0;
+        let mut anchors_count = 0;
+        let mut radius_min = 0;
+        let mut forces = BTreeMap::new();
+        let mut positions = BTreeMap::new();
+        for (node, placement) in nodes {
+            let freezed = placement.freezed;
+            positions.insert(
+                node.clone(),
+                (freezed, placement.x as i64, placement.y as i64),
+            );
+            if freezed || kept.contains(node) {
+                avg_x += placement.x as i64;
+                avg_y += placement.y as i64;
+                anchors_count += 1;
+            }
+            if freezed {
+                freezed_count += 1;
+                continue;
+            }
+            forces.insert(node.clone(), (0, 0));
+        }
+
+        if freezed_count > 0 && adja.keys().len() == freezed_count {
+            return BTreeMap::new();
+        }
+        if anchors_count > 0 {
+            avg_x /= anchors_count as i64;
+            avg_y /= anchors_count as i64;
+            for (node, (freezed, x, y)) in &positions {
+                if *freezed || kept.contains(node) {
+                    radius_min = max(radius_min, distance(*x, *y, avg_x, avg_y));
+                }
+            }
+        }
+
+        // other nodes are put on a circle around the anchored ones (in a shuffled order when seeded)
+        let circled = positions.keys().len() - anchors_count;
+        let diameter = 2 * radii.values().max().copied().unwrap_or(0) as u32;
+        let perimeter = (diameter * circled as u32 * 2) as f64;
+        let mut radius = perimeter / (2. * PI);
+        radius = max(radius as u32, radius_min as u32 + 2 * diameter) as f64;
+        let angle = 2. * PI / circled as f64;
+        let mut slots: Vec<usize> = (0..circled).collect();
+        if let Some(seed) = seed {
+            let mut random = Random(seed);
+            for i in (1..slots.len()).rev() {
+                slots.swap(i, (random.next() * (i + 1) as f64) as usize);
+            }
+        }
+
+        let mut i = 0;
+        for (node, (freezed, x, y)) in positions.iter_mut() {
+            if *freezed || kept.contains(node) {
+                continue;
+            }
+            let slot = slots[i] as f64;
+            *x = (radius * (slot * angle).cos() + avg_x as f64) as i64;
+            *y = (radius * (slot * angle).sin() + avg_y as f64) as i64;
+            i += 1;
+        }
+
+        let mut buckets = Buckets::new(bucket_size);
+        for (node, (_, x, y)) in &positions {
+            buckets.insert(node.clone(), (*x as f64, *y as f64));
+        }
+
+        let links_count: usize = adja.values().map(|n| n.len()).sum::<usize>() / 2;
+
+        let mut density = links_count / adja.len();
+        density = max(1, density);
+        let radius_unit = radii.values().min().copied().unwrap_or(0) as u32;
+        let length_unit = radius_unit * density as u32;
+        let k_unit = 0.01;
+        // every pair of unlinked nodes is a long and weak spring
+        let (length_far, k_far) = (
+            max(24, adja.len() / 2) as i64 * length_unit as i64,
+            5. * k_unit,
+        );
+        let spring = |node: &N, other: &N| {
+            if adja[node].contains(other) {
+                match max(adja[node].len(), adja[other].len()) {
+                    1 => (5 * length_unit as i64, 30. * k_unit),
+                    2 => (6 * length_unit as i64, 25. * k_unit),
+                    3 => (7 * length_unit as i64, 20. * k_unit),
+                    4 => (8 * length_unit as i64, 15. * k_unit),
+                    _ => (9 * length_unit as i64, 10. * k_unit),
+                }
+            } else {
+                (length_far, k_far)
+            }
+        };
+        let index: BTreeMap<N, usize> = positions
+            .keys()
+            .enumerate()
+            .map(|(i, node)| (node.clone(), i))
+            .collect();
+
+        let mut not_moved = Vec::with_capacity(adja.len());
+        for (node, neighbors) in &adja {
+            if !kept.contains(node) {
+                not_moved.push((node.clone(), neighbors.len()));
+            }
+        }
+        not_moved.sort_by(|(_, l1), (_, l2)| l2.cmp(l1));
+        let mut not_moved: Vec<N> = not_moved.into_iter().map(|(n, _)| n).collect();
+        let mut stalled: BTreeSet<N> = not_moved.iter().cloned().collect();
+
+        // with many nodes, the forces of the unlinked nodes are approximated with a Barnes-Hut quadtree,
+        // and the iterations are bounded
+        let approximated = positions.len() > SPRINGS_EXACT_MAX;
+        let iterations = match approximated {
+            true => 10 * SPRINGS_EXACT_MAX,
+            false => 10 * adja.len(),
+        };
+        for i in 0..iterations {
+            if i % adja.len() == 0 && !not_moved.is_empty() {
+                for node in &not_moved {
+                    let (freezed, n_x, n_y) = positions[node];
+                    if freezed {
+                        continue;
+                    }
+                    let neighbors = adja.get(node).unwrap();
+                    if neighbors.is_empty() {
+                        continue;
+                    }
+
+                    let mut try_x: i64 = 0;
+                    let mut try_y: i64 = 0;
+                    if neighbors.len() == 1 {
+                        let other = neighbors.iter().next().unwrap();
+                        let (_, o_x, o_y) = positions.get(other).unwrap();
+                        let dist = distance(*o_x, *o_y, 0, 0);
+                        let length = 1.1 * gap(node, other) as f64;
+                        try_x = o_x + (length * *o_x as f64 / dist as f64) as i64;
+                        try_y = o_y + (length * *o_y as f64 / dist as f64) as i64;
+                    } else {
+                        for other in neighbors {
+                            let (_, o_x, o_y) = positions.get(other).unwrap();
+                            try_x += o_x;
+                            try_y += o_y;
+                        }
+                        try_x = 10 + try_x / neighbors.len() as i64;
+                        try_y = 10 + try_y / neighbors.len() as i64;
+                    }
+
+                    if !collides(&buckets, &positions, node, try_x, try_y) {
+                        buckets.remove(node, (n_x as f64, n_y as f64));
+                        buckets.insert(node.clone(), (try_x as f64, try_y as f64));
+                        let (_, n_x, n_y) = positions.get_mut(node).unwrap();
+                        *n_x = try_x;
+                        *n_y = try_y;
+                    }
+                }
+                not_moved.clear();
+                stalled.clear();
+            }
+
+            let points: Vec<(f64, f64)> = positions
+                .values()
+                .map(|(_, x, y)| (*x as f64, *y as f64))
+                .collect();
+            let tree = approximated.then(|| QuadTree::new(&points));
+            let sum = points
+                .iter()
+                .fold((0., 0.), |(s_x, s_y), (x, y)| (s_x + x, s_y + y));
+
+            let mut max_f = 0;
+            for (node, (n_freezed, n_x, n_y)) in &positions {
+                if *n_freezed {
+                    continue;
+                }
+
+                let mut sum_f_x = 0;
+                let mut sum_f_y = 0;
+                if let Some(tree) = &tree {
+                    // unlinked springs pull toward each node (sum of positions) and push away at a constant intensity
+                    let (x, y) = (*n_x as f64, *n_y as f64);
+                    let count = points.len() as f64;
+                    let strength = k_far * length_far as f64;
+                    let repulsion = tree.repulsion(index[node], (x, y), strength, 0, 0.8);
+                    let mut f_x = k_far * (sum.0 - count * x) + repulsion.0;
+                    let mut f_y = k_far * (sum.1 - count * y) + repulsion.1;
+                    for other in &adja[node] {
+                        let (_, o_x, o_y) = positions[other];
+                        let (d_x, d_y) = (o_x as f64 - x, o_y as f64 - y);
+                        let dist = (d_x * d_x + d_y * d_y).sqrt().max(0.01);
+                        let (spring_length, k) = spring(node, other);
+                        let force =
+                            (dist - spring_length as f64) * k - (dist - length_far as f64) * k_far;
+                        f_x += force * d_x / dist;
+                        f_y += force * d_y / dist;
+                    }
+                    sum_f_x = f_x as i64;
+                    sum_f_y = f_y as i64;
+                } else {
+                    for (other, (_, o_x, o_y)) in &positions {
+                        if *other == *node {
+                            continue;
+                        }
+
+                        let dist = distance(*n_x, *n_y, *o_x, *o_y);
+                        assert!(dist >= gap(node, other));
+
+                        let (d_x, d_y) = (o_x - n_x, o_y - n_y);
+                        let (spring_length, k) = spring(node, other);
+                        let force = (dist as i64 - spring_length) as f64 * k;
+                        let f_x = (force * (d_x as f64 / dist as f64)) as i64;
+                        let f_y = (force * (d_y as f64 / dist as f64)) as i64;
+                        sum_f_x += f_x;
+                        sum_f_y += f_y;
+                    }
+                }
+
+                let f = distance(sum_f_x, sum_f_y, 0, 0) as i64;
+                max_f = max(max_f, f);
+
+                let (f_x, f_y) = forces.get_mut(node).unwrap();
+                *f_x = sum_f_x;
+                *f_y = sum_f_y;
+            }
+
+            let maxi = 6 * radius as i64 / adja.len() as i64;
+            if max_f > maxi {
+                let reduce = maxi as f64 / max_f as f64;
+                for (_, (f_x, f_y)) in forces.iter_mut() {
+                    *f_x = (*f_x as f64 * reduce) as i64;
+                    *f_y = (*f_y as f64 * reduce) as i64;
+                }
+            }
+
+            for (node, (f_x, f_y)) in &forces {
+                let f = distance(*f_x, *f_y, 0, 0);
+                if f <= length_unit as u64 / 10 {
+                    continue;
+                };
+                let (freezed, n_x, n_y) = positions[node];
+                if freezed {
+                    continue;
+                }
+                let mut new_x = n_x;
+                let mut new_y = n_y;
+                for m in (0..=4).rev() {
+                    let try_x = n_x + ((f * m / 4) as f64 * (*f_x as f64 / f as f64)) as i64;
+                    let try_y = n_y + ((f * m / 4) as f64 * (*f_y as f64 / f as f64)) as i64;
+                    if collides(&buckets, &positions, node, try_x, try_y) {
+                        assert!(m != 0);
+                    } else {
+                        new_x = try_x;
+                        new_y = try_y;
+                        break;
+                    }
+                }
+                if n_x != new_x || n_y != new_y {
+                    buckets.remove(node, (n_x as f64, n_y as f64));
+                    buckets.insert(node.clone(), (new_x as f64, new_y as f64));
+                    let (_, n_x, n_y) = positions.get_mut(node).unwrap();
+                    *n_x = new_x;
+                    *n_y = new_y;
+                } else if stalled.insert(node.clone()) {
+                    not_moved.push(node.clone());
+                }
+            }
+
+            if not_moved.len() == adja.len() {
+                break;
+            }
+        }
+
+        positions
+            .into_iter()
+            .filter(|(_, (freezed, _, _))| !freezed)
+            .map(|(node, (_, x, y))| (node, (x as i32, y as i32)))
+            .collect()
+    }
+}
+
+impl<N: NodeId> Layout<N> for FruchtermanReingold {
+    fn arrange(
+        &self,
+        nodes: &BTreeMap<N, Placement>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        seed: Option<u64>,
+    ) -> BTreeMap<N, (i32, i32)> {
+        let mut frame = Frame::new(nodes, adjacency);
+        frame.seed(seed);
+        let count = frame.names.len();
+        let k = frame.spacing;
+        let mut temperature = k * (count as f64).sqrt();
+        let cooling = temperature / (self.iterations + 1) as f64;
+
+        for _ in 0..self.iterations {
+            let mut moves = vec![(0., 0.); count];
+            let delta = |positions: &[(f64, f64)], i: usize, j: usize| {
+                let (dx, dy) = (
+                    positions[i].0 - positions[j].0,
+                    positions[i].1 - positions[j].1,
+                );
+                (dx, dy, (dx * dx + dy * dy).sqrt().max(0.01))
+            };
+            let tree = QuadTree::new(&frame.positions);
+            for (i, position) in frame.positions.iter().enumerate() {
+                if !frame.freezed[i] {
+                    moves[i] = tree.repulsion(i, *position, k * k, 1, self.theta);
+                }
+            }
+            for (i, j) in &frame.links {
+                let (dx, dy, d) = delta(&frame.positions, *i, *j);
+                let attraction = d * d / k;
+                moves[*i].0 -= dx / d * attraction;
+                moves[*i].1 -= dy / d * attraction;
+                moves[*j].0 += dx / d * attraction;
+                moves[*j].1 += dy / d * attraction;
+            }
+            for (i, (mx, my)) in moves.into_iter().enumerate() {
+                if frame.freezed[i] {
+                    continue;
+                }
+                let length = (mx * mx + my * my).sqrt();
+                if length > 0. {
+                    let step = length.min(temperature);
+                    frame.positions[i].0 += mx / length * step;
+                    frame.positions[i].1 += my / length * step;
+                }
+            }
+            temperature -= cooling;
+        }
+
+        frame.separate();
+        frame.centers()
+    }
+}
+
+impl<N: NodeId> Layout<N> for KamadaKawai {
+    fn arrange(
+        &self,
+        nodes: &BTreeMap<N, Placement>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        seed: Option<u64>,
+    ) -> BTreeMap<N, (i32, i32)> {
+        let mut frame = Frame::new(nodes, adjacency);
+        frame.seed(seed);
+        let count = frame.names.len();
+        let distances = frame.distances();
+        let unit = frame.spacing;
+
+        // energy gradient and hessian for the node m
+        let derivatives = |positions: &[(f64, f64)], m: usize| {
+            let (mut ex, mut ey, mut exx, mut exy, mut eyy) = (0., 0., 0., 0., 0.);
+            for i in 0..count {
+                if i == m {
+                    continue;
+                }
+                let (dx, dy) = (
+                    positions[m].0 - positions[i].0,
+                    positions[m].1 - positions[i].1,
+                );
+                let d = (dx * dx + dy * dy).sqrt().max(0.01);
+                let (k, l) = (1. / distances[m][i].powi(2), unit * distances[m][i]);
+                ex += k * (dx - l * dx / d);
+                ey += k * (dy - l * dy / d);
+                exx += k * (1. - l * dy * dy / d.powi(3));
+                exy += k * l * dx * dy / d.powi(3);
+                eyy += k * (1. - l * dx * dx / d.powi(3));
+            }
+            (ex, ey, exx, exy, eyy)
+        };
+
+        for _ in 0..self.iterations {
+            let mut selected = None;
+            let mut delta_max = 0.01;
+            for m in (0..count).filter(|m| !frame.freezed[*m]) {
+                let (ex, ey, _, _, _) = derivatives(&frame.positions, m);
+                let delta = (ex * ex + ey * ey).sqrt();
+                if delta > delta_max {
+                    (selected, delta_max) = (Some(m), delta);
+                }
+            }
+            let m = match selected {
+                Some(m) => m,
+                None => break,
+            };
+
+            let (ex, ey, exx, exy, eyy) = derivatives(&frame.positions, m);
+            let determinant = exx * eyy - exy * exy;
+            if determinant.abs() < 1e-9 {
+                break;
+            }
+            frame.positions[m].0 += (-ex * eyy + ey * exy) / determinant;
+            frame.positions[m].1 += (-ey * exx + ex * exy) / determinant;
+        }
+
+        frame.separate();
+        frame.centers()
+    }
+}
+
+impl<N: NodeId> Layout<N> for Circular {
+    fn incremental(&self) -> bool {
+        false
+    }
+
+    fn arrange(
+        &self,
+        nodes: &BTreeMap<N, Placement>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        _seed: Option<u64>,
+    ) -> BTreeMap<N, (i32, i32)> {
+        let mut frame = Frame::new(nodes, adjacency);
+        let count = frame.names.len();
+        if count > 1 {
+            let radius = (frame.spacing * count as f64 / (2. * PI)).max(frame.spacing);
+            for (i, position) in frame.positions.iter_mut().enumerate() {
+                let angle = 2. * PI * i as f64 / count as f64 - PI / 2.;
+                *position = (radius * angle.cos(), radius * angle.sin());
+            }
+        }
+        frame.centers()
+    }
+}
+
+impl<N: NodeId> Layout<N> for Grid {
+    fn incremental(&self) -> bool {
+        false
+    }
+
+    fn arrange(
+        &self,
+        nodes: &BTreeMap<N, Placement>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        _seed: Option<u64>,
+    ) -> BTreeMap<N, (i32, i32)> {
+        let mut frame = Frame::new(nodes, adjacency);
+        let count = frame.names.len();
+        let columns = match self.columns {
+            Some(columns) => columns,
+            None => (count as f64).sqrt().ceil() as usize,
+        }
+        .max(1);
+        let rows = count.div_ceil(columns);
+        for (i, position) in frame.positions.iter_mut().enumerate() {
+            let (column, row) = ((i % columns) as f64, (i / columns) as f64);
+            *position = (
+                (column - (columns - 1) as f64 / 2.) * frame.spacing,
+                (row - (rows.max(1) - 1) as f64 / 2.) * frame.spacing,
+            );
+        }
+        frame.centers()
+    }
+}
+
+impl<N: NodeId> Layout<N> for Sugiyama {
+    fn incremental(&self) -> bool {
+        false
+    }
+
+    fn arrange(
+        &self,
+        nodes: &BTreeMap<N, Placement>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        _seed: Option<u64>,
+    ) -> BTreeMap<N, (i32, i32)> {
+        let mut frame = Frame::new(nodes, adjacency);
+        let count = frame.names.len();
+        let index: BTreeMap<&N, usize> = frame
+            .names
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (n, i))
+            .collect();
+
+        // directed links (a single one for links in both directions)
+        let mut successors = vec![BTreeSet::new(); count];
+        for (from, tos) in adjacency {
+            for to in tos {
+                let (i, j) = (index[from], index[to]);
+                if i != j && !successors[j].contains(&i) {
+                    successors[i].insert(j);
+                }
+            }
+        }
+
+        // cycles removal: links closing a cycle of the depth-first search are reversed
+        let mut state = vec![0; count];
+        let mut reversed = Vec::new();
+        for root in 0..count {
+            if state[root] != 0 {
+                continue;
+            }
+            state[root] = 1;
+            let mut stack = vec![(
+                root,
+                successors[root].iter().rev().copied().collect::<Vec<_>>(),
+            )];
+            while let Some((node, pending)) = stack.last_mut() {
+                let node = *node;
+                match pending.pop() {
+                    Some(next) if state[next] == 1 => reversed.push((node, next)),
+                    Some(next) if state[next] == 0 => {
+                        state[next] = 1;
+                        stack.push((next, successors[next].iter().rev().copied().collect()));
+                    }
+                    Some(_) => {}
+                    None => {
+                        state[node] = 2;
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        for (from, to) in reversed {
+            successors[from].remove(&to);
+            successors[to].insert(from);
+        }
+
+        // layers by longest path from the sources
+        let mut indegree: Vec<usize> = vec![0; count];
+        for tos in &successors {
+            for to in tos {
+                indegree[*to] += 1;
+            }
+        }
+        let mut layer = vec![0; count];
+        let mut ready: Vec<usize> = (0..count).filter(|i| indegree[*i] == 0).collect();
+        while let Some(node) = ready.pop() {
+            for to in &successors[node] {
+                layer[*to] = max(layer[*to], layer[node] + 1);
+                indegree[*to] -= 1;
+                if indegree[*to] == 0 {
+                    ready.push(*to);
+                }
+            }
+        }
+
+        // long links are split by dummy nodes, one per crossed layer
+        let mut layers: Vec<Vec<usize>> = vec![Vec::new(); layer.iter().max().map_or(0, |l| l + 1)];
+        let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); count];
+        let mut followers: Vec<Vec<usize>> = vec![Vec::new(); count];
+        for node in 0..count {
+            layers[layer[node]].push(node);
+        }
+        for from in 0..count {
+            for to in successors[from].clone() {
+                let mut previous = from;
+                for crossed in &mut layers[(layer[from] + 1)..layer[to]] {
+                    let dummy = predecessors.len();
+                    predecessors.push(Vec::new());
+                    followers.push(Vec::new());
+                    crossed.push(dummy);
+                    predecessors[dummy].push(previous);
+                    followers[previous].push(dummy);
+                    previous = dummy;
+                }
+                predecessors[to].push(previous);
+                followers[previous].push(to);
+            }
+        }
+
+        // crossings reduction: nodes sorted by the barycenter of their neighbors in the previous layer, sweeping down and up
+        let mut order = vec![0.; predecessors.len()];
+        for nodes in &layers {
+            for (i, node) in nodes.iter().enumerate() {
+                order[*node] = i as f64;
+            }
+        }
+        for sweep in 0..8 {
+            let (range, neighbors): (Vec<usize>, _) = match sweep % 2 {
+                0 => ((1..layers.len()).collect(), &predecessors),
+                _ => (
+                    (0..layers.len().saturating_sub(1)).rev().collect(),
+                    &followers,
+                ),
+            };
+            for l in range {
+                let barycenter = |node: &usize| match neighbors[*node].len() {
+                    0 => order[*node],
+                    n => neighbors[*node].iter().map(|o| order[*o]).sum::<f64>() / n as f64,
+                };
+                let mut keys: Vec<(f64, usize)> =
+                    layers[l].iter().map(|n| (barycenter(n), *n)).collect();
+                keys.sort_by(|a, b| a.partial_cmp(b).unwrap());
+                layers[l] = keys.into_iter().map(|(_, n)| n).collect();
+                for (i, node) in layers[l].iter().enumerate() {
+                    order[*node] = i as f64;
+                }
+            }
+        }
+
+        let depth = layers.len() as f64;
+        for (l, nodes) in layers.iter().enumerate() {
+            let width = nodes.len() as f64;
+            for (i, node) in nodes.iter().enumerate().filter(|(_, n)| **n < count) {
+                frame.positions[*node] = (
+                    (i as f64 - (width - 1.) / 2.) * frame.spacing,
+                    (l as f64 - (depth - 1.) / 2.) * frame.spacing,
+                );
+            }
+        }
+        frame.centers()
+    }
+}
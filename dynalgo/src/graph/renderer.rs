@@ -1,34 +1,60 @@
+mod badge;
+mod caption;
 pub mod color;
+mod font;
+mod gif;
 pub mod html;
 mod link;
 mod node;
+mod png;
 pub mod point;
+mod raster;
+mod script;
 mod svg;
 mod tag;
 
+use super::layout::{Layout, Placement};
+use super::AnimationFormat;
+use super::NodeId;
+use super::{Shape, Style};
+use badge::Badge;
+use caption::Caption;
 use color::Color;
-use link::Link;
-use node::Node;
+use gif::Gif;
+use link::{Link, LinkStyle};
+use node::{Node, NodeStyle};
+use png::Png;
 use point::Point;
+use raster::{Canvas, Raster};
+use script::Script;
 use std::cmp::max;
 use std::cmp::min;
 use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 
-use std::f64::consts::PI;
 use std::sync::Mutex;
 use svg::Svg;
 use tag::Tag;
 
 static SEQ: Mutex<u32> = Mutex::new(0);
 
-pub struct Renderer {
-    nodes: BTreeMap<char, Node>,
-    links: BTreeMap<(char, char), Link>,
-    previous_nodes: BTreeMap<char, Node>,
-    previous_links: BTreeMap<(char, char), Link>,
-    initial_nodes: BTreeMap<char, Node>,
-    initial_links: BTreeMap<(char, char), Link>,
+// (x min, x max, y min, y max) of nodes centers, extended by the nodes exceeding the default radius.
+type Bounds = (i32, i32, i32, i32);
+
+// (start, duration, from, to) of the captions overlay, following the viewbox.
+type Move = (u32, u32, Point, Point);
+
+// (from, to, sequence) of a link: parallel links between the same nodes have distinct sequences.
+type LinkKey<N> = (N, N, u32);
+
+pub struct Renderer<N> {
+    nodes: BTreeMap<N, Node<N>>,
+    links: BTreeMap<LinkKey<N>, Link<N>>,
+    previous_nodes: BTreeMap<N, Node<N>>,
+    previous_links: BTreeMap<LinkKey<N>, Link<N>>,
+    initial_nodes: BTreeMap<N, Node<N>>,
+    initial_links: BTreeMap<LinkKey<N>, Link<N>>,
     animation: String,
     svg: Svg,
     pub p_color_node_fill: Color,
@@ -36,32 +62,42 @@ pub struct Renderer {
     pub p_color_link_stroke: Color,
     pub p_color_node_text: Color,
     pub p_color_link_text: Color,
+    pub p_color_background: Color,
+    p_shape_node: Shape,
+    p_shapes_node: BTreeMap<N, Shape>,
     total_duration: u32,
-    svg_first_lign: String,
+    viewbox_init: Option<(i32, i32, i32, i32)>,
+    timeline: Vec<Keyframe<N>>,
+    captions: Vec<Caption>,
+    // nodes never moved by a layout (nor placed by the user), and nodes whose links changed since the last layout
+    unplaced: BTreeSet<N>,
+    touched: BTreeSet<N>,
 }
 
-impl Renderer {
-    pub fn new(
-        show_value: bool,
-        show_name: bool,
-        p_color_node_fill: Color,
-        p_color_node_stroke: Color,
-        p_color_link_stroke: Color,
-        p_color_node_text: Color,
-        p_color_link_text: Color,
-        p_radius_node: u8,
-    ) -> Renderer {
+// Step of the animation: only the elements changed by the step are recorded, as (name, previous, current).
+struct Keyframe<N> {
+    start: u32,
+    duration: u32,
+    viewbox: (Bounds, Bounds),
+    nodes: Vec<(N, Node<N>, Node<N>)>,
+    links: Vec<(LinkKey<N>, Link<N>, Link<N>)>,
+}
+
+impl<N: NodeId> Renderer<N> {
+    pub fn new(show_value: bool, show_name: bool, style: &Style<N>) -> Renderer<N> {
         let p_display_node_label = show_name;
         let p_display_link_value = show_value;
-        let p_stroke_width_node = 2;
-        let p_stroke_width_link = 2;
-        let svg = Svg::new(
+        let color = |(r, g, b)| Color::new(r, g, b);
+        let mut svg = Svg::new(
             p_display_node_label,
             p_display_link_value,
-            p_stroke_width_node,
-            p_stroke_width_link,
-            p_radius_node,
+            style.node_stroke_width,
+            style.link_stroke_width,
+            style.node_radius,
         );
+        svg.p_font_family = style.font_family.clone();
+        svg.p_font_size = style.font_size;
+        svg.p_color_background = color(style.background);
         Renderer {
             nodes: BTreeMap::new(),
             links: BTreeMap::new(),
@@ -71,13 +107,20 @@ impl Renderer {
             initial_links: BTreeMap::new(),
             animation: String::new(),
             svg,
-            p_color_node_fill,
-            p_color_node_stroke,
-            p_color_link_stroke,
-            p_color_node_text,
-            p_color_link_text,
+            p_color_node_fill: color(style.node_fill),
+            p_color_node_stroke: color(style.node_stroke),
+            p_color_link_stroke: color(style.link_stroke),
+            p_color_node_text: color(style.node_text),
+            p_color_link_text: color(style.link_text),
+            p_color_background: color(style.background),
+            p_shape_node: style.node_shape,
+            p_shapes_node: style.node_shapes.clone(),
             total_duration: 0,
-            svg_first_lign: String::new(),
+            viewbox_init: None,
+            timeline: Vec::new(),
+            captions: Vec::new(),
+            unplaced: BTreeSet::new(),
+            touched: BTreeSet::new(),
         }
     }
 
@@ -91,6 +134,34 @@ impl Renderer {
         self.svg.p_display_node_label = !hide;
     }
 
+    pub fn settings_from(&mut self, other: &Renderer<N>) {
+        self.svg.p_display_node_label = other.svg.p_display_node_label;
+        self.svg.p_pause_on_click = other.svg.p_pause_on_click;
+        self.svg.p_curved_links = other.svg.p_curved_links;
+    }
+
+    pub fn pause_on_click(&mut self, enabled: bool) {
+        self.svg.p_pause_on_click = enabled;
+    }
+
+    pub fn paused_on_click(&self) -> bool {
+        self.svg.p_pause_on_click
+    }
+
+    pub fn caption(&mut self, text: &str) {
+        let id = self.id_seq();
+        self.captions
+            .push(Caption::new(id, text.to_string(), self.total_duration));
+    }
+
+    /// Captions texts with their start times (in ms), in chronological order.
+    pub fn captions(&self) -> Vec<(u32, &str)> {
+        self.captions
+            .iter()
+            .map(|caption| (caption.start(), caption.text()))
+            .collect()
+    }
+
     pub fn sleep(&mut self, duration: u32) {
         self.total_duration += duration;
     }
@@ -99,432 +170,356 @@ impl Renderer {
         self.total_duration
     }
 
-    pub fn add_node(&mut self, name: char, center: Option<Point>) {
-        let mut node = Node::new(
-            self.id_seq(),
-            name,
-            center,
-            self.svg.p_radius_node,
-            self.p_color_node_fill,
-            self.p_color_node_stroke,
-            self.p_color_node_text,
-            self.svg.p_stroke_width_node,
-        );
+    /// Start times (in ms) of the animation steps, in chronological order.
+    pub fn step_starts(&self) -> Vec<u32> {
+        self.timeline
+            .iter()
+            .map(|keyframe| keyframe.start)
+            .collect()
+    }
+
+    pub fn add_node(&mut self, name: N, center: Option<Point>) {
+        let style = NodeStyle {
+            radius: self.svg.p_radius_node,
+            fill_color: self.p_color_node_fill,
+            stroke_color: self.p_color_node_stroke,
+            text_color: self.p_color_node_text,
+            stroke_width: self.svg.p_stroke_width_node,
+        };
+        let mut node = Node::new(self.id_seq(), name.clone(), center, style);
         node.tag(Some(Tag::Created));
+        let shape = self.p_shapes_node.get(&name);
+        node.set_shape(*shape.unwrap_or(&self.p_shape_node));
 
-        self.nodes.insert(node.name(), node.clone());
-        self.previous_nodes.insert(node.name(), node.clone());
-        self.initial_nodes.insert(node.name(), node);
+        let instance = self.svg.instantiate_node(&node);
+        self.animation.push_str(&instance);
 
-        self.animation.push_str(&self.svg.instantiate_node(&node));
+        if center.is_none() {
+            self.unplaced.insert(name.clone());
+        }
+        self.touched.insert(name.clone());
+        self.nodes.insert(name.clone(), node.clone());
+        self.previous_nodes.insert(name.clone(), node.clone());
+        self.initial_nodes.insert(name, node);
     }
 
-    pub fn delete_node(&mut self, name: char) {
-        self.nodes.get_mut(&name).unwrap().tag(Some(Tag::Deleted));
+    pub fn delete_node(&mut self, name: &N) {
+        self.nodes.get_mut(name).unwrap().tag(Some(Tag::Deleted));
     }
 
-    pub fn add_link(&mut self, from: char, to: char, bidirect: bool, value: i8) {
+    pub fn add_link(&mut self, from: &N, to: &N, bidirect: bool, value: String) {
+        self.touched.extend([from.clone(), to.clone()]);
         let id_seq = self.id_seq();
-        let from = self.nodes.get(&from).unwrap();
-        let to = self.nodes.get(&to).unwrap();
+        let from_center = *self.nodes[from].center();
+        let to_center = *self.nodes[to].center();
 
+        let style = LinkStyle {
+            stroke_color: self.p_color_link_stroke,
+            text_color: self.p_color_link_text,
+            stroke_width: self.svg.p_stroke_width_link,
+        };
         let mut link = Link::new(
             id_seq,
-            from.name(),
-            to.name(),
-            from.center().clone(),
-            to.center().clone(),
+            from.clone(),
+            to.clone(),
+            (from_center, to_center),
             bidirect,
             value,
-            self.p_color_link_stroke,
-            self.p_color_link_text,
-            self.svg.p_stroke_width_link,
+            style,
         );
         link.tag(Some(Tag::Created));
+        link.set_looped(from == to);
+        link.update_from_radius(self.nodes[from].radius() as i32);
+        link.update_to_radius(self.nodes[to].radius() as i32);
 
-        self.links.insert((from.name(), to.name()), link.clone());
-        self.previous_links
-            .insert((from.name(), to.name()), link.clone());
-        self.initial_links.insert((from.name(), to.name()), link);
+        let name = (from.clone(), to.clone(), id_seq);
+        self.links.insert(name.clone(), link);
+        self.bend_links(from, to);
+        let link = self.links[&name].clone();
 
-        self.animation
-            .insert_str(0, &self.svg.instantiate_link(&link));
-    }
+        let instance = self.svg.instantiate_link(&link);
+        self.animation.insert_str(0, &instance);
 
-    pub fn delete_link(&mut self, from: char, to: char) {
-        let link = match self.links.get_mut(&(from, to)) {
-            None => self.links.get_mut(&(to, from)).unwrap(),
-            Some(l) => l,
-        };
-        link.tag(Some(Tag::Deleted));
+        self.previous_links.insert(name.clone(), link.clone());
+        self.initial_links.insert(name, link);
     }
 
-    pub fn layout(&mut self, adja: BTreeMap<char, BTreeMap<char, i8>>) {
-        assert!(adja.len() == self.nodes.len());
-
-        if adja.is_empty() {
-            return;
-        }
+    pub fn delete_link(&mut self, from: &N, to: &N) {
+        self.touched.extend([from.clone(), to.clone()]);
+        self.link_mut(from, to).tag(Some(Tag::Deleted));
+        self.bend_links(from, to);
+    }
 
-        let distance = |x1: i64, y1: i64, x2: i64, y2: i64| {
-            (((x2 - x1).pow(2) + (y2 - y1).pow(2)) as f64).sqrt() as u64
-        };
+    /// Oldest link from `name_1` to `name_2` (or between them if bidirectional), the next parallel link being used once it is deleted.
+    fn link_mut(&mut self, name_1: &N, name_2: &N) -> &mut Link<N> {
+        let name = self.links_between(name_1, name_2).next().unwrap();
+        self.links.get_mut(&name).unwrap()
+    }
 
-        let mut avg_x: i64 = 0;
-        let mut avg_y: i64 = 0;
-        let mut freezed_count = 0;
-        let mut radius_min = 0;
-        let mut forces = BTreeMap::new();
-        let mut positions = BTreeMap::new();
-        for node in adja.keys() {
-            let xy = self.node_center(*node);
-            let freezed = self.node_center_freezed(*node);
-            positions.insert(*node, (freezed, xy.x() as i64, xy.y() as i64));
-            if freezed {
-                avg_x += xy.x() as i64;
-                avg_y += xy.y() as i64;
-                freezed_count += 1;
-                continue;
-            }
-            forces.insert(*node, (0, 0));
-        }
+    /// Links from `name_1` to `name_2` (or between them if bidirectional), oldest first.
+    fn links_between(&self, name_1: &N, name_2: &N) -> impl Iterator<Item = LinkKey<N>> {
+        let mut names: Vec<LinkKey<N>> = self
+            .links
+            .iter()
+            .filter(|((from, to, _), link)| {
+                !link.tag_deleted()
+                    && (from == name_1 && to == name_2
+                        || link.bidirect() && from == name_2 && to == name_1)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort_by_key(|(_, _, seq)| *seq);
+        names.into_iter()
+    }
 
-        if freezed_count > 0 {
-            if adja.keys().len() == freezed_count {
-                return;
-            }
-            avg_x = avg_x / freezed_count as i64;
-            avg_y = avg_y / freezed_count as i64;
-            for (_, (freezed, x, y)) in &positions {
-                if *freezed {
-                    radius_min = max(radius_min, distance(*x, *y, avg_x, avg_y));
-                }
-            }
+    /// Spreads the links between two nodes: parallel and antiparallel links are curved on both sides, loops are nested circles.
+    fn bend_links(&mut self, name_1: &N, name_2: &N) {
+        let mut names: Vec<LinkKey<N>> = self
+            .links
+            .iter()
+            .filter(|((from, to, _), link)| {
+                !link.tag_deleted()
+                    && (from == name_1 && to == name_2 || from == name_2 && to == name_1)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort_by_key(|(_, _, seq)| *seq);
+
+        let radius = self.svg.p_radius_node as i32;
+        // loops circles must be larger than the half radius of their node
+        let loop_radius = max(radius, self.nodes[name_1].radius() as i32);
+        let count = names.len() as i32;
+        for (i, name) in names.iter().enumerate() {
+            let i = i as i32;
+            let link = self.links.get_mut(name).unwrap();
+            let bend = match (link.looped(), link.from() == min(name_1, name_2)) {
+                (true, _) => loop_radius * (2 + i) / 2,
+                (false, true) => (2 * i - (count - 1)) * radius,
+                (false, false) => (count - 1 - 2 * i) * radius,
+            };
+            let bend = match bend == 0 && self.svg.p_curved_links {
+                true => radius,
+                false => bend,
+            };
+            link.set_bend(bend);
         }
+    }
 
-        let diameter = 2 * self.svg.p_radius_node;
-        let perimeter =
-            (diameter as u32 * (positions.keys().len() - freezed_count) as u32 * 2) as f64;
-        let mut radius = (perimeter / (2. * PI)) as f64;
-        radius = max(radius as u32, radius_min as u32 + 2 * diameter as u32) as f64;
-        let angle = 2. * PI / (positions.keys().len() - freezed_count) as f64;
+    /// Curves (or straightens) the single links between nodes, returning whether a link changed.
+    pub fn curve_links(&mut self, enabled: bool) -> bool {
+        self.svg.p_curved_links = enabled;
 
-        let mut i = 0;
-        for (_, (freezed, x, y)) in positions.iter_mut() {
-            if *freezed {
-                continue;
-            }
-            *x = (radius * (i as f64 * angle).cos() + avg_x as f64) as i64;
-            *y = (radius * (i as f64 * angle).sin() + avg_y as f64) as i64;
-            i += 1;
+        let paths: Vec<Vec<i32>> = self.links.values().map(|link| link.path()).collect();
+        let names: Vec<LinkKey<N>> = self.links.keys().cloned().collect();
+        for (from, to, _) in names {
+            self.bend_links(&from, &to);
         }
+        self.links
+            .values()
+            .map(|link| link.path())
+            .collect::<Vec<_>>()
+            != paths
+    }
 
-        let links_count: usize = adja
-            .iter()
-            .map(|(_, n)| n.len())
-            .collect::<Vec<usize>>()
+    /// Moves the nodes which are not freezed to the centers computed by the layout algorithm.
+    /// An incremental layout only moves the nodes whose links changed since the last layout (and the new nodes),
+    /// starting from the current positions.
+    pub fn layout(
+        &mut self,
+        layout: &dyn Layout<N>,
+        adjacency: &BTreeMap<N, BTreeSet<N>>,
+        incremental: bool,
+        seed: Option<u64>,
+    ) {
+        let incremental = incremental && layout.incremental();
+        let nodes: BTreeMap<N, Placement> = self
+            .nodes
             .iter()
-            .sum::<usize>()
-            / 2;
-
-        let mut density = links_count / adja.len();
-        density = max(1, density);
-        let mut springs = HashMap::new();
-        let length_unit = self.svg.p_radius_node as u32 * density as u32;
-        let k_unit = 0.01;
-        for node in positions.keys() {
-            for other in positions.keys() {
-                if *other == *node {
-                    continue;
-                }
-
-                let (length, k) = if adja.get(node).unwrap().get(other).is_some()
-                //    || adja.get(other).unwrap().get(node).is_some()
-                {
-                    match max(
-                        adja.get(node).unwrap().len(),
-                        adja.get(other).unwrap().len(),
-                    ) {
-                        n if n == 1 => (5 * length_unit as i64, 30. * k_unit),
-                        n if n == 2 => (6 * length_unit as i64, 25. * k_unit),
-                        n if n == 3 => (7 * length_unit as i64, 20. * k_unit),
-                        n if n == 4 => (8 * length_unit as i64, 15. * k_unit),
-                        _ => (9 * length_unit as i64, 10. * k_unit),
-                    }
-                } else {
-                    (
-                        max(24, adja.len() / 2) as i64 * length_unit as i64,
-                        5. * k_unit,
-                    )
+            .filter(|(_, node)| !node.tag_deleted())
+            .map(|(name, node)| {
+                let placed = incremental && !self.unplaced.contains(name);
+                let pinned = placed && !self.touched.contains(name);
+                let placement = Placement {
+                    x: node.center().x(),
+                    y: node.center().y(),
+                    freezed: node.center_freezed() || pinned,
+                    radius: node.radius(),
+                    placed,
                 };
-                springs.insert((*node, *other), (length, k));
-            }
-        }
-
-        let mut not_moved = Vec::with_capacity(adja.len());
-        for (node, neighbors) in &adja {
-            not_moved.push((*node, neighbors.len()));
-        }
-        not_moved.sort_by(|(_, l1), (_, l2)| l2.cmp(l1));
-        let mut not_moved: Vec<char> = not_moved.into_iter().map(|(n, _)| n).collect();
-
-        for i in 0..(10 * adja.len()) {
-            if i % adja.len() == 0 && not_moved.len() > 0 {
-                for node in &not_moved {
-                    let (freezed, _, _) = positions.get(node).unwrap();
-                    if *freezed {
-                        continue;
-                    }
-                    let neighbors = adja.get(&node).unwrap();
-                    if neighbors.len() < 1 {
-                        continue;
-                    }
-
-                    let mut try_x: i64 = 0;
-                    let mut try_y: i64 = 0;
-                    if neighbors.len() == 1 {
-                        let (other, _) = neighbors.iter().next().unwrap();
-                        let (_, o_x, o_y) = positions.get(&other).unwrap();
-                        let dist = distance(*o_x, *o_y, 0, 0);
-                        try_x = o_x + (1.1 * diameter as f64 * *o_x as f64 / dist as f64) as i64;
-                        try_y = o_y + (1.1 * diameter as f64 * *o_y as f64 / dist as f64) as i64;
-                    } else {
-                        for (other, _) in neighbors {
-                            let (_, o_x, o_y) = positions.get(&other).unwrap();
-                            try_x += o_x;
-                            try_y += o_y;
-                        }
-                        try_x = 10 + try_x / neighbors.len() as i64;
-                        try_y = 10 + try_y / neighbors.len() as i64;
-                    }
-
-                    let mut collision = false;
-                    for (other, (_, o_x, o_y)) in &positions {
-                        if *other == *node {
-                            continue;
-                        }
-                        collision = distance(try_x, try_y, *o_x, *o_y) <= diameter as u64;
-                        if collision {
-                            break;
-                        }
-                    }
-                    if !collision {
-                        let (_, n_x, n_y) = positions.get_mut(node).unwrap();
-                        *n_x = try_x;
-                        *n_y = try_y;
-                    }
-                }
-                not_moved.clear();
-            }
-
-            let mut max_f = 0;
-            for (node, (n_freezed, n_x, n_y)) in &positions {
-                if *n_freezed {
-                    continue;
-                }
-
-                let mut sum_f_x = 0;
-                let mut sum_f_y = 0;
-                for (other, (_, o_x, o_y)) in &positions {
-                    if *other == *node {
-                        continue;
-                    }
-
-                    let dist = distance(*n_x, *n_y, *o_x, *o_y);
-                    assert!(dist >= diameter as u64);
-
-                    let (d_x, d_y) = (o_x - n_x, o_y - n_y);
-                    let (spring_length, k) = springs.get(&(*node, *other)).unwrap();
-                    let force = (dist as i64 - spring_length) as f64 * k;
-                    let f_x = (force * (d_x as f64 / dist as f64)) as i64;
-                    let f_y = (force * (d_y as f64 / dist as f64)) as i64;
-                    sum_f_x += f_x;
-                    sum_f_y += f_y;
-                }
-
-                let f = distance(sum_f_x, sum_f_y, 0, 0) as i64;
-                max_f = max(max_f, f);
-
-                let (f_x, f_y) = forces.get_mut(node).unwrap();
-                *f_x = sum_f_x;
-                *f_y = sum_f_y;
-            }
-
-            let maxi = 6 * radius as i64 / adja.len() as i64;
-            if max_f > maxi {
-                let reduce = maxi as f64 / max_f as f64;
-                for (_, (f_x, f_y)) in forces.iter_mut() {
-                    *f_x = (*f_x as f64 * reduce) as i64;
-                    *f_y = (*f_y as f64 * reduce) as i64;
-                }
-            }
-
-            for (node, (f_x, f_y)) in &forces {
-                let f = distance(*f_x, *f_y, 0, 0);
-                if f <= length_unit as u64 / 10 {
-                    continue;
-                };
-                let (freezed, n_x, n_y) = positions.get(node).unwrap();
-                if *freezed {
-                    continue;
-                }
-                let mut new_x = *n_x;
-                let mut new_y = *n_y;
-                for m in (0..=4).rev() {
-                    let try_x = *n_x + ((f * m / 4) as f64 * (*f_x as f64 / f as f64)) as i64;
-                    let try_y = *n_y + ((f * m / 4) as f64 * (*f_y as f64 / f as f64)) as i64;
-                    let mut collision = false;
-                    for (other, (_, o_x, o_y)) in &positions {
-                        if *other == *node {
-                            continue;
-                        }
-                        collision = distance(try_x, try_y, *o_x, *o_y) <= diameter as u64;
-                        if collision {
-                            assert!(m != 0);
-                            break;
-                        }
-                    }
-                    if !collision {
-                        new_x = try_x;
-                        new_y = try_y;
-                        break;
-                    }
-                }
-                if *n_x != new_x || *n_y != new_y {
-                    let (_, n_x, n_y) = positions.get_mut(node).unwrap();
-                    *n_x = new_x;
-                    *n_y = new_y;
-                } else {
-                    if !not_moved.contains(node) {
-                        not_moved.push(*node);
-                    }
-                }
-            }
-
-            if not_moved.len() == adja.len() {
-                break;
-            }
-        }
-
-        for (node, (freezed, n_x, n_y)) in &positions {
-            if !freezed {
-                self.node_move(*node, Point::new((*n_x) as i32, (*n_y) as i32), false);
+                (name.clone(), placement)
+            })
+            .collect();
+        assert!(adjacency.len() == nodes.len());
+        self.unplaced.clear();
+        self.touched.clear();
+
+        for (node, (x, y)) in layout.arrange(&nodes, adjacency, seed) {
+            if !nodes[&node].freezed {
+                self.node_move(&node, Point::new(x, y), false);
             }
         }
     }
 
-    pub fn node_move(&mut self, name: char, center: Point, freezed: bool) {
-        let node = self.nodes.get_mut(&name).unwrap();
+    pub fn node_move(&mut self, name: &N, center: Point, freezed: bool) {
+        let node = self.nodes.get_mut(name).unwrap();
 
         let center_curr = node.center();
         if center_curr.x() == center.x() && center_curr.y() == center.y() {
+            self.node_freezed(name, freezed);
             return;
         }
 
         node.move_to(center);
 
-        for (_, link) in &mut self.links {
-            if link.from() == node.name() {
-                link.update_from_center(node.center().clone());
+        for link in self.links.values_mut() {
+            if link.from() == name {
+                link.update_from_center(center);
             }
-            if link.to() == node.name() {
-                link.update_to_center(node.center().clone());
+            if link.to() == name {
+                link.update_to_center(center);
             }
         }
 
         self.node_freezed(name, freezed);
     }
 
-    pub fn node_freezed(&mut self, name: char, freezed: bool) {
-        self.nodes.get_mut(&name).unwrap().center_freeze(freezed);
+    pub fn node_freezed(&mut self, name: &N, freezed: bool) {
+        let node = self.nodes.get_mut(name).unwrap();
+        if node.center_freezed() && !freezed {
+            self.touched.insert(name.clone());
+        }
+        node.center_freeze(freezed);
     }
 
-    pub fn node_fill_color(&mut self, name: char, (red, green, blue): (u8, u8, u8)) {
+    pub fn node_fill_color(&mut self, name: &N, (red, green, blue): (u8, u8, u8)) {
         self.nodes
-            .get_mut(&name)
+            .get_mut(name)
             .unwrap()
             .set_fill_color(Color::new(red, green, blue));
     }
 
-    pub fn node_text_color(&mut self, name: char, (red, green, blue): (u8, u8, u8)) {
+    pub fn node_text_color(&mut self, name: &N, (red, green, blue): (u8, u8, u8)) {
         self.nodes
-            .get_mut(&name)
+            .get_mut(name)
             .unwrap()
             .set_text_color(Color::new(red, green, blue));
     }
 
-    pub fn link_text_color(
-        &mut self,
-        name_1: char,
-        name_2: char,
-        (red, green, blue): (u8, u8, u8),
-    ) {
-        let link = match self.links.get_mut(&(name_1, name_2)) {
-            None => self.links.get_mut(&(name_2, name_1)).unwrap(),
-            Some(l) => l,
-        };
+    pub fn link_text_color(&mut self, name_1: &N, name_2: &N, (red, green, blue): (u8, u8, u8)) {
+        self.link_mut(name_1, name_2)
+            .set_text_color(Color::new(red, green, blue));
+    }
 
-        link.set_text_color(Color::new(red, green, blue));
+    /// Changes the node radius, links being clipped at the new node border.
+    pub fn node_resize(&mut self, name: &N, radius: u8) {
+        self.nodes.get_mut(name).unwrap().set_radius(radius);
+
+        let mut looped = false;
+        for link in self.links.values_mut() {
+            if link.from() == name {
+                link.update_from_radius(radius as i32);
+            }
+            if link.to() == name {
+                link.update_to_radius(radius as i32);
+                looped |= link.looped();
+            }
+        }
+        if looped {
+            self.bend_links(name, name);
+        }
+    }
+
+    pub fn link_width(&mut self, name_1: &N, name_2: &N, width: u8) {
+        self.link_mut(name_1, name_2).set_stroke_width(width);
+    }
+
+    pub fn node_badge(&mut self, name: &N, text: String) {
+        self.nodes.get_mut(name).unwrap().set_badge(text);
     }
 
-    pub fn node_stroke_color(&mut self, name: char, (red, green, blue): (u8, u8, u8)) {
+    pub fn link_label(&mut self, name_1: &N, name_2: &N, text: String) {
+        self.link_mut(name_1, name_2).set_label(text);
+    }
+
+    pub fn link_value(&mut self, name_1: &N, name_2: &N, value: String) {
+        self.link_mut(name_1, name_2).set_value(value);
+    }
+
+    pub fn node_stroke_color(&mut self, name: &N, (red, green, blue): (u8, u8, u8)) {
         self.nodes
-            .get_mut(&name)
+            .get_mut(name)
             .unwrap()
             .set_stroke_color(Color::new(red, green, blue));
     }
 
-    pub fn link_stroke_color(
+    pub fn link_stroke_color(&mut self, name_1: &N, name_2: &N, (red, green, blue): (u8, u8, u8)) {
+        self.link_mut(name_1, name_2)
+            .set_stroke_color(Color::new(red, green, blue));
+    }
+
+    /// Colors the newest link from `name_1` to `name_2` (or between them if bidirectional).
+    pub fn last_link_stroke_color(
         &mut self,
-        name_1: char,
-        name_2: char,
+        name_1: &N,
+        name_2: &N,
         (red, green, blue): (u8, u8, u8),
     ) {
-        let link = match self.links.get_mut(&(name_1, name_2)) {
-            None => self.links.get_mut(&(name_2, name_1)).unwrap(),
-            Some(l) => l,
-        };
+        let name = self.links_between(name_1, name_2).last().unwrap();
+        self.links
+            .get_mut(&name)
+            .unwrap()
+            .set_stroke_color(Color::new(red, green, blue));
+    }
 
-        link.set_stroke_color(Color::new(red, green, blue));
+    pub fn node_center(&self, name: &N) -> &Point {
+        self.nodes[name].center()
     }
 
-    pub fn node_center(&self, name: char) -> &Point {
-        self.nodes.get(&name).unwrap().center()
+    pub fn node_center_freezed(&self, name: &N) -> bool {
+        self.nodes[name].center_freezed()
     }
 
-    pub fn node_center_freezed(&self, name: char) -> bool {
-        self.nodes.get(&name).unwrap().center_freezed()
+    /// Fill and stroke colors of a node.
+    pub fn node_colors(&self, name: &N) -> (Color, Color) {
+        let node = &self.nodes[name];
+        (node.fill_color(), node.stroke_color())
     }
 
-    fn animate_viewbox(&mut self, duration: u32) -> String {
+    /// Stroke colors of the links from `from` to `to`, in creation order.
+    pub fn link_colors(&self, from: &N, to: &N) -> Vec<Color> {
+        self.links_between(from, to)
+            .map(|name| self.links[&name].stroke_color())
+            .collect()
+    }
+
+    fn viewbox_bounds(&self) -> (Bounds, Bounds) {
+        let extra = |node: &Node<N>| max(0, node.radius() as i32 - self.svg.p_radius_node as i32);
         let mut x_min_curr = i32::MAX;
         let mut x_max_curr = i32::MIN;
         let mut y_min_curr = i32::MAX;
         let mut y_max_curr = i32::MIN;
-        for (_, node) in &self.previous_nodes {
-            if node.tag_created() {
-                if self.nodes.get(&node.name()).unwrap().tag_created() {
-                    continue;
-                }
+        for node in self.previous_nodes.values() {
+            if node.tag_created() && self.nodes[node.name()].tag_created() {
+                continue;
             }
-            x_min_curr = min(x_min_curr, node.center().x());
-            x_max_curr = max(x_max_curr, node.center().x());
-            y_min_curr = min(y_min_curr, node.center().y());
-            y_max_curr = max(y_max_curr, node.center().y());
+            x_min_curr = min(x_min_curr, node.center().x() - extra(node));
+            x_max_curr = max(x_max_curr, node.center().x() + extra(node));
+            y_min_curr = min(y_min_curr, node.center().y() - extra(node));
+            y_max_curr = max(y_max_curr, node.center().y() + extra(node));
         }
 
         let mut x_min_next = i32::MAX;
         let mut x_max_next = i32::MIN;
         let mut y_min_next = i32::MAX;
         let mut y_max_next = i32::MIN;
-        for (_, node) in &self.nodes {
+        for node in self.nodes.values() {
             if node.tag_deleted() {
                 continue;
             }
-            x_min_next = min(x_min_next, node.center().x());
-            x_max_next = max(x_max_next, node.center().x());
-            y_min_next = min(y_min_next, node.center().y());
-            y_max_next = max(y_max_next, node.center().y());
+            x_min_next = min(x_min_next, node.center().x() - extra(node));
+            x_max_next = max(x_max_next, node.center().x() + extra(node));
+            y_min_next = min(y_min_next, node.center().y() - extra(node));
+            y_max_next = max(y_max_next, node.center().y() + extra(node));
         }
 
         if x_min_next == i32::MAX {
@@ -541,40 +536,36 @@ impl Renderer {
             y_max_curr = y_max_next;
         }
 
-        if self.svg_first_lign.is_empty() {
-            self.svg_first_lign = self
-                .svg
-                .instanciate_viewbox(x_min_curr, x_max_curr, y_min_curr, y_max_curr);
+        (
+            (x_min_curr, x_max_curr, y_min_curr, y_max_curr),
+            (x_min_next, x_max_next, y_min_next, y_max_next),
+        )
+    }
+
+    fn animate_viewbox(&mut self, duration: u32, (curr, next): (Bounds, Bounds)) -> String {
+        if self.viewbox_init.is_none() {
+            self.viewbox_init = Some(curr);
 
             String::new()
         } else {
-            self.svg.animate_viewbox(
-                x_min_curr,
-                x_max_curr,
-                y_min_curr,
-                y_max_curr,
-                x_min_next,
-                x_max_next,
-                y_min_next,
-                y_max_next,
-                duration,
-                self.total_duration,
-            )
+            self.svg
+                .animate_viewbox(curr, next, duration, self.total_duration)
         }
     }
 
     pub fn animate(&mut self, duration: u32) {
         let mut svg = String::new();
 
-        svg.push_str(&self.animate_viewbox(duration));
+        let viewbox = self.viewbox_bounds();
+        svg.push_str(&self.animate_viewbox(duration, viewbox));
 
         for (name, current_link) in self.links.iter() {
             let initial_link = self.initial_links.get(name).unwrap();
             let previous_link = self.previous_links.get(name).unwrap();
             svg.push_str(&self.svg.animate_link(
-                &current_link,
-                &initial_link,
-                &previous_link,
+                current_link,
+                initial_link,
+                previous_link,
                 duration,
                 self.total_duration,
             ));
@@ -583,18 +574,43 @@ impl Renderer {
             let initial_node = self.initial_nodes.get(name).unwrap();
             let previous_node = self.previous_nodes.get(name).unwrap();
             svg.push_str(&self.svg.animate_node(
-                &current_node,
-                &initial_node,
-                &previous_node,
+                current_node,
+                initial_node,
+                previous_node,
                 duration,
                 self.total_duration,
             ));
         }
         self.animation.push_str(&svg);
 
+        let mut nodes = Vec::new();
+        for (name, node) in self.nodes.iter() {
+            let previous = self
+                .previous_nodes
+                .insert(name.clone(), node.clone())
+                .unwrap();
+            if node.tag_created() || previous != *node {
+                nodes.push((name.clone(), previous, node.clone()));
+            }
+        }
+        let mut links = Vec::new();
+        for (name, link) in self.links.iter() {
+            let previous = self
+                .previous_links
+                .insert(name.clone(), link.clone())
+                .unwrap();
+            if link.tag_created() || previous != *link {
+                links.push((name.clone(), previous, link.clone()));
+            }
+        }
+        self.timeline.push(Keyframe {
+            start: self.total_duration,
+            duration,
+            viewbox,
+            nodes,
+            links,
+        });
         self.total_duration += duration;
-        self.previous_nodes = self.nodes.clone();
-        self.previous_links = self.links.clone();
 
         // clean
         let mut untag_created = Vec::new();
@@ -636,17 +652,391 @@ impl Renderer {
         }
     }
 
-    pub fn animation(&self) -> String {
+    pub fn animation(&self, standalone: bool, format: AnimationFormat) -> String {
         let mut svg = String::new();
 
-        if self.svg_first_lign.is_empty() {
-            return svg;
-        }
+        let (x_min, x_max, y_min, y_max) = match self.viewbox_init {
+            Some(viewbox) => viewbox,
+            None => return svg,
+        };
 
-        svg.push_str(&self.svg_first_lign);
-        svg.push_str(&self.animation);
+        svg.push_str(
+            &self
+                .svg
+                .instanciate_viewbox(x_min, x_max, y_min, y_max, standalone, format),
+        );
+        let (origin, moves) = self.caption_moves();
+        let badges = self.badges();
+        match format {
+            AnimationFormat::Smil => {
+                svg.push_str(&self.animation);
+                for badge in &badges {
+                    svg.push_str(&self.svg.instantiate_badge(badge, true));
+                }
+                svg.push_str(&self.svg.instantiate_captions(
+                    &self.captions,
+                    origin,
+                    &moves,
+                    self.p_color_node_text,
+                    true,
+                ));
+            }
+            AnimationFormat::Script => {
+                svg.push_str(&self.elements());
+                for badge in &badges {
+                    svg.push_str(&self.svg.instantiate_badge(badge, false));
+                }
+                svg.push_str(&self.svg.instantiate_captions(
+                    &self.captions,
+                    origin,
+                    &moves,
+                    self.p_color_node_text,
+                    false,
+                ));
+                let mut tweens = self.tweens();
+                for badge in &badges {
+                    tweens.extend(Script::animate_badge(badge));
+                }
+                tweens.extend(Script::animate_captions(&self.captions, &moves));
+                svg.push_str(&Script::player(&tweens, self.svg.p_pause_on_click));
+            }
+        }
         svg.push_str("</svg>");
 
         svg
     }
+
+    /// Nodes and links as instantiated when added, links first (latest added first) then nodes, without SMIL animations.
+    fn elements(&self) -> String {
+        let mut links = BTreeMap::new();
+        let mut nodes = BTreeMap::new();
+        for keyframe in &self.timeline {
+            for (_, previous, _) in &keyframe.links {
+                links.entry(previous.seq()).or_insert(previous);
+            }
+            for (_, previous, _) in &keyframe.nodes {
+                nodes.entry(previous.seq()).or_insert(previous);
+            }
+        }
+        // elements added while paused are not in the timeline yet
+        for (name, link) in &self.links {
+            links
+                .entry(link.seq())
+                .or_insert(&self.previous_links[name]);
+        }
+        for (name, node) in &self.nodes {
+            nodes
+                .entry(node.seq())
+                .or_insert(&self.previous_nodes[name]);
+        }
+
+        let mut elements = String::new();
+        for link in links.values().rev() {
+            elements.push_str(&self.svg.instantiate_link(link));
+        }
+        for node in nodes.values() {
+            elements.push_str(&self.svg.instantiate_node(node));
+        }
+        elements
+    }
+
+    /// Script tweens of the timeline steps (the first step sets the initial viewbox).
+    fn tweens(&self) -> Vec<String> {
+        let mut tweens = Vec::new();
+        for (k, keyframe) in self.timeline.iter().enumerate() {
+            if k > 0 {
+                tweens.extend(Script::animate_viewbox(
+                    keyframe.viewbox.0,
+                    keyframe.viewbox.1,
+                    2 * self.svg.p_radius_node as i32,
+                    keyframe.duration,
+                    keyframe.start,
+                ));
+            }
+            for (_, previous, link) in &keyframe.links {
+                tweens.extend(Script::animate_link(
+                    link,
+                    previous,
+                    keyframe.duration,
+                    keyframe.start,
+                ));
+            }
+            for (_, previous, node) in &keyframe.nodes {
+                tweens.extend(Script::animate_node(
+                    node,
+                    previous,
+                    keyframe.duration,
+                    keyframe.start,
+                ));
+            }
+        }
+        tweens
+    }
+
+    /// Node badges and link labels along the timeline: a new badge replaces the previous one each time the text changes.
+    fn badges(&self) -> Vec<Badge> {
+        let mut badges = Vec::new();
+        let mut visible = HashMap::new();
+        for (k, keyframe) in self.timeline.iter().enumerate() {
+            let step = (keyframe.start, keyframe.duration);
+            for (_, previous, link) in &keyframe.links {
+                let label = (previous.label(), link.label(), link.text_color(), true);
+                let positions = (self.label_center(previous), self.label_center(link));
+                let deleted = link.tag_deleted();
+                let owner = (link.id(), format!("b{}_{}", link.id(), k));
+                Self::track(
+                    &mut badges,
+                    &mut visible,
+                    owner,
+                    step,
+                    label,
+                    positions,
+                    deleted,
+                );
+
+                // updated values replace the initial value label
+                if self.svg.p_display_link_value {
+                    let shown = |link: &Link<N>| match link.value_updated() {
+                        true => link.value().to_string(),
+                        false => String::new(),
+                    };
+                    let (shown_previous, shown_current) = (shown(previous), shown(link));
+                    let value = (
+                        &shown_previous[..],
+                        &shown_current[..],
+                        link.text_color(),
+                        false,
+                    );
+                    let positions = (previous.middle(), link.middle());
+                    let owner = (format!("v{}", link.id()), format!("bv{}_{}", link.id(), k));
+                    Self::track(
+                        &mut badges,
+                        &mut visible,
+                        owner,
+                        step,
+                        value,
+                        positions,
+                        deleted,
+                    );
+                }
+            }
+            for (_, previous, node) in &keyframe.nodes {
+                let badge = (previous.badge(), node.badge(), node.text_color(), true);
+                let positions = (self.badge_center(previous), self.badge_center(node));
+                let deleted = node.tag_deleted();
+                let owner = (node.id(), format!("b{}_{}", node.id(), k));
+                Self::track(
+                    &mut badges,
+                    &mut visible,
+                    owner,
+                    step,
+                    badge,
+                    positions,
+                    deleted,
+                );
+            }
+        }
+
+        badges
+    }
+
+    fn track(
+        badges: &mut Vec<Badge>,
+        visible: &mut HashMap<String, usize>,
+        (owner, id): (String, String),
+        (start, duration): (u32, u32),
+        (previous, current, color, small): (&str, &str, Color, bool),
+        (from, to): (Point, Point),
+        deleted: bool,
+    ) {
+        let changed = previous != current;
+        if let Some(i) = visible.get(&owner) {
+            badges[*i].move_to(start, duration, from, to);
+            if changed || deleted {
+                badges[*i].hide(start, duration);
+                visible.remove(&owner);
+            }
+        }
+        if changed && !deleted && !current.is_empty() {
+            let mut badge = Badge::new(
+                id,
+                current.to_string(),
+                color,
+                small,
+                from,
+                (start, duration),
+            );
+            badge.move_to(start, duration, from, to);
+            visible.insert(owner, badges.len());
+            badges.push(badge);
+        }
+    }
+
+    fn badge_center(&self, node: &Node<N>) -> Point {
+        let radius = node.radius() as i32;
+        Point::new(node.center().x() + radius, node.center().y() - radius)
+    }
+
+    fn label_center(&self, link: &Link<N>) -> Point {
+        let middle = link.middle();
+        Point::new(middle.x(), middle.y() - self.svg.p_radius_node as i32)
+    }
+
+    /// Position of the captions overlay (top center of the viewbox), at the beginning and along the viewbox animations.
+    fn caption_moves(&self) -> (Point, Vec<Move>) {
+        let origin = match self.viewbox_init {
+            Some(viewbox) => self.caption_center(viewbox),
+            None => Point::new(0, 0),
+        };
+        let moves = self
+            .timeline
+            .iter()
+            .skip(1)
+            .filter(|keyframe| keyframe.viewbox.0 != keyframe.viewbox.1)
+            .map(|keyframe| {
+                (
+                    keyframe.start,
+                    keyframe.duration,
+                    self.caption_center(keyframe.viewbox.0),
+                    self.caption_center(keyframe.viewbox.1),
+                )
+            })
+            .collect();
+
+        (origin, moves)
+    }
+
+    fn caption_center(&self, (x_min, x_max, y_min, _): Bounds) -> Point {
+        Point::new((x_min + x_max) / 2, y_min - self.svg.p_radius_node as i32)
+    }
+
+    pub fn png_frames(&self, fps: u32) -> Vec<Vec<u8>> {
+        let (width, height, frames) = self.frames(fps);
+        frames
+            .into_iter()
+            .map(|rgb| Png::encode(width, height, &rgb))
+            .collect()
+    }
+
+    pub fn gif(&self, fps: u32) -> Vec<u8> {
+        let (width, height, frames) = self.frames(fps);
+        // delays in hundredths of a second, following the frames timestamps so that rounding errors do not add up
+        let timestamp = |i: u64| (i * 200 + fps as u64) / (2 * fps as u64);
+
+        let mut gif_frames: Vec<(Vec<u8>, u16)> = Vec::new();
+        for (i, rgb) in frames.into_iter().enumerate() {
+            let delay = max(timestamp(i as u64 + 1) - timestamp(i as u64), 1) as u16;
+            match gif_frames.last_mut() {
+                // a frame longer than the GIF maximum delay is repeated
+                Some((last, last_delay)) if *last == rgb && *last_delay <= u16::MAX - delay => {
+                    *last_delay += delay
+                }
+                _ => gif_frames.push((rgb, delay)),
+            }
+        }
+
+        Gif::encode(width as u16, height as u16, &gif_frames)
+    }
+
+    fn frames(&self, fps: u32) -> (u32, u32, Vec<Vec<u8>>) {
+        let margin = 2 * self.svg.p_radius_node as i32;
+        let viewbox = |(x_min, x_max, y_min, y_max): (f64, f64, f64, f64)| {
+            (
+                x_min - margin as f64,
+                y_min - margin as f64,
+                x_max - x_min + 2. * margin as f64,
+                y_max - y_min + 2. * margin as f64,
+            )
+        };
+
+        let (mut width, mut height) = (0, 0);
+        for keyframe in &self.timeline {
+            for (x_min, x_max, y_min, y_max) in [keyframe.viewbox.0, keyframe.viewbox.1] {
+                width = max(width, x_max - x_min + 2 * margin);
+                height = max(height, y_max - y_min + 2 * margin);
+            }
+        }
+        let count = self.total_duration as u64 * fps as u64 / 1000 + 1;
+        if width == 0 || height == 0 {
+            let blank = Canvas::new(1, 1, (0., 0., 1., 1.), self.p_color_background).pixels();
+            return (1, 1, vec![blank; count as usize]);
+        }
+        let scale = (640. / max(width, height) as f64).clamp(0.5, 2.);
+        let width = (width as f64 * scale).round() as u32;
+        let height = (height as f64 * scale).round() as u32;
+
+        let raster = Raster::new(
+            self.svg.p_display_node_label,
+            self.svg.p_display_link_value,
+            self.svg.p_radius_node,
+        );
+        let mut frames = Vec::with_capacity(count as usize);
+        // elements at the current keyframe, rebuilt by applying the keyframes changes in a single forward walk
+        let mut nodes: BTreeMap<&N, &Node<N>> = BTreeMap::new();
+        let mut links: BTreeMap<&LinkKey<N>, &Link<N>> = BTreeMap::new();
+        let mut previous_nodes: BTreeMap<&N, &Node<N>> = BTreeMap::new();
+        let mut previous_links: BTreeMap<&LinkKey<N>, &Link<N>> = BTreeMap::new();
+        let mut step = 0;
+        for i in 0..count {
+            let time = (i * 1000 / fps as u64) as u32;
+            while step < self.timeline.len() && self.timeline[step].start <= time {
+                nodes.retain(|_, node| !node.tag_deleted());
+                links.retain(|_, link| !link.tag_deleted());
+                previous_nodes.clear();
+                previous_links.clear();
+                for (name, previous, node) in &self.timeline[step].nodes {
+                    nodes.insert(name, node);
+                    previous_nodes.insert(name, previous);
+                }
+                for (name, previous, link) in &self.timeline[step].links {
+                    links.insert(name, link);
+                    previous_links.insert(name, previous);
+                }
+                step += 1;
+            }
+            let keyframe = match step {
+                0 => {
+                    let (curr, _) = self.timeline[0].viewbox;
+                    let curr = (curr.0 as f64, curr.1 as f64, curr.2 as f64, curr.3 as f64);
+                    frames.push(
+                        Canvas::new(width, height, viewbox(curr), self.p_color_background).pixels(),
+                    );
+                    continue;
+                }
+                step => &self.timeline[step - 1],
+            };
+            let f = match keyframe.duration {
+                0 => 1.,
+                duration => ((time - keyframe.start) as f64 / duration as f64).min(1.),
+            };
+
+            let (curr, next) = keyframe.viewbox;
+            let mix = |a: i32, b: i32| a as f64 + (b - a) as f64 * f;
+            let bounds = (
+                mix(curr.0, next.0),
+                mix(curr.1, next.1),
+                mix(curr.2, next.2),
+                mix(curr.3, next.3),
+            );
+            let mut canvas = Canvas::new(width, height, viewbox(bounds), self.p_color_background);
+            for (name, link) in &links {
+                let previous = previous_links.get(name).unwrap_or(link);
+                raster.draw_link(&mut canvas, link, previous, f);
+            }
+            for (name, node) in &nodes {
+                let previous = previous_nodes.get(name).unwrap_or(node);
+                raster.draw_node(&mut canvas, node, previous, f);
+            }
+            if let Some(caption) = self.captions.iter().rev().find(|c| c.start() <= time) {
+                let center = (
+                    (bounds.0 + bounds.1) / 2.,
+                    bounds.2 - self.svg.p_radius_node as f64,
+                );
+                canvas.text(center, caption.text(), self.p_color_node_text, 1.);
+            }
+            frames.push(canvas.pixels());
+        }
+
+        (width, height, frames)
+    }
 }
@@ -0,0 +1,82 @@
+use super::color::Color;
+use super::point::Point;
+
+/// A text shown next to a node or along a link, from its appearance until it is replaced.
+pub struct Badge {
+    id: String,
+    text: String,
+    color: Color,
+    small: bool,
+    position: Point,
+    appear: (u32, u32),
+    disappear: Option<(u32, u32)>,
+    moves: Vec<(u32, u32, Point, Point)>,
+}
+
+impl Badge {
+    pub fn new(
+        id: String,
+        text: String,
+        color: Color,
+        small: bool,
+        position: Point,
+        appear: (u32, u32),
+    ) -> Badge {
+        Badge {
+            id,
+            text,
+            color,
+            small,
+            position,
+            appear,
+            disappear: None,
+            moves: Vec::new(),
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Badges are written smaller than nodes names and links values.
+    pub fn small(&self) -> bool {
+        self.small
+    }
+
+    pub fn position(&self) -> &Point {
+        &self.position
+    }
+
+    /// (start, duration) of the fade in.
+    pub fn appear(&self) -> (u32, u32) {
+        self.appear
+    }
+
+    /// (start, duration) of the fade out, if the badge has been replaced.
+    pub fn disappear(&self) -> Option<(u32, u32)> {
+        self.disappear
+    }
+
+    pub fn hide(&mut self, start: u32, duration: u32) {
+        self.disappear = Some((start, duration));
+    }
+
+    /// (start, duration, from, to) of the badge moves, following its node or link.
+    pub fn moves(&self) -> &[(u32, u32, Point, Point)] {
+        &self.moves
+    }
+
+    pub fn move_to(&mut self, start: u32, duration: u32, from: Point, to: Point) {
+        if from.x() != to.x() || from.y() != to.y() {
+            self.moves.push((start, duration, from, to));
+        }
+    }
+}
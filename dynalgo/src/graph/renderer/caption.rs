@@ -0,0 +1,24 @@
+#[derive(Clone)]
+pub struct Caption {
+    id: u32,
+    text: String,
+    start: u32,
+}
+
+impl Caption {
+    pub fn new(id: u32, text: String, start: u32) -> Caption {
+        Caption { id, text, start }
+    }
+
+    pub fn id(&self) -> String {
+        format!("t{}", self.id)
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+}
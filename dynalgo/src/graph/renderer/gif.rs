@@ -0,0 +1,167 @@
+use std::collections::HashMap;
+
+pub struct Gif {}
+
+impl Gif {
+    /// Encodes RGB frames (3 bytes per pixel, rows from top to bottom) into an endlessly looping animated GIF.
+    /// Each frame is displayed during its delay, in hundredths of a second.
+    /// Colors are exact when frames use at most 256 colors, and mapped to a 252 colors cube otherwise.
+    pub fn encode(width: u16, height: u16, frames: &[(Vec<u8>, u16)]) -> Vec<u8> {
+        let palette = Self::palette(frames);
+
+        let mut gif = Vec::new();
+        gif.extend_from_slice(b"GIF89a");
+        gif.extend_from_slice(&width.to_le_bytes());
+        gif.extend_from_slice(&height.to_le_bytes());
+        gif.extend_from_slice(&[0xf7, 0, 0]);
+        let mut table = vec![0; 256 * 3];
+        for (color, index) in &palette {
+            let i = *index as usize * 3;
+            table[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+        }
+        gif.extend_from_slice(&table);
+
+        gif.extend_from_slice(&[0x21, 0xff, 0x0b]);
+        gif.extend_from_slice(b"NETSCAPE2.0");
+        gif.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        for (rgb, delay) in frames {
+            assert!(rgb.len() == width as usize * height as usize * 3);
+
+            gif.extend_from_slice(&[0x21, 0xf9, 0x04, 0x04]);
+            gif.extend_from_slice(&delay.to_le_bytes());
+            gif.extend_from_slice(&[0x00, 0x00]);
+
+            gif.push(0x2c);
+            gif.extend_from_slice(&[0, 0, 0, 0]);
+            gif.extend_from_slice(&width.to_le_bytes());
+            gif.extend_from_slice(&height.to_le_bytes());
+            gif.push(0);
+
+            let indexes: Vec<u8> = rgb
+                .chunks(3)
+                .map(|p| Self::index(&palette, (p[0], p[1], p[2])))
+                .collect();
+            gif.push(8);
+            for block in Self::lzw(&indexes).chunks(255) {
+                gif.push(block.len() as u8);
+                gif.extend_from_slice(block);
+            }
+            gif.push(0);
+        }
+
+        gif.push(0x3b);
+        gif
+    }
+
+    fn palette(frames: &[(Vec<u8>, u16)]) -> HashMap<(u8, u8, u8), u8> {
+        let mut palette = HashMap::new();
+        for (rgb, _) in frames {
+            for p in rgb.chunks(3) {
+                let color = (p[0], p[1], p[2]);
+                if palette.contains_key(&color) {
+                    continue;
+                }
+                if palette.len() == 256 {
+                    return Self::cube();
+                }
+                palette.insert(color, palette.len() as u8);
+            }
+        }
+        palette
+    }
+
+    fn cube() -> HashMap<(u8, u8, u8), u8> {
+        let mut palette = HashMap::new();
+        for r in 0..6 {
+            for g in 0..7 {
+                for b in 0..6 {
+                    let color = (r * 51, g * 42 + g / 2, b * 51);
+                    palette.insert(color, r * 42 + g * 6 + b);
+                }
+            }
+        }
+        palette
+    }
+
+    fn index(palette: &HashMap<(u8, u8, u8), u8>, color: (u8, u8, u8)) -> u8 {
+        match palette.get(&color) {
+            Some(index) => *index,
+            None => {
+                let r = (color.0 as u32 * 5 + 127) / 255;
+                let g = (color.1 as u32 * 6 + 127) / 255;
+                let b = (color.2 as u32 * 5 + 127) / 255;
+                (r * 42 + g * 6 + b) as u8
+            }
+        }
+    }
+
+    fn lzw(indexes: &[u8]) -> Vec<u8> {
+        const CLEAR: u16 = 256;
+        const END: u16 = 257;
+
+        let mut bits = BitWriter::default();
+        let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+        let mut next_code = END + 1;
+        let mut code_size = 9;
+
+        bits.write(CLEAR, code_size);
+        let mut prefix = match indexes.first() {
+            Some(index) => *index as u16,
+            None => {
+                bits.write(END, code_size);
+                return bits.finish();
+            }
+        };
+        for index in &indexes[1..] {
+            if let Some(code) = codes.get(&(prefix, *index)) {
+                prefix = *code;
+                continue;
+            }
+            bits.write(prefix, code_size);
+            if next_code == 4096 {
+                bits.write(CLEAR, code_size);
+                codes.clear();
+                next_code = END + 1;
+                code_size = 9;
+            } else {
+                codes.insert((prefix, *index), next_code);
+                next_code += 1;
+                if next_code > 1 << code_size {
+                    code_size += 1;
+                }
+            }
+            prefix = *index as u16;
+        }
+        bits.write(prefix, code_size);
+        bits.write(END, code_size);
+
+        bits.finish()
+    }
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.count;
+        self.count += size;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
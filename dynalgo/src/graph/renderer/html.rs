@@ -1,15 +1,19 @@
+use super::super::Style;
+use super::svg::escape;
+
 pub struct Html {}
 
 impl Html {
-    pub fn render_flexbox(
-        titles: &Vec<String>,
+    pub fn render_flexbox<N>(
+        titles: &[String],
         title_idx: usize,
-        file_names: &Vec<String>,
+        file_names: &[String],
         contents: Vec<String>,
+        style: &Style<N>,
     ) -> String {
-        let back_color = (255, 255, 255);
-        let text_color = (0, 0, 139);
-        let sepe_color = (211, 211, 211);
+        let back_color = style.background;
+        let text_color = style.node_text;
+        let sepe_color = style.border;
 
         let mut html = String::from(
             "
@@ -153,6 +157,7 @@ impl Html {
 
 		article {
 			display: flex;
+			flex-direction: column;
 			border: none;
 			width: 50%;
 			height: 100%;
@@ -174,6 +179,47 @@ impl Html {
 		  dominant-baseline: central;
 		}
 
+		article svg {
+			flex: 1;
+			min-height: 0;
+		}
+
+		.player {
+			display: flex;
+			align-items: center;
+			gap: 0.4em;
+			padding: 0.3em;
+		}
+
+		.player input {
+			flex: 1;
+		}
+
+		.player button {
+			min-width: 2.2em;
+			cursor: pointer;
+		}
+
+		.player span {
+			font-family: monospace;
+			white-space: nowrap;
+		}
+
+		.captions {
+			max-height: 20%;
+			overflow-y: auto;
+			margin: 0px;
+			padding: 0.3em 0.3em 0.3em 2.5em;
+		}
+
+		.captions li {
+			cursor: pointer;
+		}
+
+		.captions .current {
+			font-weight: bold;
+		}
+
 		@media screen and (max-width: 800px) {
 			body {
 				flex-direction: column;
@@ -257,6 +303,92 @@ impl Html {
                 svg.pauseAnimations();
             }
         }
+
+        function player(controls) {
+            var svg = controls.parentNode.querySelector('svg');
+            var steps = controls.dataset.steps === '' ? [] : controls.dataset.steps.split(',').map(Number);
+            var duration = Number(controls.dataset.duration);
+            var buttons = controls.querySelectorAll('button');
+            var scrub = controls.querySelector('input');
+            var speed = controls.querySelector('select');
+            var clock = controls.querySelector('span');
+            var captions = controls.parentNode.querySelectorAll('.captions li');
+            var time = 0, playing = true, last = null;
+
+            var seek = function (t) {
+                time = Math.max(0, Math.min(duration, t));
+                if (svg.dynalgoSeek) {
+                    svg.dynalgoSeek(time);
+                } else {
+                    svg.pauseAnimations();
+                    svg.setCurrentTime(time / 1000);
+                }
+                scrub.value = time;
+                clock.textContent = (time / 1000).toFixed(1) + ' / ' + (duration / 1000).toFixed(1) + ' s';
+                var current = null;
+                captions.forEach(function (caption) {
+                    caption.classList.remove('current');
+                    if (Number(caption.dataset.time) <= time) {
+                        current = caption;
+                    }
+                });
+                if (current) {
+                    current.classList.add('current');
+                }
+            };
+            var play = function (enabled) {
+                playing = enabled;
+                last = null;
+                buttons[1].textContent = enabled ? '⏸' : '▶';
+            };
+            var frame = function (now) {
+                if (playing) {
+                    if (last !== null) {
+                        seek(time + (now - last) * Number(speed.value));
+                    }
+                    last = now;
+                    if (time >= duration) {
+                        play(false);
+                    }
+                }
+                requestAnimationFrame(frame);
+            };
+            var toggle = function () {
+                if (!playing && time >= duration) {
+                    seek(0);
+                }
+                play(!playing);
+            };
+
+            buttons[0].onclick = function () {
+                play(false);
+                seek(steps.filter(function (s) { return s < time - 1; }).pop() || 0);
+            };
+            buttons[1].onclick = toggle;
+            buttons[2].onclick = function () {
+                play(false);
+                var next = steps.find(function (s) { return s > time + 1; });
+                seek(next === undefined ? duration : next);
+            };
+            scrub.oninput = function () {
+                play(false);
+                seek(Number(scrub.value));
+            };
+            captions.forEach(function (caption) {
+                caption.onclick = function () {
+                    play(false);
+                    seek(Number(caption.dataset.time));
+                };
+            });
+            svg.onclick = controls.dataset.pause === 'true' ? toggle : null;
+
+            seek(0);
+            requestAnimationFrame(frame);
+        }
+
+        document.addEventListener('DOMContentLoaded', function () {
+            document.querySelectorAll('.player').forEach(player);
+        });
     </script>
   </head>
   <body>
@@ -326,4 +458,71 @@ impl Html {
 
         html
     }
+
+    /// Returns the controls playing the animation of the SVG element preceding them in the same article.
+    /// Steps are the start times (in ms) the backward and forward buttons jump to.
+    /// Captions are listed below the controls, with their start times.
+    pub fn player(
+        steps: &[u32],
+        duration: u32,
+        pause_on_click: bool,
+        captions: &[(u32, &str)],
+    ) -> String {
+        let steps: Vec<String> = steps.iter().map(|start| start.to_string()).collect();
+        let mut html = format!(
+            "
+		<div class=\"player\" data-steps=\"{}\" data-duration=\"{}\" data-pause=\"{}\">",
+            steps.join(","),
+            duration,
+            pause_on_click
+        );
+        html.push_str(
+            "
+			<button title=\"Previous step\">⏮</button>
+			<button title=\"Play / pause\">⏸</button>
+			<button title=\"Next step\">⏭</button>",
+        );
+        html.push_str(&format!(
+            "
+			<input type=\"range\" min=\"0\" max=\"{}\" step=\"1\" value=\"0\">",
+            duration
+        ));
+        html.push_str(
+            "
+			<select title=\"Speed\">
+				<option value=\"0.25\">x0.25</option>
+				<option value=\"0.5\">x0.5</option>
+				<option value=\"1\" selected>x1</option>
+				<option value=\"2\">x2</option>
+				<option value=\"4\">x4</option>
+			</select>",
+        );
+        html.push_str(&format!(
+            "
+			<span>0.0 / {:.1} s</span>
+		</div>",
+            duration as f64 / 1000.
+        ));
+
+        if !captions.is_empty() {
+            html.push_str(
+                "
+		<ol class=\"captions\">",
+            );
+            for (start, text) in captions {
+                html.push_str(&format!(
+                    "
+			<li data-time=\"{}\">{}</li>",
+                    start,
+                    escape(text)
+                ));
+            }
+            html.push_str(
+                "
+		</ol>",
+            );
+        }
+
+        html
+    }
 }
@@ -2,35 +2,50 @@ use super::color::Color;
 use super::point::Point;
 use super::tag::Tag;
 
-#[derive(Copy, Clone)]
-pub struct Link {
+#[derive(Clone, PartialEq)]
+pub struct Link<N> {
     id: u32,
-    from: char,
-    to: char,
+    from: N,
+    to: N,
     from_center: Point,
     to_center: Point,
     bidirect: bool,
-    value: i8,
+    value: String,
+    value_updated: bool,
     stroke_color: Color,
     stroke_color_init: Color,
     text_color: Color,
     stroke_width: u8,
+    label: String,
+    bend: i32,
+    looped: bool,
+    from_radius: i32,
+    to_radius: i32,
     tag: Option<Tag>,
 }
 
-impl Link {
+/// Initial look of a link.
+pub struct LinkStyle {
+    pub stroke_color: Color,
+    pub text_color: Color,
+    pub stroke_width: u8,
+}
+
+impl<N> Link<N> {
     pub fn new(
         id: u32,
-        from: char,
-        to: char,
-        from_center: Point,
-        to_center: Point,
+        from: N,
+        to: N,
+        (from_center, to_center): (Point, Point),
         bidirect: bool,
-        value: i8,
-        stroke_color: Color,
-        text_color: Color,
-        stroke_width: u8,
-    ) -> Link {
+        value: String,
+        style: LinkStyle,
+    ) -> Link<N> {
+        let LinkStyle {
+            stroke_color,
+            text_color,
+            stroke_width,
+        } = style;
         Link {
             id,
             from,
@@ -39,32 +54,35 @@ impl Link {
             to_center,
             bidirect,
             value,
+            value_updated: false,
             stroke_color,
             stroke_color_init: stroke_color,
             text_color,
             stroke_width,
+            label: String::new(),
+            bend: 0,
+            looped: false,
+            from_radius: 0,
+            to_radius: 0,
             tag: None,
         }
     }
 
     pub fn id(&self) -> String {
-        format!("{}{}{}", self.from, self.to, self.id)
-    }
-
-    pub fn from(&self) -> char {
-        self.from
+        format!("l{}", self.id)
     }
 
-    pub fn to(&self) -> char {
-        self.to
+    /// Creation sequence number.
+    pub fn seq(&self) -> u32 {
+        self.id
     }
 
-    pub fn from_center(&self) -> &Point {
-        &self.from_center
+    pub fn from(&self) -> &N {
+        &self.from
     }
 
-    pub fn to_center(&self) -> &Point {
-        &self.to_center
+    pub fn to(&self) -> &N {
+        &self.to
     }
 
     pub fn update_from_center(&mut self, center: Point) {
@@ -75,12 +93,124 @@ impl Link {
         self.to_center = center;
     }
 
+    /// Sets the radius of the source node, where the link is clipped.
+    pub fn update_from_radius(&mut self, radius: i32) {
+        self.from_radius = radius;
+    }
+
+    /// Sets the radius of the target node, where the link (and the arrowhead) is clipped.
+    pub fn update_to_radius(&mut self, radius: i32) {
+        self.to_radius = radius;
+    }
+
     pub fn bidirect(&self) -> bool {
         self.bidirect
     }
 
-    pub fn value(&self) -> i8 {
-        self.value
+    /// Sets the distance between the middle of the nodes centers and the middle of the curve (on the right side of the link direction when positive),
+    /// or the radius of the loop circle.
+    pub fn set_bend(&mut self, bend: i32) {
+        self.bend = bend
+    }
+
+    /// Indicates whether the link goes from a node to itself.
+    pub fn looped(&self) -> bool {
+        self.looped
+    }
+
+    pub fn set_looped(&mut self, looped: bool) {
+        self.looped = looped
+    }
+
+    /// Path coordinates: `[x1, y1, r, x2, y2]` for a loop (large arc of radius `r` above the node, between its intersections with the node circle),
+    /// else `[x1, y1, cx, cy, x2, y2]` for a quadratic curve (straight when the control point is the middle).
+    /// Links are clipped at the nodes borders, so that arrowheads of directed links point to the target node border.
+    pub fn path(&self) -> Vec<i32> {
+        let (from, to) = (self.from_center, self.to_center);
+        if self.looped {
+            let (r, radius) = (self.bend as f64, self.to_radius as f64);
+            let dy = -radius * radius / (2. * r);
+            let dx = (radius * radius - dy * dy).sqrt();
+            let (dx, dy) = (dx.round() as i32, dy.round() as i32);
+            return vec![
+                from.x() - dx,
+                from.y() + dy,
+                self.bend,
+                from.x() + dx,
+                from.y() + dy,
+            ];
+        }
+
+        let control = self.curve_point(2 * self.bend);
+        let start = Self::clip(from, control, self.from_radius);
+        let end = Self::clip(to, control, self.to_radius);
+        vec![
+            start.x(),
+            start.y(),
+            control.x(),
+            control.y(),
+            end.x(),
+            end.y(),
+        ]
+    }
+
+    /// Middle of the drawn link (top of the loop), where the value is displayed.
+    pub fn middle(&self) -> Point {
+        match self.path()[..] {
+            [x1, y1, r, x2, _] => {
+                let half_chord = (x2 - x1) as f64 / 2.;
+                let center_y = y1 as f64 - (((r * r) as f64 - half_chord * half_chord).sqrt());
+                Point::new((x1 + x2) / 2, (center_y - r as f64).round() as i32)
+            }
+            [x1, y1, cx, cy, x2, y2] => Point::new((x1 + 2 * cx + x2) / 4, (y1 + 2 * cy + y2) / 4),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Point at `radius` from the node center, towards the curve control point.
+    fn clip(center: Point, control: Point, radius: i32) -> Point {
+        let (dx, dy) = (
+            (control.x() - center.x()) as f64,
+            (control.y() - center.y()) as f64,
+        );
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0. {
+            return center;
+        }
+        Point::new(
+            center.x() + (dx * radius as f64 / length).round() as i32,
+            center.y() + (dy * radius as f64 / length).round() as i32,
+        )
+    }
+
+    fn curve_point(&self, offset: i32) -> Point {
+        let (from, to) = (self.from_center, self.to_center);
+        let (dx, dy) = ((to.x() - from.x()) as f64, (to.y() - from.y()) as f64);
+        let length = (dx * dx + dy * dy).sqrt();
+        let (nx, ny) = match length > 0. {
+            true => (-dy / length, dx / length),
+            false => (0., 0.),
+        };
+        Point::new(
+            (from.x() + to.x()) / 2 + (nx * offset as f64).round() as i32,
+            (from.y() + to.y()) / 2 + (ny * offset as f64).round() as i32,
+        )
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Indicates whether the value has changed since the link creation.
+    pub fn value_updated(&self) -> bool {
+        self.value_updated
+    }
+
+    pub fn set_value(&mut self, value: String) {
+        if value != self.value {
+            self.value = value;
+            self.value_updated = true;
+        }
     }
 
     pub fn stroke_color(&self) -> Color {
@@ -91,10 +221,6 @@ impl Link {
         self.stroke_color = color
     }
 
-    pub fn stroke_color_init(&self) -> Color {
-        self.stroke_color_init
-    }
-
     pub fn set_text_color(&mut self, color: Color) {
         self.text_color = color
     }
@@ -107,21 +233,35 @@ impl Link {
         self.stroke_width
     }
 
+    pub fn set_stroke_width(&mut self, width: u8) {
+        self.stroke_width = width
+    }
+
+    /// Displayed stroke width: doubled while the stroke color is not the initial one (highlighted link).
+    pub fn stroke_width_shown(&self) -> u16 {
+        match self.stroke_color == self.stroke_color_init {
+            true => self.stroke_width as u16,
+            false => 2 * self.stroke_width as u16,
+        }
+    }
+
     pub fn tag(&mut self, tag: Option<Tag>) {
         self.tag = tag;
     }
 
     pub fn tag_created(&self) -> bool {
-        match self.tag {
-            Some(Tag::Created) => true,
-            _ => false,
-        }
+        matches!(self.tag, Some(Tag::Created))
     }
 
     pub fn tag_deleted(&self) -> bool {
-        match self.tag {
-            Some(Tag::Deleted) => true,
-            _ => false,
-        }
+        matches!(self.tag, Some(Tag::Deleted))
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn set_label(&mut self, label: String) {
+        self.label = label
     }
 }
@@ -1,11 +1,12 @@
+use super::super::Shape;
 use super::color::Color;
 use super::point::Point;
 use super::tag::Tag;
 
-#[derive(Copy, Clone)]
-pub struct Node {
+#[derive(Clone, PartialEq)]
+pub struct Node<N> {
     id: u32,
-    name: char,
+    name: N,
     center: Point,
     center_freezed: bool,
     radius: u8,
@@ -14,20 +15,29 @@ pub struct Node {
     stroke_color_init: Color,
     text_color: Color,
     stroke_width: u8,
+    badge: String,
+    shape: Shape,
     tag: Option<Tag>,
 }
 
-impl Node {
-    pub fn new(
-        id: u32,
-        name: char,
-        center: Option<Point>,
-        radius: u8,
-        fill_color: Color,
-        stroke_color: Color,
-        text_color: Color,
-        stroke_width: u8,
-    ) -> Node {
+/// Initial look of a node.
+pub struct NodeStyle {
+    pub radius: u8,
+    pub fill_color: Color,
+    pub stroke_color: Color,
+    pub text_color: Color,
+    pub stroke_width: u8,
+}
+
+impl<N> Node<N> {
+    pub fn new(id: u32, name: N, center: Option<Point>, style: NodeStyle) -> Node<N> {
+        let NodeStyle {
+            radius,
+            fill_color,
+            stroke_color,
+            text_color,
+            stroke_width,
+        } = style;
         let center_freezed = center.is_some();
         let center = match center {
             Some(c) => c,
@@ -44,16 +54,23 @@ impl Node {
             stroke_color_init: stroke_color,
             text_color,
             stroke_width,
+            badge: String::new(),
+            shape: Shape::Circle,
             tag: None,
         }
     }
 
     pub fn id(&self) -> String {
-        format!("{}{}", self.name, self.id)
+        format!("n{}", self.id)
     }
 
-    pub fn name(&self) -> char {
-        self.name
+    /// Creation sequence number.
+    pub fn seq(&self) -> u32 {
+        self.id
+    }
+
+    pub fn name(&self) -> &N {
+        &self.name
     }
 
     pub fn center(&self) -> &Point {
@@ -72,6 +89,10 @@ impl Node {
         self.radius
     }
 
+    pub fn set_radius(&mut self, radius: u8) {
+        self.radius = radius
+    }
+
     pub fn move_to(&mut self, center: Point) {
         self.center = center;
     }
@@ -81,23 +102,25 @@ impl Node {
     }
 
     pub fn tag_created(&self) -> bool {
-        match self.tag {
-            Some(Tag::Created) => true,
-            _ => false,
-        }
+        matches!(self.tag, Some(Tag::Created))
     }
 
     pub fn tag_deleted(&self) -> bool {
-        match self.tag {
-            Some(Tag::Deleted) => true,
-            _ => false,
-        }
+        matches!(self.tag, Some(Tag::Deleted))
     }
 
     pub fn stroke_width(&self) -> u8 {
         self.stroke_width
     }
 
+    /// Displayed stroke width: doubled while the stroke color is not the initial one (highlighted node).
+    pub fn stroke_width_shown(&self) -> u16 {
+        match self.stroke_color == self.stroke_color_init {
+            true => self.stroke_width as u16,
+            false => 2 * self.stroke_width as u16,
+        }
+    }
+
     pub fn set_fill_color(&mut self, color: Color) {
         self.fill_color = color
     }
@@ -118,11 +141,23 @@ impl Node {
         self.stroke_color
     }
 
-    pub fn stroke_color_init(&self) -> Color {
-        self.stroke_color_init
-    }
-
     pub fn set_stroke_color(&mut self, color: Color) {
         self.stroke_color = color
     }
+
+    pub fn badge(&self) -> &str {
+        &self.badge
+    }
+
+    pub fn set_badge(&mut self, badge: String) {
+        self.badge = badge
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn set_shape(&mut self, shape: Shape) {
+        self.shape = shape
+    }
 }
@@ -0,0 +1,77 @@
+pub struct Png {}
+
+impl Png {
+    /// Encodes RGB pixels (3 bytes per pixel, rows from top to bottom) into a PNG image.
+    /// Image data is stored without compression, so the encoder stays tiny.
+    pub fn encode(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+        assert!(rgb.len() == (width * height * 3) as usize);
+
+        let mut png = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&width.to_be_bytes());
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+        Self::chunk(&mut png, b"IHDR", &ihdr);
+
+        let mut raw = Vec::with_capacity(rgb.len() + height as usize);
+        for row in rgb.chunks((width * 3) as usize) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        Self::chunk(&mut png, b"IDAT", &Self::zlib_stored(&raw));
+        Self::chunk(&mut png, b"IEND", &[]);
+
+        png
+    }
+
+    fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = png.len();
+        png.extend_from_slice(kind);
+        png.extend_from_slice(data);
+        let crc = Self::crc32(&png[start..]);
+        png.extend_from_slice(&crc.to_be_bytes());
+    }
+
+    fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut zlib = vec![0x78, 0x01];
+        let mut blocks = data.chunks(0xffff).peekable();
+        if blocks.peek().is_none() {
+            zlib.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+        }
+        while let Some(block) = blocks.next() {
+            let last = blocks.peek().is_none();
+            let len = block.len() as u16;
+            zlib.push(last as u8);
+            zlib.extend_from_slice(&len.to_le_bytes());
+            zlib.extend_from_slice(&(!len).to_le_bytes());
+            zlib.extend_from_slice(block);
+        }
+        zlib.extend_from_slice(&Self::adler32(data).to_be_bytes());
+        zlib
+    }
+
+    fn crc32(data: &[u8]) -> u32 {
+        let mut crc = 0xffffffff_u32;
+        for byte in data {
+            crc ^= *byte as u32;
+            for _ in 0..8 {
+                crc = match crc & 1 {
+                    1 => (crc >> 1) ^ 0xedb88320,
+                    _ => crc >> 1,
+                };
+            }
+        }
+        !crc
+    }
+
+    fn adler32(data: &[u8]) -> u32 {
+        let (mut a, mut b) = (1_u32, 0_u32);
+        for byte in data {
+            a = (a + *byte as u32) % 65521;
+            b = (b + a) % 65521;
+        }
+        (b << 16) | a
+    }
+}
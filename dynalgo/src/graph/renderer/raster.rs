@@ -0,0 +1,407 @@
+use super::super::Shape;
+use super::color::Color;
+use super::font;
+use super::link::Link;
+use super::node::Node;
+use super::svg::Svg;
+use std::f64::consts::{PI, SQRT_2};
+use std::fmt::Display;
+
+pub struct Raster {
+    pub p_display_node_label: bool,
+    pub p_display_link_value: bool,
+    pub p_radius_node: u8,
+}
+
+impl Raster {
+    pub fn new(
+        p_display_node_label: bool,
+        p_display_link_value: bool,
+        p_radius_node: u8,
+    ) -> Raster {
+        Raster {
+            p_display_node_label,
+            p_display_link_value,
+            p_radius_node,
+        }
+    }
+
+    pub fn draw_link<N>(&self, canvas: &mut Canvas, current: &Link<N>, previous: &Link<N>, f: f64) {
+        let opacity = Self::opacity(current.tag_created(), current.tag_deleted(), f);
+        if opacity <= 0. {
+            return;
+        }
+
+        let path: Vec<f64> = previous
+            .path()
+            .into_iter()
+            .zip(current.path())
+            .map(|(from, to)| Self::mix(from, to, f))
+            .collect();
+        let points = Self::path_points(&path);
+        let width_curr = previous.stroke_width_shown() as f64;
+        let width_next = current.stroke_width_shown() as f64;
+        let stroke = Self::mix_color(previous.stroke_color(), current.stroke_color(), f);
+        let text = Self::mix_color(previous.text_color(), current.text_color(), f);
+        canvas.polyline(
+            &points,
+            width_curr + (width_next - width_curr) * f,
+            stroke,
+            opacity,
+        );
+
+        if !current.bidirect() {
+            let (base, tip) = (points[points.len() - 2], points[points.len() - 1]);
+            canvas.arrow(base, tip, 10., stroke, opacity);
+        }
+
+        let middle = points[points.len() / 2];
+        if self.p_display_link_value {
+            Self::draw_badge(
+                canvas,
+                middle,
+                previous.value(),
+                current.value(),
+                text,
+                opacity,
+                f,
+            );
+        }
+
+        let label = (middle.0, middle.1 - self.p_radius_node as f64);
+        Self::draw_badge(
+            canvas,
+            label,
+            previous.label(),
+            current.label(),
+            text,
+            opacity,
+            f,
+        );
+    }
+
+    pub fn draw_node<N: Display>(
+        &self,
+        canvas: &mut Canvas,
+        current: &Node<N>,
+        previous: &Node<N>,
+        f: f64,
+    ) {
+        let opacity = Self::opacity(current.tag_created(), current.tag_deleted(), f);
+        if opacity <= 0. {
+            return;
+        }
+
+        let center = (
+            Self::mix(previous.center().x(), current.center().x(), f),
+            Self::mix(previous.center().y(), current.center().y(), f),
+        );
+        let radius = Self::mix(previous.radius() as i32, current.radius() as i32, f);
+        let width_curr = previous.stroke_width_shown() as f64;
+        let width_next = current.stroke_width_shown() as f64;
+        canvas.shape(
+            center,
+            current.shape(),
+            radius,
+            width_curr + (width_next - width_curr) * f,
+            (
+                Self::mix_color(previous.fill_color(), current.fill_color(), f),
+                Self::mix_color(previous.stroke_color(), current.stroke_color(), f),
+            ),
+            opacity,
+        );
+
+        let text = Self::mix_color(previous.text_color(), current.text_color(), f);
+        if self.p_display_node_label {
+            canvas.text(center, &current.name().to_string(), text, opacity);
+        }
+
+        let badge = (center.0 + radius, center.1 - radius);
+        Self::draw_badge(
+            canvas,
+            badge,
+            previous.badge(),
+            current.badge(),
+            text,
+            opacity,
+            f,
+        );
+    }
+
+    /// Draws the badge text, cross-fading from the previous text when it changes.
+    fn draw_badge(
+        canvas: &mut Canvas,
+        center: (f64, f64),
+        previous: &str,
+        current: &str,
+        color: Color,
+        opacity: f64,
+        f: f64,
+    ) {
+        if previous == current {
+            canvas.text(center, current, color, opacity);
+            return;
+        }
+        canvas.text(center, previous, color, opacity * (1. - f));
+        canvas.text(center, current, color, opacity * f);
+    }
+
+    /// Points along the link path (see `Link::path`): a loop arc or a quadratic curve, the middle point being the middle of the link.
+    fn path_points(path: &[f64]) -> Vec<(f64, f64)> {
+        let count = 16;
+        (0..=count)
+            .map(|i| {
+                let t = i as f64 / count as f64;
+                match path {
+                    [x1, y1, r, x2, _] => {
+                        let half_chord = (x2 - x1) / 2.;
+                        let center = (
+                            (x1 + x2) / 2.,
+                            y1 - (r * r - half_chord * half_chord).max(0.).sqrt(),
+                        );
+                        let angle_from = (y1 - center.1).atan2(x1 - center.0);
+                        let angle_to = angle_from + PI + 2. * (y1 - center.1).atan2(half_chord);
+                        let angle = angle_from + (angle_to - angle_from) * t;
+                        (center.0 + r * angle.cos(), center.1 + r * angle.sin())
+                    }
+                    _ => {
+                        let point = |a: f64, c: f64, b: f64| {
+                            (1. - t) * (1. - t) * a + 2. * t * (1. - t) * c + t * t * b
+                        };
+                        (
+                            point(path[0], path[2], path[4]),
+                            point(path[1], path[3], path[5]),
+                        )
+                    }
+                }
+            })
+            .collect()
+    }
+
+    fn opacity(created: bool, deleted: bool, f: f64) -> f64 {
+        match (created, deleted) {
+            (true, _) => f,
+            (_, true) => 1. - f,
+            _ => 1.,
+        }
+    }
+
+    fn mix(from: i32, to: i32, f: f64) -> f64 {
+        from as f64 + (to - from) as f64 * f
+    }
+
+    fn mix_color(from: Color, to: Color, f: f64) -> Color {
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * f).round() as u8;
+        Color::new(
+            mix(from.r(), to.r()),
+            mix(from.g(), to.g()),
+            mix(from.b(), to.b()),
+        )
+    }
+}
+
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    scale: f64,
+    x_0: f64,
+    y_0: f64,
+}
+
+impl Canvas {
+    /// Creates a canvas filled with the background color, showing the (x, y, width, height) viewbox, centered and scaled to fit (as SVG "xMidYMid meet").
+    pub fn new(
+        width: u32,
+        height: u32,
+        viewbox: (f64, f64, f64, f64),
+        background: Color,
+    ) -> Canvas {
+        let (x, y, w, h) = viewbox;
+        let scale = (width as f64 / w).min(height as f64 / h);
+        let pixel = [background.r(), background.g(), background.b()];
+        Canvas {
+            width,
+            height,
+            pixels: pixel.repeat((width * height) as usize),
+            scale,
+            x_0: width as f64 / 2. - (x + w / 2.) * scale,
+            y_0: height as f64 / 2. - (y + h / 2.) * scale,
+        }
+    }
+
+    pub fn pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+
+    /// Draws a node shape: a circle of the given radius, or a square (possibly rotated as a diamond) of the same size as in SVG.
+    pub fn shape(
+        &mut self,
+        center: (f64, f64),
+        shape: Shape,
+        radius: f64,
+        stroke_width: f64,
+        (fill, stroke): (Color, Color),
+        opacity: f64,
+    ) {
+        let (cx, cy) = self.to_pixel(center);
+        let radius = match shape {
+            Shape::Circle => radius,
+            _ => radius * Svg::half_side(100) as f64 / 100.,
+        } * self.scale;
+        let half_width = (stroke_width * self.scale).max(1.) / 2.;
+        // bounding box of the diamond (half diagonal)
+        let extent = radius * SQRT_2 + half_width + 1.;
+        let (x_min, x_max, y_min, y_max) =
+            self.bounds(cx - extent, cx + extent, cy - extent, cy + extent);
+        for y in y_min..=y_max {
+            for x in x_min..=x_max {
+                let (dx, dy) = ((x as f64 + 0.5 - cx).abs(), (y as f64 + 0.5 - cy).abs());
+                // distance to the center, in the shape norm (the border is at radius)
+                let d = match shape {
+                    Shape::Circle => (dx * dx + dy * dy).sqrt(),
+                    Shape::Square => dx.max(dy),
+                    Shape::Diamond => (dx + dy) / SQRT_2,
+                };
+                let fill_coverage = (radius - d + 0.5).clamp(0., 1.);
+                self.blend(x, y, fill, fill_coverage * opacity);
+                let stroke_coverage = (half_width - (d - radius).abs() + 0.5).clamp(0., 1.);
+                self.blend(x, y, stroke, stroke_coverage * opacity);
+            }
+        }
+    }
+
+    /// Draws the segments joining the points.
+    pub fn polyline(&mut self, points: &[(f64, f64)], width: f64, color: Color, opacity: f64) {
+        let points: Vec<(f64, f64)> = points.iter().map(|p| self.to_pixel(*p)).collect();
+        let half_width = (width * self.scale).max(1.) / 2.;
+        let (x_min, x_max, y_min, y_max) = self.bounds(
+            points.iter().map(|p| p.0).fold(f64::MAX, f64::min) - half_width - 1.,
+            points.iter().map(|p| p.0).fold(f64::MIN, f64::max) + half_width + 1.,
+            points.iter().map(|p| p.1).fold(f64::MAX, f64::min) - half_width - 1.,
+            points.iter().map(|p| p.1).fold(f64::MIN, f64::max) + half_width + 1.,
+        );
+        for y in y_min..=y_max {
+            for x in x_min..=x_max {
+                let (px, py) = (x as f64 + 0.5, y as f64 + 0.5);
+                let mut d = f64::MAX;
+                for segment in points.windows(2) {
+                    let ((x1, y1), (x2, y2)) = (segment[0], segment[1]);
+                    let (dx, dy) = (x2 - x1, y2 - y1);
+                    let length = dx * dx + dy * dy;
+                    let t = match length > 0. {
+                        true => (((px - x1) * dx + (py - y1) * dy) / length).clamp(0., 1.),
+                        false => 0.,
+                    };
+                    d = d.min(((px - x1 - t * dx).powi(2) + (py - y1 - t * dy).powi(2)).sqrt());
+                }
+                let coverage = (half_width - d + 0.5).clamp(0., 1.);
+                self.blend(x, y, color, coverage * opacity);
+            }
+        }
+    }
+
+    /// Draws an arrow head pointing to `tip`, in the direction from `from`.
+    pub fn arrow(
+        &mut self,
+        from: (f64, f64),
+        tip: (f64, f64),
+        size: f64,
+        color: Color,
+        opacity: f64,
+    ) {
+        let (dx, dy) = (tip.0 - from.0, tip.1 - from.1);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0. {
+            return;
+        }
+        let (ux, uy) = (dx / length, dy / length);
+        let base = (tip.0 - ux * size, tip.1 - uy * size);
+        let tip = self.to_pixel((base.0 + ux * size, base.1 + uy * size));
+        let left = self.to_pixel((base.0 - uy * size / 2., base.1 + ux * size / 2.));
+        let right = self.to_pixel((base.0 + uy * size / 2., base.1 - ux * size / 2.));
+
+        let (x_min, x_max, y_min, y_max) = self.bounds(
+            tip.0.min(left.0).min(right.0),
+            tip.0.max(left.0).max(right.0),
+            tip.1.min(left.1).min(right.1),
+            tip.1.max(left.1).max(right.1),
+        );
+        let edge = |a: (f64, f64), b: (f64, f64), p: (f64, f64)| {
+            (b.0 - a.0) * (p.1 - a.1) - (b.1 - a.1) * (p.0 - a.0)
+        };
+        for y in y_min..=y_max {
+            for x in x_min..=x_max {
+                let mut inside = 0;
+                for (sx, sy) in [(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)] {
+                    let p = (x as f64 + sx, y as f64 + sy);
+                    let e = [
+                        edge(tip, left, p),
+                        edge(left, right, p),
+                        edge(right, tip, p),
+                    ];
+                    if e.iter().all(|e| *e >= 0.) || e.iter().all(|e| *e <= 0.) {
+                        inside += 1;
+                    }
+                }
+                self.blend(x, y, color, inside as f64 / 4. * opacity);
+            }
+        }
+    }
+
+    /// Draws a text centered on the given position.
+    pub fn text(&mut self, center: (f64, f64), text: &str, color: Color, opacity: f64) {
+        let k = (self.scale * 1.5).round().max(1.) as i64;
+        let (cx, cy) = self.to_pixel(center);
+        let count = text.chars().count() as i64;
+        let width = count * (font::GLYPH_WIDTH as i64 + 1) * k - k;
+        let x_0 = (cx - width as f64 / 2.).round() as i64;
+        let y_0 = (cy - (font::GLYPH_HEIGHT as i64 * k) as f64 / 2.).round() as i64;
+
+        for (i, c) in text.chars().enumerate() {
+            let x_c = x_0 + i as i64 * (font::GLYPH_WIDTH as i64 + 1) * k;
+            for (row, bits) in font::glyph(c).iter().enumerate() {
+                for column in 0..font::GLYPH_WIDTH as i64 {
+                    if bits & (0x10 >> column) == 0 {
+                        continue;
+                    }
+                    for y in 0..k {
+                        for x in 0..k {
+                            self.blend(
+                                x_c + column * k + x,
+                                y_0 + row as i64 * k + y,
+                                color,
+                                opacity,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn to_pixel(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (x * self.scale + self.x_0, y * self.scale + self.y_0)
+    }
+
+    fn bounds(&self, x_min: f64, x_max: f64, y_min: f64, y_max: f64) -> (i64, i64, i64, i64) {
+        (
+            (x_min.floor() as i64).max(0),
+            (x_max.ceil() as i64).min(self.width as i64 - 1),
+            (y_min.floor() as i64).max(0),
+            (y_max.ceil() as i64).min(self.height as i64 - 1),
+        )
+    }
+
+    fn blend(&mut self, x: i64, y: i64, color: Color, alpha: f64) {
+        if alpha <= 0. || x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let i = ((y as u32 * self.width + x as u32) * 3) as usize;
+        let alpha = alpha.min(1.);
+        for (j, channel) in [color.r(), color.g(), color.b()].into_iter().enumerate() {
+            let pixel = self.pixels[i + j] as f64;
+            self.pixels[i + j] = (pixel + (channel as f64 - pixel) * alpha).round() as u8;
+        }
+    }
+}
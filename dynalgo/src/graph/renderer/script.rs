@@ -0,0 +1,478 @@
+use super::super::Shape;
+use super::badge::Badge;
+use super::caption::Caption;
+use super::color::Color;
+use super::link::Link;
+use super::node::Node;
+use super::point::Point;
+use super::svg::Svg;
+
+pub struct Script {}
+
+// Timeline tweens are JSON arrays: [begin ms, duration ms, element id, attribute, from values, to values].
+impl Script {
+    /// Bounds are (x min, x max, y min, y max) of nodes centers, padded by `margin` in the viewbox.
+    pub fn animate_viewbox(
+        curr: (i32, i32, i32, i32),
+        next: (i32, i32, i32, i32),
+        margin: i32,
+        duration: u32,
+        start_time: u32,
+    ) -> Vec<String> {
+        if curr == next {
+            return Vec::new();
+        }
+
+        let viewbox = |(x_min, x_max, y_min, y_max): (i32, i32, i32, i32)| {
+            [
+                x_min - margin,
+                y_min - margin,
+                x_max - x_min + 2 * margin,
+                y_max - y_min + 2 * margin,
+            ]
+        };
+        vec![Self::tween(
+            start_time,
+            duration,
+            "",
+            "viewBox",
+            &viewbox(curr),
+            &viewbox(next),
+        )]
+    }
+
+    pub fn animate_node<N>(
+        current: &Node<N>,
+        previous: &Node<N>,
+        duration: u32,
+        start_time: u32,
+    ) -> Vec<String> {
+        let mut tweens = Vec::new();
+        let id = current.id();
+
+        if previous.center().x() != current.center().x()
+            || previous.center().y() != current.center().y()
+        {
+            tweens.push(Self::tween(
+                start_time,
+                duration,
+                &id,
+                "center",
+                &[previous.center().x(), previous.center().y()],
+                &[current.center().x(), current.center().y()],
+            ));
+        }
+
+        if let Some(tween) = Self::opacity(
+            &id,
+            current.tag_created(),
+            current.tag_deleted(),
+            duration,
+            start_time,
+        ) {
+            tweens.push(tween);
+        }
+
+        if current.text_color() != previous.text_color() {
+            tweens.push(Self::color(
+                start_time,
+                duration,
+                &format!("co{}", id),
+                "fill",
+                previous.text_color(),
+                current.text_color(),
+            ));
+        }
+
+        if current.stroke_width_shown() != previous.stroke_width_shown() {
+            tweens.push(Self::tween(
+                start_time,
+                duration,
+                &format!("c{}", id),
+                "stroke-width",
+                &[previous.stroke_width_shown() as i32],
+                &[current.stroke_width_shown() as i32],
+            ));
+        }
+
+        if current.radius() != previous.radius() {
+            let (attribute, size_curr, size_next) = match current.shape() {
+                Shape::Circle => ("r", previous.radius() as i32, current.radius() as i32),
+                _ => (
+                    "size",
+                    Svg::half_side(previous.radius()),
+                    Svg::half_side(current.radius()),
+                ),
+            };
+            tweens.push(Self::tween(
+                start_time,
+                duration,
+                &format!("c{}", id),
+                attribute,
+                &[size_curr],
+                &[size_next],
+            ));
+        }
+
+        if current.stroke_color() != previous.stroke_color() {
+            tweens.push(Self::color(
+                start_time,
+                duration,
+                &format!("c{}", id),
+                "stroke",
+                previous.stroke_color(),
+                current.stroke_color(),
+            ));
+        }
+
+        if current.fill_color() != previous.fill_color() {
+            tweens.push(Self::color(
+                start_time,
+                duration,
+                &format!("c{}", id),
+                "fill",
+                previous.fill_color(),
+                current.fill_color(),
+            ));
+        }
+
+        tweens
+    }
+
+    pub fn animate_link<N>(
+        current: &Link<N>,
+        previous: &Link<N>,
+        duration: u32,
+        start_time: u32,
+    ) -> Vec<String> {
+        let mut tweens = Vec::new();
+        let id = current.id();
+
+        if previous.path() != current.path() {
+            tweens.push(Self::tween(
+                start_time,
+                duration,
+                &id,
+                "d",
+                &previous.path(),
+                &current.path(),
+            ));
+        }
+
+        if let Some(tween) = Self::opacity(
+            &id,
+            current.tag_created(),
+            current.tag_deleted(),
+            duration,
+            start_time,
+        ) {
+            tweens.push(tween);
+        }
+
+        let value_opacity = match (current.tag_created(), previous.value_updated()) {
+            (true, _) if !current.value_updated() => Some((0, 1)),
+            (false, false) if current.tag_deleted() || current.value_updated() => Some((1, 0)),
+            _ => None,
+        };
+        if let Some((opacity_curr, opacity_next)) = value_opacity {
+            tweens.push(Self::tween(
+                start_time,
+                duration,
+                &format!("lib{}", id),
+                "opacity",
+                &[opacity_curr],
+                &[opacity_next],
+            ));
+        }
+
+        if current.text_color() != previous.text_color() {
+            tweens.push(Self::color(
+                start_time,
+                duration,
+                &format!("m{}", id),
+                "fill",
+                previous.text_color(),
+                current.text_color(),
+            ));
+        }
+
+        if current.stroke_width_shown() != previous.stroke_width_shown() {
+            tweens.push(Self::tween(
+                start_time,
+                duration,
+                &id,
+                "stroke-width",
+                &[previous.stroke_width_shown() as i32],
+                &[current.stroke_width_shown() as i32],
+            ));
+        }
+
+        if current.stroke_color() != previous.stroke_color() {
+            tweens.push(Self::color(
+                start_time,
+                duration,
+                &id,
+                "stroke",
+                previous.stroke_color(),
+                current.stroke_color(),
+            ));
+            if !current.bidirect() {
+                tweens.push(Self::color(
+                    start_time,
+                    duration,
+                    &format!("bi{}", id),
+                    "fill",
+                    previous.stroke_color(),
+                    current.stroke_color(),
+                ));
+            }
+        }
+
+        tweens
+    }
+
+    /// Captions overlay moves (following the viewbox) and captions display, each one until the next one starts.
+    pub fn animate_captions(
+        captions: &[Caption],
+        moves: &[(u32, u32, Point, Point)],
+    ) -> Vec<String> {
+        let mut tweens = Vec::new();
+
+        let first = match captions.first() {
+            Some(caption) => caption,
+            None => return tweens,
+        };
+        for (start_time, duration, from, to) in moves {
+            tweens.push(Self::tween(
+                *start_time,
+                *duration,
+                &format!("g{}", first.id()),
+                "translate",
+                &[from.x(), from.y()],
+                &[to.x(), to.y()],
+            ));
+        }
+        for (i, caption) in captions.iter().enumerate() {
+            tweens.push(Self::tween(
+                caption.start(),
+                0,
+                &caption.id(),
+                "opacity",
+                &[0],
+                &[1],
+            ));
+            if let Some(next) = captions.get(i + 1) {
+                tweens.push(Self::tween(
+                    next.start(),
+                    0,
+                    &caption.id(),
+                    "opacity",
+                    &[1],
+                    &[0],
+                ));
+            }
+        }
+
+        tweens
+    }
+
+    /// Node badge or link label fades and moves.
+    pub fn animate_badge(badge: &Badge) -> Vec<String> {
+        let mut tweens = Vec::new();
+
+        let (start_time, duration) = badge.appear();
+        tweens.push(Self::tween(
+            start_time,
+            duration,
+            badge.id(),
+            "opacity",
+            &[0],
+            &[1],
+        ));
+        if let Some((start_time, duration)) = badge.disappear() {
+            tweens.push(Self::tween(
+                start_time,
+                duration,
+                badge.id(),
+                "opacity",
+                &[1],
+                &[0],
+            ));
+        }
+        for (start_time, duration, from, to) in badge.moves() {
+            tweens.push(Self::tween(
+                *start_time,
+                *duration,
+                badge.id(),
+                "xy",
+                &[from.x(), from.y()],
+                &[to.x(), to.y()],
+            ));
+        }
+
+        tweens
+    }
+
+    /// Returns the script playing the timeline of the SVG element containing it.
+    pub fn player(tweens: &[String], pause_on_click: bool) -> String {
+        // the player compares and combines values: CDATA keeps the SVG document well-formed
+        let mut script = String::from("<script>\n<![CDATA[\n");
+        script.push_str(PLAYER);
+        script.push_str(&format!(
+            "dynalgoPlay(document.currentScript.parentNode, [\n{}\n], {});\n",
+            tweens.join(",\n"),
+            pause_on_click
+        ));
+        script.push_str("]]>\n</script>\n");
+        script
+    }
+
+    fn opacity(
+        id: &str,
+        created: bool,
+        deleted: bool,
+        duration: u32,
+        start_time: u32,
+    ) -> Option<String> {
+        match (created, deleted) {
+            (true, _) => Some(Self::tween(start_time, duration, id, "opacity", &[0], &[1])),
+            (_, true) => Some(Self::tween(start_time, duration, id, "opacity", &[1], &[0])),
+            _ => None,
+        }
+    }
+
+    fn color(
+        start_time: u32,
+        duration: u32,
+        id: &str,
+        attribute: &str,
+        from: Color,
+        to: Color,
+    ) -> String {
+        Self::tween(
+            start_time,
+            duration,
+            id,
+            attribute,
+            &[from.r() as i32, from.g() as i32, from.b() as i32],
+            &[to.r() as i32, to.g() as i32, to.b() as i32],
+        )
+    }
+
+    fn tween(
+        start_time: u32,
+        duration: u32,
+        id: &str,
+        attribute: &str,
+        from: &[i32],
+        to: &[i32],
+    ) -> String {
+        let values = |v: &[i32]| {
+            v.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        };
+        format!(
+            "[{},{},\"{}\",\"{}\",[{}],[{}]]",
+            start_time,
+            duration,
+            id,
+            attribute,
+            values(from),
+            values(to)
+        )
+    }
+}
+
+const PLAYER: &str = "if (!window.dynalgoPlay) {
+  window.dynalgoPlay = function (svg, tweens, pauseOnClick) {
+    var find = function (id) { return id === '' ? svg : svg.querySelector('[id=\"' + id + '\"]'); };
+    var end = 0;
+    tweens.forEach(function (t) {
+      t.push(find(t[2]), t[3] === 'center' ? find('co' + t[2]) : t[3] === 'd' ? find('m' + t[2]) : null);
+      t.push(t[3] === 'center' ? find('c' + t[2]) : null);
+      end = Math.max(end, t[0] + t[1]);
+    });
+    var apply = function (t, v) {
+      var e = t[6];
+      switch (t[3]) {
+        case 'center':
+          if (t[8] && t[8].tagName === 'rect') {
+            // square or diamond (rotated square) shape
+            var half = t[8].getAttribute('width') / 2;
+            t[8].setAttribute('x', v[0] - half); t[8].setAttribute('y', v[1] - half);
+            if (t[8].hasAttribute('transform')) { t[8].setAttribute('transform', 'rotate(45 ' + v[0] + ' ' + v[1] + ')'); }
+          } else if (t[8]) { t[8].setAttribute('cx', v[0]); t[8].setAttribute('cy', v[1]); }
+          if (t[7]) { t[7].setAttribute('x', v[0]); t[7].setAttribute('y', v[1]); }
+          break;
+        case 'd':
+          // [x1, y1, r, x2, y2] loop arc above the node, or [x1, y1, cx, cy, x2, y2] quadratic curve
+          if (v.length === 5) {
+            e.setAttribute('d', 'M' + v[0] + ' ' + v[1] + ' A' + v[2] + ' ' + v[2] + ' 0 1 1 ' + v[3] + ' ' + v[4]);
+            var h = (v[3] - v[0]) / 2;
+            if (t[7]) { t[7].setAttribute('x', (v[0] + v[3]) / 2); t[7].setAttribute('y', v[1] - Math.sqrt(Math.max(0, v[2] * v[2] - h * h)) - v[2]); }
+          } else {
+            e.setAttribute('d', 'M' + v[0] + ' ' + v[1] + ' Q' + v[2] + ' ' + v[3] + ' ' + v[4] + ' ' + v[5]);
+            if (t[7]) { t[7].setAttribute('x', (v[0] + 2 * v[2] + v[4]) / 4); t[7].setAttribute('y', (v[1] + 2 * v[3] + v[5]) / 4); }
+          }
+          break;
+        case 'size':
+          // half side of a square or diamond shape, around its center
+          var side = +e.getAttribute('width');
+          var x = +e.getAttribute('x') + side / 2, y = +e.getAttribute('y') + side / 2;
+          e.setAttribute('x', x - v[0]); e.setAttribute('y', y - v[0]);
+          e.setAttribute('width', 2 * v[0]); e.setAttribute('height', 2 * v[0]);
+          break;
+        case 'xy':
+          e.setAttribute('x', v[0]);
+          e.setAttribute('y', v[1]);
+          break;
+        case 'translate':
+          e.setAttribute('transform', 'translate(' + v[0] + ' ' + v[1] + ')');
+          break;
+        case 'fill': case 'stroke':
+          e.setAttribute(t[3], 'rgb(' + v.map(Math.round).join(',') + ')');
+          break;
+        default:
+          e.setAttribute(t[3], v.join(' '));
+      }
+    };
+    // later tweens first restore their starting values, so that seeking backward works
+    var seek = function (time) {
+      for (var i = tweens.length - 1; i >= 0; i--) {
+        if (tweens[i][6] && tweens[i][0] > time) { apply(tweens[i], tweens[i][4]); }
+      }
+      tweens.forEach(function (t) {
+        if (!t[6] || t[0] > time) { return; }
+        var f = t[1] > 0 ? Math.min(1, (time - t[0]) / t[1]) : 1;
+        apply(t, t[4].map(function (from, i) { return from + (t[5][i] - from) * f; }));
+      });
+    };
+    var start = null, pausedAt = null, controlled = false;
+    var frame = function (now) {
+      if (controlled || pausedAt !== null) { return; }
+      if (start === null) { start = now; }
+      var time = now - start;
+      seek(time);
+      if (time <= end) { requestAnimationFrame(frame); }
+    };
+    if (pauseOnClick) {
+      svg.addEventListener('click', function () {
+        if (controlled) { return; }
+        var now = performance.now();
+        if (pausedAt === null) { pausedAt = now; return; }
+        start += now - pausedAt;
+        pausedAt = null;
+        requestAnimationFrame(frame);
+      });
+    }
+    // an external player (e.g. the HTML page controls) takes over the clock
+    svg.dynalgoSeek = function (time) {
+      controlled = true;
+      seek(time);
+    };
+    requestAnimationFrame(frame);
+  };
+}
+";
@@ -1,5 +1,12 @@
+use super::super::AnimationFormat;
+use super::super::Shape;
+use super::badge::Badge;
+use super::caption::Caption;
+use super::color::Color;
 use super::link::Link;
 use super::node::Node;
+use super::point::Point;
+use std::fmt::Display;
 
 pub struct Svg {
     pub p_display_node_label: bool,
@@ -7,6 +14,11 @@ pub struct Svg {
     pub p_stroke_width_node: u8,
     pub p_stroke_width_link: u8,
     pub p_radius_node: u8,
+    pub p_pause_on_click: bool,
+    pub p_curved_links: bool,
+    pub p_font_family: Option<String>,
+    pub p_font_size: Option<u8>,
+    pub p_color_background: Color,
 }
 
 impl Svg {
@@ -23,20 +35,47 @@ impl Svg {
             p_stroke_width_node,
             p_stroke_width_link,
             p_radius_node,
+            p_pause_on_click: true,
+            p_curved_links: false,
+            p_font_family: None,
+            p_font_size: None,
+            p_color_background: Color::new(255, 255, 255),
         }
     }
 
-    pub fn instantiate_node(&self, node: &Node) -> String {
+    pub fn instantiate_node<N: Display>(&self, node: &Node<N>) -> String {
         let mut svg = String::new();
 
         svg.push_str(&format!("<g id=\"{}\" opacity=\"{}\">\n", node.id(), 0));
-        svg.push_str(&format!(
-            "  <circle id=\"c{}\" cx=\"{}\" cy=\"{}\" r=\"{}\" ",
-            node.id(),
-            node.center().x(),
-            node.center().y(),
-            node.radius()
-        ));
+        let (x, y) = (node.center().x(), node.center().y());
+        let tag = match node.shape() {
+            Shape::Circle => {
+                svg.push_str(&format!(
+                    "  <circle id=\"c{}\" cx=\"{}\" cy=\"{}\" r=\"{}\" ",
+                    node.id(),
+                    x,
+                    y,
+                    node.radius()
+                ));
+                "circle"
+            }
+            shape => {
+                // squares and diamonds have the same size, diamonds being rotated squares
+                let half = Self::half_side(node.radius());
+                svg.push_str(&format!(
+                    "  <rect id=\"c{}\" x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" ",
+                    node.id(),
+                    x - half,
+                    y - half,
+                    2 * half,
+                    2 * half
+                ));
+                if shape == Shape::Diamond {
+                    svg.push_str(&format!("transform=\"rotate(45 {} {})\" ", x, y));
+                }
+                "rect"
+            }
+        };
         svg.push_str(&format!(
             "fill=\"rgb({},{},{})\" ",
             node.fill_color().r(),
@@ -44,11 +83,12 @@ impl Svg {
             node.fill_color().b()
         ));
         svg.push_str(&format!(
-            "stroke=\"rgb({},{},{})\" stroke-width=\"{}\"></circle>\n",
+            "stroke=\"rgb({},{},{})\" stroke-width=\"{}\"></{}>\n",
             node.stroke_color().r(),
             node.stroke_color().g(),
             node.stroke_color().b(),
-            node.stroke_width()
+            node.stroke_width(),
+            tag
         ));
 
         if self.p_display_node_label {
@@ -63,7 +103,7 @@ impl Svg {
                 node.text_color().r(),
                 node.text_color().g(),
                 node.text_color().b(),
-                node.name()
+                escape(&node.name().to_string())
             ));
         }
         svg.push_str("</g>\n");
@@ -71,35 +111,49 @@ impl Svg {
         svg
     }
 
-    pub fn instantiate_link(&self, link: &Link) -> String {
+    pub fn instantiate_link<N>(&self, link: &Link<N>) -> String {
         let mut svg = String::new();
 
+        if !link.bidirect() {
+            svg.push_str(&format!(
+                "<marker id=\"mk{}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"10\" markerHeight=\"10\" ",
+                link.id()
+            ));
+            svg.push_str(&format!(
+                "markerUnits=\"userSpaceOnUse\" orient=\"auto\">\n  <path id=\"bi{}\" d=\"M0 0 L10 5 L0 10 Z\" fill=\"rgb({},{},{})\" />\n</marker>\n",
+                link.id(),
+                link.stroke_color().r(),
+                link.stroke_color().g(),
+                link.stroke_color().b()
+            ));
+        }
+
         svg.push_str(&format!(
             "<path id=\"{}\" stroke-width=\"{}\" opacity=\"{}\"",
             link.id(),
             link.stroke_width(),
             0
         ));
+        if !link.bidirect() {
+            svg.push_str(&format!(" marker-end=\"url(#mk{})\"", link.id()));
+        }
         svg.push_str(&format!(
-            " stroke=\"rgb({},{},{})\" d=\"M{} {} L{} {} Z\" />\n",
+            " stroke=\"rgb({},{},{})\" fill=\"none\" d=\"{}\" />\n",
             link.stroke_color().r(),
             link.stroke_color().g(),
             link.stroke_color().b(),
-            link.from_center().x(),
-            link.from_center().y(),
-            link.to_center().x(),
-            link.to_center().y()
+            path_d(&link.path())
         ));
 
-        if self.p_display_link_value && link.value() != 0 {
+        if self.p_display_link_value && !link.value().is_empty() {
             svg.push_str(&format!("<g id=\"lib{}\" opacity=\"{}\">\n", link.id(), 0));
 
             let (dx, dy) = (0, 0);
             svg.push_str(&format!(
                 "  <text id=\"m{}\" x=\"{}\" y=\"{}\" dx=\"{}\" dy=\"{}\" ",
                 link.id(),
-                (link.from_center().x() + link.to_center().x()) / 2 as i32,
-                (link.from_center().y() + link.to_center().y()) / 2 as i32,
+                link.middle().x(),
+                link.middle().y(),
                 dx,
                 dy
             ));
@@ -108,70 +162,96 @@ impl Svg {
                 link.text_color().r(),
                 link.text_color().g(),
                 link.text_color().b(),
-                link.value()
+                escape(link.value())
             ));
             svg.push_str("</g>\n");
         }
 
-        if !link.bidirect() {
-            let (dx, dy) = match (
-                link.from_center().x() > link.to_center().x(),
-                link.from_center().y() > link.to_center().y(),
-            ) {
-                (true, true) | (false, false) => (5, -5),
-                _ => (-5, -5),
-            };
-            svg.push_str(&format!(
-                "<text id=\"bi{}\" fill=\"rgb({},{},{})\" opacity=\"{}\" dx=\"{}\" dy=\"{}\">\n",
-                link.id(),
-                link.text_color().r(),
-                link.text_color().g(),
-                link.text_color().b(),
-                0,
-                dx,
-                dy
-            ));
-            svg.push_str(&format!(
-                "<textpath startOffset=\"{}\" href=\"#{}\">⇒</textpath>\n",
-                self.p_radius_node + 10,
-                link.id()
-            ));
-            svg.push_str("</text>\n");
-        }
-
         svg
     }
 
+    /// Half side of the square (or diamond) shape of a node.
+    pub fn half_side(radius: u8) -> i32 {
+        (radius as i32 * 9 + 5) / 10
+    }
+
+    /// Font attributes of the root element.
+    fn font(&self) -> String {
+        let mut font = String::new();
+        if let Some(family) = &self.p_font_family {
+            font.push_str(&format!(" font-family=\"{}\"", escape(family)));
+        }
+        if let Some(size) = self.p_font_size {
+            font.push_str(&format!(" font-size=\"{}\"", size));
+        }
+        font
+    }
+
     pub fn instanciate_viewbox(
         &self,
         x_min_init: i32,
         x_max_init: i32,
         y_min_init: i32,
         y_max_init: i32,
+        standalone: bool,
+        format: AnimationFormat,
     ) -> String {
         let mut svg = String::new();
+        // the script player handles clicks itself
+        let pause_on_click = self.p_pause_on_click && format == AnimationFormat::Smil;
+
+        let x = x_min_init - 2 * self.p_radius_node as i32;
+        let y = y_min_init - 2 * self.p_radius_node as i32;
+        let width = x_max_init - x_min_init + 4 * self.p_radius_node as i32;
+        let height = y_max_init - y_min_init + 4 * self.p_radius_node as i32;
+        let onclick = match pause_on_click {
+            true => " onclick=\"pause(this)\"",
+            false => "",
+        };
+
+        if !standalone {
+            svg.push_str(&format!(
+                "\n<svg class=\"svg_dynalgo\"{}{} viewBox=\"{} {} {} {}\" preserveAspectRatio=\"xMidYMid meet\">\n",
+                onclick,
+                self.font(),
+                x,
+                y,
+                width,
+                height
+            ));
+            return svg;
+        }
 
+        svg.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
         svg.push_str(&format!(
-            "\n<svg class=\"svg_dynalgo\" onclick=\"pause(this)\" viewBox=\"{} {} {} {}\" preserveAspectRatio=\"xMidYMid meet\">\n",
-            x_min_init - 2 * self.p_radius_node as i32,
-            y_min_init - 2 * self.p_radius_node as i32,
-            x_max_init - x_min_init + 4 * self.p_radius_node as i32,
-            y_max_init - y_min_init + 4 * self.p_radius_node as i32
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"svg_dynalgo\"{}{} width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\" preserveAspectRatio=\"xMidYMid meet\" style=\"background-color: rgb({},{},{})\">\n",
+            onclick,
+            self.font(),
+            width,
+            height,
+            x,
+            y,
+            width,
+            height,
+            self.p_color_background.r(),
+            self.p_color_background.g(),
+            self.p_color_background.b()
         ));
+        svg.push_str("<style>\n  text {\n    text-anchor: middle;\n    dominant-baseline: central;\n  }\n</style>\n");
+        if pause_on_click {
+            svg.push_str(
+                "<script>\n  function pause(svg) {\n    if (svg.animationsPaused()) {\n      svg.unpauseAnimations();\n    } else {\n      svg.pauseAnimations();\n    }\n  }\n</script>\n",
+            );
+        }
 
         svg
     }
 
+    /// Bounds are (x min, x max, y min, y max) of nodes centers.
     pub fn animate_viewbox(
         &self,
-        x_min_curr: i32,
-        x_max_curr: i32,
-        y_min_curr: i32,
-        y_max_curr: i32,
-        x_min_next: i32,
-        x_max_next: i32,
-        y_min_next: i32,
-        y_max_next: i32,
+        (x_min_curr, x_max_curr, y_min_curr, y_max_curr): (i32, i32, i32, i32),
+        (x_min_next, x_max_next, y_min_next, y_max_next): (i32, i32, i32, i32),
         duration: u32,
         start_time: u32,
     ) -> String {
@@ -205,11 +285,11 @@ impl Svg {
         svg
     }
 
-    pub fn animate_node(
+    pub fn animate_node<N>(
         &self,
-        current: &Node,
-        initial: &Node,
-        previous: &Node,
+        current: &Node<N>,
+        initial: &Node<N>,
+        previous: &Node<N>,
         duration: u32,
         start_time: u32,
     ) -> String {
@@ -278,26 +358,53 @@ impl Svg {
             ));
         }
 
-        if current.stroke_color() != previous.stroke_color() {
-            if current.stroke_color() == current.stroke_color_init()
-                || previous.stroke_color() == current.stroke_color_init()
-            {
-                let (width_curr, width_next) =
-                    match current.stroke_color() == current.stroke_color_init() {
-                        false => (current.stroke_width(), 2 * current.stroke_width()),
-                        true => (2 * current.stroke_width(), current.stroke_width()),
-                    };
+        if current.stroke_width_shown() != previous.stroke_width_shown() {
+            svg.push_str(&format!(
+                "<animate href=\"#c{}\" attributeName=\"stroke-width\" ",
+                current.id()
+            ));
+            svg.push_str(&format!(
+                "from=\"{}\" to=\"{}\" ",
+                previous.stroke_width_shown(),
+                current.stroke_width_shown()
+            ));
+            svg.push_str(&format!(
+                "dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>\n",
+                duration, start_time
+            ));
+        }
+
+        if current.radius() != previous.radius() {
+            // shapes keep their initial coordinates, the group being moved
+            let (x, y) = (initial.center().x(), initial.center().y());
+            let sizes = match current.shape() {
+                Shape::Circle => vec![("r", previous.radius() as i32, current.radius() as i32)],
+                _ => {
+                    let half_curr = Self::half_side(previous.radius());
+                    let half_next = Self::half_side(current.radius());
+                    vec![
+                        ("x", x - half_curr, x - half_next),
+                        ("y", y - half_curr, y - half_next),
+                        ("width", 2 * half_curr, 2 * half_next),
+                        ("height", 2 * half_curr, 2 * half_next),
+                    ]
+                }
+            };
+            for (attribute, size_curr, size_next) in sizes {
                 svg.push_str(&format!(
-                    "<animate href=\"#c{}\" attributeName=\"stroke-width\" ",
-                    current.id()
+                    "<animate href=\"#c{}\" attributeName=\"{}\" ",
+                    current.id(),
+                    attribute
                 ));
-                svg.push_str(&format!("from=\"{}\" to=\"{}\" ", width_curr, width_next));
+                svg.push_str(&format!("from=\"{}\" to=\"{}\" ", size_curr, size_next));
                 svg.push_str(&format!(
                     "dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>\n",
                     duration, start_time
                 ));
             }
+        }
 
+        if current.stroke_color() != previous.stroke_color() {
             let color_curr = previous.stroke_color();
             let color_next = current.stroke_color();
             svg.push_str(&format!(
@@ -344,44 +451,34 @@ impl Svg {
         svg
     }
 
-    pub fn animate_link(
+    pub fn animate_link<N>(
         &self,
-        current: &Link,
-        initial: &Link,
-        previous: &Link,
+        current: &Link<N>,
+        initial: &Link<N>,
+        previous: &Link<N>,
         duration: u32,
         start_time: u32,
     ) -> String {
         let mut svg = String::new();
 
-        if previous.from_center().x() != current.from_center().x()
-            || previous.from_center().y() != current.from_center().y()
-            || previous.to_center().x() != current.to_center().x()
-            || previous.to_center().y() != current.to_center().y()
-        {
+        if previous.path() != current.path() {
             svg.push_str(&format!("<animate href=\"#{}\" ", current.id()));
             svg.push_str(&format!(
                 "begin=\"{}ms\" fill=\"freeze\" attributeName=\"d\" dur=\"{}ms\" ",
                 start_time, duration
             ));
             svg.push_str(&format!(
-                "values=\"M{} {} L{} {} Z;M{} {} L{} {} Z\" />\n",
-                previous.from_center().x(),
-                previous.from_center().y(),
-                previous.to_center().x(),
-                previous.to_center().y(),
-                current.from_center().x(),
-                current.from_center().y(),
-                current.to_center().x(),
-                current.to_center().y(),
+                "values=\"{};{}\" />\n",
+                path_d(&previous.path()),
+                path_d(&current.path())
             ));
 
-            let midle_next_x = (current.from_center().x() + current.to_center().x()) / 2 as i32;
-            let midle_next_y = (current.from_center().y() + current.to_center().y()) / 2 as i32;
-            let midle_curr_x = (previous.from_center().x() + previous.to_center().x()) / 2 as i32;
-            let midle_curr_y = (previous.from_center().y() + previous.to_center().y()) / 2 as i32;
-            let midle_init_x = (initial.from_center().x() + initial.to_center().x()) / 2 as i32;
-            let midle_init_y = (initial.from_center().y() + initial.to_center().y()) / 2 as i32;
+            let midle_next_x = current.middle().x();
+            let midle_next_y = current.middle().y();
+            let midle_curr_x = previous.middle().x();
+            let midle_curr_y = previous.middle().y();
+            let midle_init_x = initial.middle().x();
+            let midle_init_y = initial.middle().y();
 
             let dx_curr = midle_curr_x - midle_init_x;
             let dy_curr = midle_curr_y - midle_init_y;
@@ -416,7 +513,15 @@ impl Svg {
                 "dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>\n",
                 duration, start_time
             ));
+        }
 
+        // the initial value label is hidden for good once the value changes (new values are badges)
+        let value_opacity = match (current.tag_created(), previous.value_updated()) {
+            (true, _) if !current.value_updated() => Some((0, 1)),
+            (false, false) if current.tag_deleted() || current.value_updated() => Some((1, 0)),
+            _ => None,
+        };
+        if let Some((opacity_curr, opacity_next)) = value_opacity {
             svg.push_str(&format!(
                 "<animate href=\"#lib{}\" attributeName=\"opacity\" from=\"{}\" to=\"{}\" ",
                 current.id(),
@@ -427,17 +532,6 @@ impl Svg {
                 "dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>\n",
                 duration, start_time
             ));
-
-            svg.push_str(&format!(
-                "<animate href=\"#bi{}\" attributeName=\"opacity\" from=\"{}\" to=\"{}\" ",
-                current.id(),
-                opacity_curr,
-                opacity_next
-            ));
-            svg.push_str(&format!(
-                "dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>\n",
-                duration, start_time
-            ));
         }
 
         if current.text_color() != previous.text_color() {
@@ -462,47 +556,175 @@ impl Svg {
             ));
         }
 
+        if current.stroke_width_shown() != previous.stroke_width_shown() {
+            svg.push_str(&format!(
+                "<animate href=\"#{}\" attributeName=\"stroke-width\" ",
+                current.id()
+            ));
+            svg.push_str(&format!(
+                "from=\"{}\" to=\"{}\" ",
+                previous.stroke_width_shown(),
+                current.stroke_width_shown()
+            ));
+            svg.push_str(&format!(
+                "dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>\n",
+                duration, start_time
+            ));
+        }
+
         if current.stroke_color() != previous.stroke_color() {
-            if current.stroke_color() == current.stroke_color_init()
-                || previous.stroke_color() == current.stroke_color_init()
-            {
-                let (width_curr, width_next) =
-                    match current.stroke_color() == current.stroke_color_init() {
-                        false => (current.stroke_width(), 2 * current.stroke_width()),
-                        true => (2 * current.stroke_width(), current.stroke_width()),
-                    };
+            let color_curr = previous.stroke_color();
+            let color_next = current.stroke_color();
+            let mut elements = vec![(current.id(), "stroke")];
+            if !current.bidirect() {
+                elements.push((format!("bi{}", current.id()), "fill"));
+            }
+            for (element, attribute) in elements {
                 svg.push_str(&format!(
-                    "<animate href=\"#{}\" attributeName=\"stroke-width\" ",
-                    current.id()
+                    "<animate href=\"#{}\" attributeName=\"{}\" ",
+                    element, attribute
+                ));
+                svg.push_str(&format!(
+                    "from=\"rgb({},{},{})\" to=\"rgb({},{},{})\" ",
+                    color_curr.r(),
+                    color_curr.g(),
+                    color_curr.b(),
+                    color_next.r(),
+                    color_next.g(),
+                    color_next.b()
                 ));
-                svg.push_str(&format!("from=\"{}\" to=\"{}\" ", width_curr, width_next));
                 svg.push_str(&format!(
                     "dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>\n",
                     duration, start_time
                 ));
             }
+        }
 
-            let color_curr = previous.stroke_color();
-            let color_next = current.stroke_color();
-            svg.push_str(&format!(
-                "<animate href=\"#{}\" attributeName=\"stroke\" ",
-                current.id()
-            ));
-            svg.push_str(&format!(
-                "from=\"rgb({},{},{})\" to=\"rgb({},{},{})\" ",
-                color_curr.r(),
-                color_curr.g(),
-                color_curr.b(),
-                color_next.r(),
-                color_next.g(),
-                color_next.b()
-            ));
+        svg
+    }
+
+    /// Returns the captions overlay, above the graph: each caption is shown from its start until the next one.
+    /// Timing is made of SMIL elements when `smil` is true, otherwise it is left to the script player.
+    pub fn instantiate_captions(
+        &self,
+        captions: &[Caption],
+        origin: Point,
+        moves: &[(u32, u32, Point, Point)],
+        color: Color,
+        smil: bool,
+    ) -> String {
+        let mut svg = String::new();
+
+        let first = match captions.first() {
+            Some(caption) => caption,
+            None => return svg,
+        };
+        svg.push_str(&format!(
+            "<g id=\"g{}\" transform=\"translate({} {})\">\n",
+            first.id(),
+            origin.x(),
+            origin.y()
+        ));
+        if smil {
+            for (start_time, duration, from, to) in moves {
+                svg.push_str(&format!(
+                    "  <animateTransform attributeName=\"transform\" type=\"translate\" from=\"{} {}\" to=\"{} {}\" ",
+                    from.x(),
+                    from.y(),
+                    to.x(),
+                    to.y()
+                ));
+                svg.push_str(&format!(
+                    "begin=\"{}ms\" dur=\"{}ms\" fill=\"freeze\" />\n",
+                    start_time, duration
+                ));
+            }
+        }
+
+        for (i, caption) in captions.iter().enumerate() {
             svg.push_str(&format!(
-                "dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>\n",
-                duration, start_time
+                "  <text id=\"{}\" x=\"0\" y=\"0\" fill=\"rgb({},{},{})\" opacity=\"0\">",
+                caption.id(),
+                color.r(),
+                color.g(),
+                color.b()
             ));
+            if smil {
+                let end = match captions.get(i + 1) {
+                    Some(next) => format!("dur=\"{}ms\"", next.start() - caption.start()),
+                    None => "fill=\"freeze\"".to_string(),
+                };
+                svg.push_str(&format!(
+                    "<set attributeName=\"opacity\" to=\"1\" begin=\"{}ms\" {} />",
+                    caption.start(),
+                    end
+                ));
+            }
+            svg.push_str(&format!("{}</text>\n", escape(caption.text())));
+        }
+        svg.push_str("</g>\n");
+
+        svg
+    }
+
+    /// Returns a node badge or link label, with its SMIL animations when `smil` is true.
+    pub fn instantiate_badge(&self, badge: &Badge, smil: bool) -> String {
+        let mut svg = String::new();
+
+        svg.push_str(&format!(
+            "<text id=\"{}\" x=\"{}\" y=\"{}\"{} fill=\"rgb({},{},{})\" opacity=\"0\">",
+            badge.id(),
+            badge.position().x(),
+            badge.position().y(),
+            match badge.small() {
+                true => format!(" font-size=\"{}\"", self.p_radius_node),
+                false => String::new(),
+            },
+            badge.color().r(),
+            badge.color().g(),
+            badge.color().b()
+        ));
+        if smil {
+            let mut fades = vec![(badge.appear(), 0, 1)];
+            if let Some(disappear) = badge.disappear() {
+                fades.push((disappear, 1, 0));
+            }
+            for ((start_time, duration), opacity_curr, opacity_next) in fades {
+                svg.push_str(&format!(
+                    "\n  <animate attributeName=\"opacity\" from=\"{}\" to=\"{}\" dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>",
+                    opacity_curr, opacity_next, duration, start_time
+                ));
+            }
+            for (start_time, duration, from, to) in badge.moves() {
+                for (attribute, from, to) in [("x", from.x(), to.x()), ("y", from.y(), to.y())] {
+                    svg.push_str(&format!(
+                        "\n  <animate attributeName=\"{}\" from=\"{}\" to=\"{}\" dur=\"{}ms\" begin=\"{}ms\" fill=\"freeze\"/>",
+                        attribute, from, to, duration, start_time
+                    ));
+                }
+            }
+            svg.push('\n');
         }
+        svg.push_str(&format!("{}</text>\n", escape(badge.text())));
 
         svg
     }
 }
+
+/// Returns the path data of link coordinates (see `Link::path`): a loop arc or a quadratic curve.
+pub fn path_d(path: &[i32]) -> String {
+    match path {
+        [x1, y1, r, x2, y2] => format!("M{} {} A{} {} 0 1 1 {} {}", x1, y1, r, r, x2, y2),
+        _ => format!(
+            "M{} {} Q{} {} {} {}",
+            path[0], path[1], path[2], path[3], path[4], path[5]
+        ),
+    }
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
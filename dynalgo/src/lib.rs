@@ -4,9 +4,13 @@
 //!
 //! The library focuces on providing a convenient tiny API for making animations in SVG SMIL format when developping algorithms working with graph structures.
 //!
-//! The crate offers a basic `graph` structure representation. Interesting point is that each graph structure modification results in an animation rendered in SVG SMIL format into a HTML page. Several graphs animations can be rendered together in the same HTML page (side to side).
+//! The crate offers a basic `graph` structure representation. Interesting point is that each graph structure modification results in an animation rendered in SVG SMIL format into a HTML page. Several graphs animations can be rendered together in the same HTML page (side to side), each with a player to play, pause, step backward and forward, scrub the timeline and change the speed. An animation can also be exported as a standalone SVG document (`Graph::to_svg`, `Graph::render_svg`) to embed it in slides or documents, or rasterized into PNG frames and animated GIF (`Graph::render_png_frames`, `Graph::render_gif`). For viewers without SMIL support, the same timeline can be rendered as a compact JSON timeline played by a tiny embedded script (`AnimationFormat::Script` with `Graph::render_as`, `Graph::to_svg_as`).
 //!
-//! Dynalgo automatically layout nodes according to imaginary springs forces applying to them.
+//! Nodes are identified by a `char` by default (`Graph::new()`), or by any other `NodeId` type such as `String` for readable labels (`Graph::<String>::default()`). Links values are `i64` by default, or any other `Weight` type such as `f64` (`Graph::<char, f64>::default()`).
+//! Graphs can also be read from and written to Graphviz DOT, GraphML, JSON and CSV edge lists (see `GraphFormat`, `Graph::from_str_as` and `Graph::to_string_as`).
+//!
+//! Dynalgo automatically layout nodes according to imaginary springs forces applying to them, or with another algorithm of the `layout` module (Fruchterman-Reingold, Kamada-Kawai, circular, grid or Sugiyama layered layouts, see `Graph::set_layout`).
+//! Trees can be drawn as tidy (Reingold-Tilford), radial or dendrogram layouts from a root node (see `Tree::tidy_layout`, `Tree::radial_layout` and `Tree::dendrogram_layout`).
 //! Custom animations can be made by playing with the nodes and links  graphical representations.
 //!
 //! The `Algo` module provides animated algorithms applying to graph.
@@ -291,13 +295,17 @@
 //! .unwrap();
 //! ```
 
+// the maze example keeps its tab indented configuration
+#![allow(clippy::tabs_in_doc_comments)]
+
 pub mod algo;
 pub mod graph;
 
 #[cfg(test)]
 mod tests {
 
-    use crate::graph::Graph;
+    use crate::graph::layout::{Circular, FruchtermanReingold, Grid, KamadaKawai, Sugiyama};
+    use crate::graph::{AnimationFormat, Graph, GraphError, Shape, Style};
 
     #[test]
     fn it_works() {
@@ -385,4 +393,1135 @@ mod tests {
         other_graph.sleep(2000);
         other_graph.fill_node('A', (128, 0, 0));
     }
+
+    #[test]
+    fn try_api_returns_errors() {
+        let mut graph = Graph::new();
+        graph.try_from_str("A, B, A > B 1").unwrap();
+
+        assert_eq!(
+            graph.try_add_node('A', None),
+            Err(GraphError::NodeExists('A'))
+        );
+        assert_eq!(
+            graph.try_add_link('A', 'C', true, 0),
+            Err(GraphError::NodeMissing('C'))
+        );
+        assert_eq!(
+            graph.try_add_link('B', 'A', true, 0),
+            Err(GraphError::LinkExists('A', 'B'))
+        );
+        assert_eq!(
+            graph.try_add_link('A', 'A', true, 0),
+            Err(GraphError::LoopLink('A'))
+        );
+        assert_eq!(
+            graph.try_delete_link('B', 'A'),
+            Err(GraphError::LinkMissing('B', 'A'))
+        );
+        assert!(matches!(
+            graph.try_resume(),
+            Err(GraphError::AnimationStateError(_))
+        ));
+        assert!(matches!(
+            graph.try_step(100),
+            Err(GraphError::AnimationStateError(_))
+        ));
+
+        assert_eq!(
+            "A, B\n  A - BC 2".parse::<Graph>().unwrap_err(),
+            GraphError::ParseError {
+                line: 2,
+                column: 3,
+                message: String::from("'BC' is an invalid node name"),
+            }
+        );
+        assert!(matches!(
+            "A, B, A - B x".parse::<Graph>(),
+            Err(GraphError::ParseError {
+                line: 1,
+                column: 7,
+                ..
+            })
+        ));
+        assert!(matches!(
+            "A, A".parse::<Graph>(),
+            Err(GraphError::ParseError {
+                line: 1,
+                column: 4,
+                ..
+            })
+        ));
+        assert!(!graph.paused());
+    }
+
+    #[test]
+    fn string_nodes() {
+        use crate::algo::connectivity::Connectivity;
+        use crate::algo::tree::Tree;
+
+        let mut graph: Graph<String> = "Paris 0 0, Lyon 100 100, Lille, Nice, <v&42>
+            Paris - Lyon 4, Paris - Lille 2, Lyon > Nice 3, <v&42> - Nice"
+            .parse()
+            .unwrap();
+        assert_eq!(graph.nodes().len(), 5);
+        assert_eq!(
+            graph.neighbors(String::from("Paris")),
+            vec![String::from("Lille"), String::from("Lyon")]
+        );
+        assert_eq!(
+            graph.try_add_node(String::from("Lyon"), None),
+            Err(GraphError::NodeExists(String::from("Lyon")))
+        );
+        graph.swap_nodes(String::from("Lille"), String::from("Nice"));
+
+        let (_, components) = Connectivity::components(&graph);
+        assert_eq!(components.len(), 1);
+        Tree::bfs_tree(&graph, String::from("Paris"));
+
+        let copy: Graph<String> = graph.to_string().parse().unwrap();
+        assert_eq!(copy.to_string(), graph.to_string());
+    }
+
+    #[test]
+    fn wide_weights() {
+        use crate::algo::tree::Tree;
+
+        let graph: Graph = "A, B, C, A - B 300, B - C 70000, A > C".parse().unwrap();
+        assert_eq!(graph.adjacency_list()[&'B'][&'C'], 70000);
+        assert!(graph.to_string().contains("A - B 300"));
+
+        let graph: Graph<char, f64> = "A, B, C, A - B 2.5, B - C 0.25, A - C 1.5".parse().unwrap();
+        let mst = Tree::minimal_spanning_tree(&graph);
+        assert_eq!(mst.links(), vec![('A', 'C'), ('B', 'C')]);
+        assert!(matches!(
+            "A, B, A - B 1.5".parse::<Graph>(),
+            Err(GraphError::ParseError { .. })
+        ));
+    }
+
+    #[test]
+    fn shortest_paths() {
+        use crate::algo::shortest_path::ShortestPath;
+
+        let graph: Graph = "A 0 0, B 100 0, C 100 100, D 200 100, E 300 0,
+            A - B 100, A - C 150, B - C 100, B > D 250, C - D 100, D - E 150"
+            .parse()
+            .unwrap();
+
+        let (_, distances, predecessors) = ShortestPath::dijkstra(&graph, 'A').unwrap();
+        assert_eq!(distances[&'D'], 250);
+        assert_eq!(distances[&'E'], 400);
+        assert_eq!(
+            ShortestPath::path(&predecessors, 'A', 'E'),
+            vec!['A', 'C', 'D', 'E']
+        );
+
+        let (_, distances, predecessors) = ShortestPath::a_star(&graph, 'A', 'E').unwrap();
+        assert_eq!(distances[&'E'], 400);
+        assert_eq!(
+            ShortestPath::path(&predecessors, 'A', 'E'),
+            vec!['A', 'C', 'D', 'E']
+        );
+
+        assert_eq!(ShortestPath::path(&predecessors, 'A', 'A'), vec!['A']);
+
+        let graph: Graph = "A 0 0, B 100 0, C 200 0, D, A > B 100, C > D 100"
+            .parse()
+            .unwrap();
+        let (_, _, predecessors) = ShortestPath::dijkstra(&graph, 'A').unwrap();
+        assert_eq!(ShortestPath::path(&predecessors, 'A', 'A'), vec!['A']);
+        assert_eq!(ShortestPath::path(&predecessors, 'A', 'B'), vec!['A', 'B']);
+        assert!(ShortestPath::path(&predecessors, 'A', 'D').is_empty());
+
+        // Admissible but not consistent heuristic: C is first settled through B, then reopened through A.
+        let graph: Graph = "S 1200 0, A 1100 0, B 0 500, C 500 0, T 0 0,
+            S - A 100, A - C 100, S - B 300, B - C 100, C - T 1000"
+            .parse()
+            .unwrap();
+        let (_, distances, predecessors) = ShortestPath::a_star(&graph, 'S', 'T').unwrap();
+        assert_eq!(distances[&'T'], 1200);
+        assert_eq!(
+            ShortestPath::path(&predecessors, 'S', 'T'),
+            vec!['S', 'A', 'C', 'T']
+        );
+
+        let graph: Graph = "A 0 0, B 100 0, C 100 100, D 200 100, E 300 0,
+            A - B 100, A - C 150, B - C 100, B > D 250, C - D 100, D - E 150"
+            .parse()
+            .unwrap();
+        let (_, distances, _, cycle) = ShortestPath::bellman_ford(&graph, 'A').unwrap();
+        assert!(cycle.is_empty());
+        assert_eq!(distances[&'E'], 400);
+
+        let graph: Graph = "A, B, C, D, A > B 4, B > C -2, C > D 1, D > B -1"
+            .parse()
+            .unwrap();
+        let (_, _, _, cycle) = ShortestPath::bellman_ford(&graph, 'A').unwrap();
+        assert_eq!(cycle.len(), 4);
+        assert_eq!(cycle.first(), cycle.last());
+        for node in ['B', 'C', 'D'] {
+            assert!(cycle.contains(&node));
+        }
+
+        assert!(matches!(
+            ShortestPath::dijkstra(&graph, 'A'),
+            Err(GraphError::NegativeValue('B', 'C'))
+        ));
+        assert!(matches!(
+            ShortestPath::a_star(&graph, 'A', 'Z'),
+            Err(GraphError::NodeMissing('Z'))
+        ));
+        assert!(matches!(
+            ShortestPath::bellman_ford(&graph, 'Z'),
+            Err(GraphError::NodeMissing('Z'))
+        ));
+    }
+
+    #[test]
+    fn max_flows() {
+        use crate::algo::flow::Flow;
+
+        let graph: Graph = "S, A, B, C, D, T,
+            S > A 16, S > B 13, A > C 12, B > A 4, B > D 14,
+            C > B 9, C > T 20, D > C 7, D > T 4"
+            .parse()
+            .unwrap();
+
+        let (_, max_flow, flows, (source_side, sink_side)) =
+            Flow::edmonds_karp(&graph, 'S', 'T').unwrap();
+        assert_eq!(max_flow, 23);
+        assert_eq!(flows[&('C', 'T')] + flows[&('D', 'T')], 23);
+        assert_eq!(source_side, vec!['A', 'B', 'D', 'S']);
+        assert_eq!(sink_side, vec!['C', 'T']);
+
+        let (_, max_flow, flows, (source_side, _)) = Flow::dinic(&graph, 'S', 'T').unwrap();
+        assert_eq!(max_flow, 23);
+        assert_eq!(flows[&('S', 'A')] + flows[&('S', 'B')], 23);
+        assert_eq!(source_side, vec!['A', 'B', 'D', 'S']);
+
+        assert!(matches!(
+            Flow::dinic(&graph, 'S', 'S'),
+            Err(GraphError::AlgorithmError(_))
+        ));
+        assert!(matches!(
+            Flow::edmonds_karp(&graph, 'S', 'Z'),
+            Err(GraphError::NodeMissing('Z'))
+        ));
+        let graph: Graph = "S, T, S > T -1".parse().unwrap();
+        assert!(matches!(
+            Flow::edmonds_karp(&graph, 'S', 'T'),
+            Err(GraphError::NegativeValue('S', 'T'))
+        ));
+
+        let graph: Graph<char, u32> = "S, A, B, T, S - A 3, S - B 2, A - B 4, A - T 1, B - T 5"
+            .parse()
+            .unwrap();
+        for (_, max_flow, flows, _) in [
+            Flow::edmonds_karp(&graph, 'S', 'T').unwrap(),
+            Flow::dinic(&graph, 'S', 'T').unwrap(),
+        ] {
+            assert_eq!(max_flow, 5);
+            assert_eq!(flows[&('A', 'B')], 2);
+            assert!(!flows.contains_key(&('B', 'A')));
+            assert_eq!(flows[&('A', 'T')] + flows[&('B', 'T')], 5);
+        }
+    }
+
+    #[test]
+    fn dags() {
+        use crate::algo::dag::Dag;
+
+        let graph: Graph = "A, B, C, D, E,
+            A > B 3, A > C 2, B > D 4, C > D 1, D > E 2, C > E 9"
+            .parse()
+            .unwrap();
+
+        let (_, order) = Dag::kahn(&graph).unwrap();
+        assert_eq!(order, vec!['A', 'B', 'C', 'D', 'E']);
+        let (_, order) = Dag::dfs_sort(&graph).unwrap();
+        assert_eq!(order, vec!['A', 'C', 'B', 'D', 'E']);
+
+        let (_, cycle) = Dag::cycle(&graph).unwrap();
+        assert!(cycle.is_empty());
+
+        let (_, distances, path) = Dag::longest_path(&graph).unwrap();
+        assert_eq!(distances[&'E'], 11);
+        assert_eq!(path, vec!['A', 'C', 'E']);
+
+        let (_, layers) = Dag::layers(&graph).unwrap();
+        assert_eq!(
+            layers,
+            vec![vec!['A'], vec!['B', 'C'], vec!['D'], vec!['E']]
+        );
+
+        let graph: Graph = "A, B, C, D, A > B, B > C, C > D, D > B".parse().unwrap();
+        let (_, order) = Dag::kahn(&graph).unwrap();
+        assert_eq!(order, vec!['A']);
+        let (_, cycle) = Dag::cycle(&graph).unwrap();
+        assert_eq!(cycle, vec!['B', 'C', 'D', 'B']);
+        assert!(matches!(
+            Dag::longest_path(&graph),
+            Err(GraphError::AlgorithmError(_))
+        ));
+        assert!(matches!(
+            Dag::layers(&graph),
+            Err(GraphError::AlgorithmError(_))
+        ));
+
+        let graph: Graph = "A, B, A - B".parse().unwrap();
+        assert!(matches!(
+            Dag::kahn(&graph),
+            Err(GraphError::AlgorithmError(_))
+        ));
+    }
+
+    #[test]
+    fn matchings() {
+        use crate::algo::matching::Matching;
+
+        let graph: Graph = "A, B, C, D, E, F,
+            A - D 7, A - E 3, B - D 5, B - F 4, C - E 8, C - F 1"
+            .parse()
+            .unwrap();
+
+        let (_, sides) = Matching::bipartite(&graph);
+        assert_eq!(sides, Some((vec!['A', 'B', 'C'], vec!['D', 'E', 'F'])));
+
+        let graph: Graph = "A, B, C, D, E, F,
+            A - D 7, A - E 3, B - D 5, B - F 4, C - E 8, C - F 6"
+            .parse()
+            .unwrap();
+        let (_, matching) = Matching::hopcroft_karp(&graph).unwrap();
+        assert_eq!(matching.len(), 3);
+
+        let (_, total, matching) = Matching::hungarian(&graph).unwrap();
+        assert_eq!(total, 19);
+        assert_eq!(matching, vec![('A', 'D'), ('B', 'F'), ('C', 'E')]);
+
+        let graph: Graph = "A, B, C, A - B, B - C, C - A".parse().unwrap();
+        let (_, sides) = Matching::bipartite(&graph);
+        assert!(sides.is_none());
+        assert!(matches!(
+            Matching::hopcroft_karp(&graph),
+            Err(GraphError::AlgorithmError(_))
+        ));
+        assert!(matches!(
+            Matching::hungarian(&graph),
+            Err(GraphError::AlgorithmError(_))
+        ));
+    }
+
+    #[test]
+    fn colorations() {
+        use crate::algo::coloration::Coloration;
+        use crate::graph::GraphFormat;
+        use std::collections::BTreeSet;
+
+        let graph: Graph = "A, B, C, D, E, F,
+            A - B, A - C, B - C, C - D, D - E, E - F, F - A"
+            .parse()
+            .unwrap();
+
+        let (_, count, partitions) = Coloration::dsatur(&graph);
+        assert_eq!(count, 3);
+        assert_eq!(partitions.iter().flatten().count(), 6);
+        let (_, count, _) = Coloration::welsh_powell(&graph);
+        assert_eq!(count, 3);
+        let (_, count, partitions) = Coloration::backtracking(&graph);
+        assert_eq!(count, 3);
+        for partition in &partitions {
+            for node_from in partition {
+                for node_to in partition {
+                    assert!(!graph.neighbors(*node_from).contains(node_to));
+                }
+            }
+        }
+
+        let (_, count, partitions) = Coloration::edge_coloring(&graph);
+        assert_eq!(count, 3);
+        assert_eq!(partitions.iter().flatten().count(), 7);
+
+        let nodes = "ABCDEFGHI";
+        let mut config = nodes.chars().map(String::from).collect::<Vec<_>>();
+        for (i, node_from) in nodes.chars().enumerate() {
+            for node_to in nodes.chars().skip(i + 1) {
+                config.push(format!("{} - {}", node_from, node_to));
+            }
+        }
+        let graph: Graph = config.join(", ").parse().unwrap();
+        let (cg, count, _) = Coloration::dsatur(&graph);
+        assert_eq!(count, 9);
+        let dot = cg.to_string_as(GraphFormat::Dot);
+        let fills: BTreeSet<&str> = dot
+            .split("fillcolor=")
+            .skip(1)
+            .map(|attr| &attr[..9])
+            .collect();
+        assert_eq!(fills.len(), 9);
+    }
+
+    #[test]
+    fn biconnectivity() {
+        use crate::algo::connectivity::Connectivity;
+
+        let graph: Graph = "A, B, C, D, E, F, G,
+            A - B, B - C, C - A, C - D, D - E, E - F, F - D, F - G"
+            .parse()
+            .unwrap();
+
+        let (_, points) = Connectivity::articulation_points(&graph);
+        assert_eq!(points, vec!['C', 'D', 'F']);
+
+        let (_, bridges) = Connectivity::bridges(&graph);
+        assert_eq!(bridges.len(), 2);
+        for (node_from, node_to) in bridges {
+            assert!([('C', 'D'), ('F', 'G')]
+                .contains(&(node_from.min(node_to), node_from.max(node_to))));
+        }
+
+        let (_, mut components) = Connectivity::biconnected_components(&graph);
+        components.sort();
+        assert_eq!(
+            components,
+            vec![
+                vec!['A', 'B', 'C'],
+                vec!['C', 'D'],
+                vec!['D', 'E', 'F'],
+                vec!['F', 'G']
+            ]
+        );
+
+        let graph: Graph = "A, B, C, A - B".parse().unwrap();
+        let (_, components) = Connectivity::biconnected_components(&graph);
+        assert_eq!(components, vec![vec!['A', 'B'], vec!['C']]);
+    }
+
+    #[test]
+    fn standalone_svg() {
+        let mut graph: Graph = "A, B, C, A - B 3, B > C".parse().unwrap();
+        graph.color_node('A', (255, 0, 0));
+
+        let svg = graph.to_svg();
+        assert!(svg.starts_with("<?xml"));
+        assert!(svg.contains("xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.contains(" width=\"") && svg.contains(" height=\""));
+        assert!(svg.contains("onclick=\"pause(this)\""));
+        assert!(svg.contains("function pause(svg)"));
+        assert!(svg.trim_end().ends_with("</svg>"));
+
+        graph.pause_on_click(false);
+        let svg = graph.to_svg();
+        assert!(!svg.contains("onclick") && !svg.contains("<script>"));
+    }
+
+    #[test]
+    fn raster_frames() {
+        let mut graph: Graph = "A, B, C, A - B 3, B > C".parse().unwrap();
+        graph.color_node('A', (255, 0, 0));
+        graph.delete_node('C');
+
+        let frames = graph.to_png_frames(10);
+        assert_eq!(frames.len() as u32, graph.duration() * 10 / 1000 + 1);
+        for png in &frames {
+            assert!(png.starts_with(&[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]));
+            assert!(png.ends_with(&[b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+        }
+
+        let gif = graph.to_gif(10);
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(gif.last(), Some(&0x3b));
+        assert!(gif.windows(11).any(|w| w == b"NETSCAPE2.0"));
+        let delays = |gif: &[u8]| -> u32 {
+            gif.windows(9)
+                .filter(|w| w[..4] == [0x21, 0xf9, 0x04, 0x04] && w[6..] == [0, 0, 0x2c])
+                .map(|w| u16::from_le_bytes([w[4], w[5]]) as u32)
+                .sum()
+        };
+        let count = graph.duration() * 30 / 1000 + 1;
+        assert_eq!(delays(&graph.to_gif(30)), (count * 200 + 30) / 60);
+
+        assert_eq!(Graph::new().to_png_frames(10).len(), 1);
+
+        let mut graph = Graph::new();
+        graph.sleep(500);
+        assert_eq!(graph.to_png_frames(10).len(), 6);
+        assert_eq!(graph.to_png_frames(0).len(), 1);
+        assert_eq!(delays(&graph.to_gif(60)), (31 * 200 + 60) / 120);
+        assert_eq!(delays(&graph.to_gif(7)), (4 * 200 + 7) / 14);
+        graph.sleep(700_000);
+        assert!(graph.to_gif(100).starts_with(b"GIF89a"));
+        assert!(graph.to_gif(65536).starts_with(b"GIF89a"));
+    }
+
+    #[test]
+    fn script_timeline() {
+        let mut graph: Graph = "A, B, C, A - B 3, B > C".parse().unwrap();
+        graph.color_node('A', (255, 0, 0));
+        graph.delete_node('C');
+
+        let smil = graph.to_svg();
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert_eq!(smil, graph.to_svg_as(AnimationFormat::Smil));
+        assert!(smil.contains("<animate") && !smil.contains("dynalgoPlay("));
+        assert!(!script.contains("<animate") && !script.contains("onclick"));
+        assert!(script.contains("dynalgoPlay(document.currentScript.parentNode, ["));
+        assert!(script.contains("\"stroke\",[47,79,79],[255,0,0]]"));
+        assert!(script.contains("\"opacity\",[1],[0]]"));
+        assert!(script.contains("], true);"));
+        assert!(script.trim_end().ends_with("</svg>"));
+
+        graph.pause_on_click(false);
+        assert!(graph
+            .to_svg_as(AnimationFormat::Script)
+            .contains("], false);"));
+    }
+
+    #[test]
+    fn well_formed_svg() {
+        // minimal XML check: balanced tags, entities only, raw text allowed in CDATA sections
+        fn parse(document: &str) {
+            let mut stack = Vec::new();
+            let mut rest = document;
+            while let Some(i) = rest.find(['<', '&']) {
+                rest = &rest[i..];
+                if rest.starts_with('&') {
+                    assert!(["&amp;", "&lt;", "&gt;", "&quot;", "&apos;", "&#"]
+                        .iter()
+                        .any(|entity| rest.starts_with(entity)));
+                    rest = &rest[1..];
+                    continue;
+                }
+                if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                    rest = &cdata[cdata.find("]]>").unwrap() + 3..];
+                    continue;
+                }
+                let end = rest.find('>').unwrap();
+                let tag = &rest[1..end];
+                assert!(!tag.contains('<') && !tag.is_empty());
+                if let Some(name) = tag.strip_prefix('/') {
+                    assert_eq!(stack.pop(), Some(name.trim()));
+                } else if !tag.ends_with('/') && !tag.starts_with(['?', '!']) {
+                    stack.push(tag.split_whitespace().next().unwrap());
+                }
+                rest = &rest[end + 1..];
+            }
+            assert!(stack.is_empty());
+        }
+
+        let mut graph: Graph = "A, B, C, A - B 3, B > C".parse().unwrap();
+        graph.color_node('A', (255, 0, 0));
+        graph.set_node_badge('B', "1 < 2 & 3");
+        graph.caption("a < b");
+        for format in [AnimationFormat::Smil, AnimationFormat::Script] {
+            parse(&graph.to_svg_as(format));
+        }
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert!(script.contains("<script>\n<![CDATA[\n"));
+    }
+
+    #[test]
+    fn html_player() {
+        let mut graph: Graph = "A, B, A - B".parse().unwrap();
+        let start = graph.duration();
+        graph.pause();
+        graph.color_node('A', (255, 0, 0));
+        graph.step(500);
+        graph.sleep(100);
+        graph.color_node('B', (255, 0, 0));
+        graph.step(400);
+        graph.resume();
+
+        let steps = graph.step_starts();
+        assert_eq!(steps[0], 0);
+        assert_eq!(steps, vec![0, start, start + 600, start + 1000]);
+        assert_eq!(graph.duration(), start + 1001);
+
+        let file = std::env::temp_dir().join("dynalgo html player");
+        let file = file.to_str().unwrap();
+        for format in [AnimationFormat::Smil, AnimationFormat::Script] {
+            graph.render_as(file, format).unwrap();
+            let html_file = format!("{}.html", file.replace(" ", "_"));
+            let html = std::fs::read_to_string(&html_file).unwrap();
+            std::fs::remove_file(&html_file).unwrap();
+
+            let data_steps: Vec<String> = steps.iter().map(|s| s.to_string()).collect();
+            assert!(html.contains(&format!(
+                "<div class=\"player\" data-steps=\"{}\" data-duration=\"{}\" data-pause=\"true\">",
+                data_steps.join(","),
+                graph.duration()
+            )));
+            assert!(html.contains("function player(controls)"));
+        }
+    }
+
+    #[test]
+    fn captions() {
+        use crate::algo::shortest_path::ShortestPath;
+
+        let mut graph: Graph = "A, B, A - B 3".parse().unwrap();
+        let start = graph.duration();
+        graph.caption("coloring <A>");
+        graph.color_node('A', (255, 0, 0));
+        let next = graph.duration();
+        graph.caption("coloring B");
+        graph.color_node('B', (255, 0, 0));
+
+        let smil = graph.to_svg();
+        assert!(smil.contains(&format!(
+            "<set attributeName=\"opacity\" to=\"1\" begin=\"{}ms\" dur=\"{}ms\" />coloring &lt;A&gt;</text>",
+            start,
+            next - start
+        )));
+        assert!(smil.contains(&format!(
+            "<set attributeName=\"opacity\" to=\"1\" begin=\"{}ms\" fill=\"freeze\" />coloring B</text>",
+            next
+        )));
+
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert!(!script.contains("<set "));
+        assert!(script.contains(&format!("[{},0,\"t", next)));
+        assert!(script.contains("\"translate\"") || !smil.contains("animateTransform"));
+
+        let (sg, _, _) = ShortestPath::dijkstra(&graph, 'A').unwrap();
+        assert!(sg.to_svg().contains(">settling B at distance 3</text>"));
+    }
+
+    #[test]
+    fn badges() {
+        use crate::algo::flow::Flow;
+
+        let mut graph: Graph = "A 0 0, B 100 0, A - B 3".parse().unwrap();
+        let start = graph.duration();
+        graph.set_node_badge('B', "d=5");
+        let next = graph.duration();
+        graph.set_node_badge('B', "d=<3>");
+        graph.set_link_label('B', 'A', "1/3");
+        graph.set_node_badge('B', "");
+
+        let svg = graph.to_svg();
+        assert_eq!(svg.matches("d=5</text>").count(), 1);
+        assert_eq!(svg.matches("d=&lt;3&gt;</text>").count(), 1);
+        assert_eq!(svg.matches("1/3</text>").count(), 1);
+        assert!(svg.contains(&format!(
+            "<animate attributeName=\"opacity\" from=\"0\" to=\"1\" dur=\"600ms\" begin=\"{}ms\" fill=\"freeze\"/>",
+            start
+        )));
+        assert!(svg.contains(&format!(
+            "<animate attributeName=\"opacity\" from=\"1\" to=\"0\" dur=\"600ms\" begin=\"{}ms\" fill=\"freeze\"/>",
+            next
+        )));
+
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert!(!script.contains("<animate"));
+        assert!(script.contains(&format!("[{},600,\"b", next)));
+
+        let graph: Graph = "S, A, T, S > A 2, A > T 1".parse().unwrap();
+        let (fg, _, _, _) = Flow::edmonds_karp(&graph, 'S', 'T').unwrap();
+        assert!(fg.to_svg().contains(">1</text>"));
+    }
+
+    #[test]
+    fn link_values() {
+        let mut graph: Graph = "A, B, C, A - B 3, B > C 1".parse().unwrap();
+        let start = graph.duration();
+        graph.set_link_value('B', 'A', 7);
+        let next = graph.duration();
+        graph.set_link_value('B', 'C', 0);
+
+        assert_eq!(graph.to_string(), "A\nB\nC\nA - B 7\nB > C 0\n");
+        assert_eq!(graph.adjacency_list()[&'B'][&'A'], 7);
+        assert_eq!(graph.adjacency_list()[&'C'].get(&'B'), None);
+
+        let svg = graph.to_svg();
+        assert_eq!(svg.matches("7</text>").count(), 1);
+        let fade_out = format!(
+            "attributeName=\"opacity\" from=\"1\" to=\"0\" dur=\"600ms\" begin=\"{}ms\"",
+            start
+        );
+        assert!(svg.split("<animate href=\"#lib").any(|animate| animate
+            .split_once("\" ")
+            .unwrap()
+            .1
+            .starts_with(&fade_out)));
+
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert!(script.contains(&format!("[{},600,\"bv", start)));
+        assert!(script.contains(&format!("[{},600,\"lib", next)));
+    }
+
+    #[test]
+    fn multigraphs() {
+        use crate::algo::coloration::Coloration;
+        use crate::algo::connectivity::Connectivity;
+        use crate::algo::dag::Dag;
+        use crate::algo::flow::Flow;
+        use crate::algo::matching::Matching;
+        use crate::algo::shortest_path::ShortestPath;
+
+        let mut graph = Graph::new();
+        graph.multigraph(true);
+        graph.from_str("A 0 0, B 100 0, A > B 1, B > A 2, A > A");
+        graph.add_link('A', 'B', false, 3);
+        let start = graph.duration();
+        graph.delete_link('B', 'A');
+
+        assert_eq!(
+            graph.to_string(),
+            "* multigraph\nA 0 0\nB 100 0\nA > A 0\nA > B 1\nA > B 3\n"
+        );
+        let copy: Graph = graph.to_string().parse().unwrap();
+        assert_eq!(copy.to_string(), graph.to_string());
+        assert_eq!(graph.adjacency_list()[&'A'][&'B'], 1);
+        assert_eq!(graph.adjacency_list()[&'B'].get(&'A'), None);
+
+        let svg = graph.to_svg();
+        assert_eq!(svg.matches("<path id=\"l").count(), 4);
+        assert!(svg.contains("d=\"M88 6 Q50 26 12 6\""));
+        assert!(svg.contains("d=\"M-11 -7 A13 13 0 1 1 11 -7\""));
+        assert!(svg.contains("values=\"M13 0 Q50 0 87 0;M12 -6 Q50 -26 88 -6\""));
+        assert!(svg.contains(&format!(
+            "begin=\"{}ms\" fill=\"freeze\" attributeName=\"d\" dur=\"300ms\" values=\"M9 9 Q50 52 91 9;M12 6 Q50 26 88 6\"",
+            start
+        )));
+
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert!(script.contains(&format!(
+            "[{},300,\"l{}\",\"d\",[9,9,50,52,91,9],[12,6,50,26,88,6]]",
+            start,
+            svg.split("<path id=\"l")
+                .nth(1)
+                .unwrap()
+                .split('"')
+                .next()
+                .unwrap()
+        )));
+        assert!(!graph.to_png_frames(10).is_empty());
+
+        let mut graph = Graph::new();
+        graph.multigraph(true);
+        graph.from_str("A, B, C, A > B 5, A > B 1, B > B 2, B > C 1, A > C 9");
+        let (_, distances, _) = ShortestPath::dijkstra(&graph, 'A').unwrap();
+        assert_eq!(distances[&'C'], 2);
+        let (_, distances, _, _) = ShortestPath::bellman_ford(&graph, 'A').unwrap();
+        assert_eq!(distances[&'C'], 2);
+        let (_, total, _, _) = Flow::edmonds_karp(&graph, 'A', 'C').unwrap();
+        assert_eq!(total, 10);
+        let (_, order) = Dag::dfs_sort(&graph).unwrap();
+        assert_eq!(order.len(), 3);
+
+        let mut graph = Graph::new();
+        graph.from_str("* multigraph, A, B, C, A > B, A > B, B > C");
+        let (_, mut components) = Connectivity::strongly_connected_components(&graph);
+        components.sort();
+        assert_eq!(components, vec![vec!['A'], vec!['B'], vec!['C']]);
+
+        let mut graph = Graph::new();
+        graph.from_str("* multigraph, A, B, C, A > B, B > A, B > C");
+        let (mg, sides) = Matching::bipartite(&graph);
+        assert!(!mg.directed());
+        assert_eq!(mg.link_entries().len(), 3);
+        assert!(sides.is_some());
+
+        let mut graph = Graph::new();
+        graph.from_str("* multigraph, A, B, C, A - B, A - B, B - C");
+        let (_, bridges) = Connectivity::bridges(&graph);
+        assert_eq!(bridges, vec![('B', 'C')]);
+        let (_, count, partitions) = Coloration::edge_coloring(&graph);
+        assert_eq!(count, 3);
+        assert_eq!(partitions.iter().flatten().count(), 3);
+    }
+
+    #[test]
+    fn arrowheads() {
+        let mut graph = Graph::new();
+        graph.from_str("A 0 0, B 100 0, C 0 100, A > B, A - C");
+        graph.curve_links(true);
+        graph.color_link('A', 'B', (255, 0, 0));
+
+        let svg = graph.to_svg();
+        assert!(!svg.contains("<textpath"));
+        assert_eq!(svg.matches("marker-end=").count(), 1);
+        assert!(svg.contains("<marker id=\"mkl"));
+        assert!(svg.contains("values=\"M13 0 Q50 0 87 0;M12 6 Q50 26 88 6\""));
+        assert!(svg.contains("values=\"M0 13 Q0 50 0 87;M-6 12 Q-26 50 -6 88\""));
+        assert!(svg.contains("attributeName=\"fill\" from=\"rgb(47,79,79)\" to=\"rgb(255,0,0)\""));
+
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert!(script.contains("\"fill\",[47,79,79],[255,0,0]]"));
+
+        let duration = graph.duration();
+        graph.curve_links(true);
+        assert_eq!(graph.duration(), duration);
+    }
+
+    #[test]
+    fn styles() {
+        let mut style = Style::dark();
+        style.node_radius = 20;
+        style.node_stroke_width = 3;
+        style.font_family = Some(String::from("monospace"));
+        style.node_shape = Shape::Square;
+        style.node_shapes.insert('B', Shape::Diamond);
+        style.node_shapes.insert('C', Shape::Circle);
+        let mut graph: Graph = Graph::with_style(style);
+        graph.from_str("A 0 0, B 100 0, C 0 100, A - B, A - C");
+        graph.move_node('A', (50, 50));
+
+        assert_eq!(graph.node_radius(), 20);
+        let svg = graph.to_svg();
+        assert!(svg.contains("<rect id=\"cn"));
+        assert!(svg.contains("width=\"36\" height=\"36\""));
+        assert_eq!(svg.matches("transform=\"rotate(45 100 0)\"").count(), 1);
+        assert_eq!(svg.matches("<circle id=\"cn").count(), 1);
+        assert!(svg.contains("stroke=\"rgb(160,190,190)\" stroke-width=\"3\""));
+        assert!(svg.contains("font-family=\"monospace\""));
+        assert!(svg.contains("background-color: rgb(30,30,30)"));
+
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert!(script.contains("tagName === 'rect'"));
+
+        let mut light = Graph::new();
+        light.from_str("A 0 0, B 100 0, C 0 100, A - B, A - C");
+        light.move_node('A', (50, 50));
+        assert_ne!(graph.to_png_frames(1)[0], light.to_png_frames(1)[0]);
+
+        use crate::algo::flow::Flow;
+        use crate::graph::layout::Grid;
+        let mut graph: Graph = Graph::with_style(graph.style().clone());
+        graph.set_layout(Grid { columns: Some(1) });
+        graph.hide_labels(true);
+        graph.from_str("A, B, C, A - B, A - C");
+        let (fg, _, _, _) = Flow::dinic(&graph, 'B', 'C').unwrap();
+        assert_eq!(fg.style(), graph.style());
+        assert_eq!(fg.node_radius(), 20);
+        assert_eq!(fg.node_position('B').0, fg.node_position('C').0);
+        assert_eq!(fg.to_svg().matches(">B</text>").count(), 0);
+    }
+
+    #[test]
+    fn sizes() {
+        let mut graph = Graph::new();
+        graph.multigraph(true);
+        graph.from_str("A 0 0, B 100 0, A > B, A > A");
+        graph.resize_node('B', 26);
+        graph.resize_node('A', 40);
+        graph.set_link_width('A', 'B', 6);
+        graph.color_link('A', 'B', (255, 0, 0));
+
+        let svg = graph.to_svg();
+        assert!(svg.contains("attributeName=\"r\" from=\"13\" to=\"26\""));
+        assert!(svg.contains("values=\"M13 0 Q50 0 87 0;M13 0 Q50 0 74 0\""));
+        assert!(svg.contains("attributeName=\"stroke-width\" from=\"2\" to=\"6\""));
+        assert!(svg.contains("attributeName=\"stroke-width\" from=\"6\" to=\"12\""));
+
+        let script = graph.to_svg_as(AnimationFormat::Script);
+        assert!(script.contains("\"r\",[13],[26]]"));
+        assert!(script.contains("\"stroke-width\",[2],[6]]"));
+
+        let style = Style {
+            node_stroke_width: 200,
+            ..Style::default()
+        };
+        let mut graph: Graph = Graph::with_style(style);
+        graph.from_str("A 0 0, B 100 0, A - B");
+        graph.set_link_width('A', 'B', 200);
+        graph.color_link('A', 'B', (255, 0, 0));
+        graph.color_node('A', (255, 0, 0));
+        assert_eq!(
+            graph
+                .to_svg()
+                .matches("attributeName=\"stroke-width\" from=\"200\" to=\"400\"")
+                .count(),
+            2
+        );
+
+        let style = Style {
+            node_shape: Shape::Square,
+            ..Style::default()
+        };
+        let mut graph: Graph = Graph::with_style(style);
+        graph.from_str("A, B, C, D, A - B, B - C");
+        for node in ['A', 'B', 'C'] {
+            graph.resize_node(node, 30);
+        }
+        assert!(graph
+            .to_svg()
+            .contains("attributeName=\"width\" from=\"24\" to=\"54\""));
+        assert!(graph
+            .to_svg_as(AnimationFormat::Script)
+            .contains("\"size\",[12],[27]]"));
+
+        graph.add_node('E', None);
+        let nodes = ['A', 'B', 'C', 'D', 'E'];
+        for (i, n1) in nodes.iter().enumerate() {
+            for n2 in &nodes[i + 1..] {
+                let (x1, y1, _) = graph.node_position(*n1);
+                let (x2, y2, _) = graph.node_position(*n2);
+                let gap = match (*n1 > 'C', *n2 > 'C') {
+                    (false, false) => 60.,
+                    (false, true) => 43.,
+                    _ => 26.,
+                };
+                assert!((((x2 - x1).pow(2) + (y2 - y1).pow(2)) as f64).sqrt() >= gap);
+            }
+        }
+    }
+
+    #[test]
+    fn layouts() {
+        let mut graph = Graph::new();
+        graph.from_str("A, B, C, D, A > B, A > C, B > D, C > D");
+
+        graph.set_layout(Sugiyama);
+        assert_eq!(graph.node_position('A'), (0, -65, false));
+        assert_eq!(graph.node_position('B'), (-33, 0, false));
+        assert_eq!(graph.node_position('C'), (33, 0, false));
+        assert_eq!(graph.node_position('D'), (0, 65, false));
+
+        graph.set_layout(Circular);
+        assert_eq!(graph.node_position('A'), (0, -65, false));
+        assert_eq!(graph.node_position('B'), (65, 0, false));
+
+        graph.set_layout(Grid { columns: Some(4) });
+        assert_eq!(graph.node_position('D'), (98, 0, false));
+
+        // a cycle is layered by reversing one of its links
+        graph.add_link('D', 'A', false, 0);
+        graph.set_layout(Sugiyama);
+        assert_eq!(graph.node_position('D'), (0, 65, false));
+
+        graph.move_node('A', (500, 500));
+        for layout in [0, 1, 2] {
+            match layout {
+                0 => graph.set_layout(FruchtermanReingold::default()),
+                1 => graph.set_layout(FruchtermanReingold {
+                    theta: 0.,
+                    ..FruchtermanReingold::default()
+                }),
+                _ => graph.set_layout(KamadaKawai::default()),
+            }
+            assert_eq!(graph.node_position('A'), (500, 500, true));
+            let nodes = ['A', 'B', 'C', 'D'];
+            for (i, n1) in nodes.iter().enumerate() {
+                for n2 in &nodes[i + 1..] {
+                    let (x1, y1, _) = graph.node_position(*n1);
+                    let (x2, y2, _) = graph.node_position(*n2);
+                    assert!((((x2 - x1).pow(2) + (y2 - y1).pow(2)) as f64).sqrt() >= 38.);
+                }
+            }
+        }
+
+        // springs of unlinked nodes approximated beyond 100 nodes
+        let mut graph = Graph::<u32>::default();
+        graph.pause();
+        for node in 0..120 {
+            graph.add_node(node, None);
+        }
+        for node in 0..120 {
+            graph.add_link(node, (node + 1) % 120, true, 0);
+        }
+        graph.resume();
+        let positions: Vec<(i32, i32, bool)> =
+            (0..120).map(|node| graph.node_position(node)).collect();
+        for (i, (x1, y1, _)) in positions.iter().enumerate() {
+            for (x2, y2, _) in &positions[i + 1..] {
+                assert!((((x2 - x1).pow(2) + (y2 - y1).pow(2)) as f64).sqrt() > 26.);
+            }
+        }
+    }
+
+    #[test]
+    fn incremental_layouts() {
+        let config = "A, B, C, D, E, A - B, B - C, C - D, D - E, E - A";
+        let positions = |incremental: bool, seed: Option<u64>| {
+            let mut graph = Graph::new();
+            graph.incremental_layout(incremental);
+            graph.layout_seed(seed);
+            graph.from_str(config);
+            let before: Vec<(i32, i32, bool)> =
+                "ABCDE".chars().map(|n| graph.node_position(n)).collect();
+            graph.add_node('F', None);
+            graph.add_link('E', 'F', true, 0);
+            let after: Vec<(i32, i32, bool)> =
+                "ABCDE".chars().map(|n| graph.node_position(n)).collect();
+            (before, after)
+        };
+
+        // untouched nodes keep their position
+        let (before, after) = positions(true, None);
+        assert_eq!(before[..4], after[..4]);
+
+        // a seed gives reproducible layouts
+        for incremental in [false, true] {
+            assert_eq!(
+                positions(incremental, Some(7)),
+                positions(incremental, Some(7))
+            );
+            assert_ne!(
+                positions(incremental, Some(7)).0,
+                positions(incremental, Some(8)).0
+            );
+        }
+    }
+
+    #[test]
+    fn tree_layouts() {
+        use crate::algo::tree::Tree;
+
+        let config = "A, B, C, D, A - B, A - C, C - D";
+        let relative = |layout: fn(&mut Graph, char)| {
+            let mut graph = Graph::new();
+            graph.from_str(config);
+            let (x_a, y_a, _) = graph.node_position('A');
+            layout(&mut graph, 'A');
+            "ABCD"
+                .chars()
+                .map(|n| {
+                    let (x, y, freezed) = graph.node_position(n);
+                    (x - x_a, y - y_a, freezed)
+                })
+                .collect::<Vec<(i32, i32, bool)>>()
+        };
+
+        assert_eq!(
+            relative(Tree::tidy_layout),
+            vec![
+                (0, 0, true),
+                (-30, 60, true),
+                (30, 60, true),
+                (30, 120, true)
+            ]
+        );
+        assert_eq!(
+            relative(Tree::radial_layout),
+            vec![(0, 0, true), (60, 0, true), (-60, 0, true), (-120, 0, true)]
+        );
+        assert_eq!(
+            relative(Tree::dendrogram_layout),
+            vec![
+                (0, 0, true),
+                (-30, 120, true),
+                (30, 60, true),
+                (30, 120, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn formats() {
+        use crate::graph::GraphFormat;
+
+        let mut graph = Graph::<String>::default();
+        graph.from_str("Lille 0 -100, Paris 0 0, Lyon, Nice, Lille - Paris 2, Paris > Lyon 4");
+        graph.fill_node(String::from("Paris"), (255, 0, 0));
+        graph.color_node(String::from("Lyon"), (0, 192, 0));
+        graph.color_link(String::from("Paris"), String::from("Lyon"), (0, 0, 255));
+
+        for format in [GraphFormat::Dot, GraphFormat::GraphMl, GraphFormat::Json] {
+            let text = graph.to_string_as(format);
+            let mut copy = Graph::<String>::default();
+            copy.from_str_as(&text, format);
+            assert_eq!(copy.to_string(), graph.to_string());
+            assert_eq!(copy.to_string_as(format), text);
+        }
+        let csv = graph.to_string_as(GraphFormat::Csv);
+        assert_eq!(
+            csv,
+            "from,to,value,directed\nLille,Paris,2,false\nParis,Lyon,4,true\nNice,,,\n"
+        );
+        let copy: Graph<String> = {
+            let mut copy = Graph::default();
+            copy.from_str_as(&csv, GraphFormat::Csv);
+            copy
+        };
+        assert_eq!(copy.links(), graph.links());
+
+        let mut graph = Graph::new();
+        graph.from_str_as(
+            "# preprocessor line
+            /* comment */ strict digraph G {
+                node [shape=box]; rankdir=LR
+                subgraph cluster { A [pos=\"10,20!\"] }
+                A -> B -> \"C\" [label=5]; // comment
+                C -> A [dir=none, color=red]
+            }",
+            GraphFormat::Dot,
+        );
+        assert_eq!(
+            graph.to_string(),
+            "A 10 -20\nB\nC\nA > B 5\nA - C 0\nB > C 5\n"
+        );
+
+        let mut graph = Graph::new();
+        graph.from_str_as(
+            "strict graph { A -- B [weight=2]; B -- A [color=red]; B -- C [label=4, weight=7] }",
+            GraphFormat::Dot,
+        );
+        assert_eq!(graph.to_string(), "A\nB\nC\nA - B 2\nB - C 4\n");
+        assert!(graph
+            .to_string_as(GraphFormat::Dot)
+            .contains("A -- B [label=2, color=\"#ff0000\"]"));
+
+        let mut graph = Graph::new();
+        graph.multigraph(true);
+        graph.from_str_as(
+            "digraph { A -> B [label=1]; A -> B [label=2, color=red]; B -> A [dir=none, color=blue] }",
+            GraphFormat::Dot,
+        );
+        let text = graph.to_string_as(GraphFormat::Dot);
+        let mut copy = Graph::new();
+        copy.multigraph(true);
+        copy.from_str_as(&text, GraphFormat::Dot);
+        assert_eq!(copy.to_string_as(GraphFormat::Dot), text);
+        assert!(text.contains("A -> B [label=1];"));
+        assert!(text.contains("A -> B [label=2, color=\"#ff0000\"]"));
+        assert!(text.contains("color=\"#0000ff\""));
+
+        let mut graph = Graph::new();
+        graph.multigraph(true);
+        graph.from_str_as(
+            "<?xml version=\"1.0\"?>
+            <graphml><key id=\"d0\" for=\"edge\" attr.name=\"weight\"><default>1</default></key>
+              <graph edgedefault=\"directed\"><node id=\"A\"/><node id=\"B\"/>
+                <edge source=\"A\" target=\"B\"><data key=\"d0\">7</data></edge>
+                <edge source=\"B\" target=\"A\" directed=\"false\"/>
+              </graph></graphml>",
+            GraphFormat::GraphMl,
+        );
+        assert_eq!(graph.to_string(), "* multigraph\nA\nB\nA > B 7\nA - B 1\n");
+
+        let mut graph = Graph::new();
+        graph.from_str_as("A,B\nB,C,3,1\nD\n", GraphFormat::Csv);
+        assert_eq!(graph.to_string(), "A\nB\nC\nD\nA - B 0\nB > C 3\n");
+
+        let errors = [
+            (
+                GraphFormat::Json,
+                "{\"nodes\": [{\"id\": \"A\"},\n {\"x\": 1}]}",
+                (2, 2),
+            ),
+            (
+                GraphFormat::Json,
+                "{\"links\": [{\"from\": \"A\", \"to\": \"B\", \"value\": \"x\"}]}",
+                (1, 12),
+            ),
+            (
+                GraphFormat::GraphMl,
+                "<graphml><graph><node/></graph></graphml>",
+                (1, 17),
+            ),
+            (GraphFormat::Dot, "graph { A -> B }", (1, 11)),
+            (GraphFormat::Csv, "A,B\nB,C,1,maybe", (2, 7)),
+        ];
+        for (format, text, (l, c)) in errors {
+            let mut graph = Graph::new();
+            let error = graph.try_from_str_as(text, format).unwrap_err();
+            assert!(
+                matches!(error, GraphError::ParseError { line, column, .. } if line == l && column == c),
+                "{:?}",
+                error
+            );
+        }
+    }
 }